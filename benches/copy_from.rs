@@ -0,0 +1,123 @@
+//! Compares multi-row `INSERT` with `COPY FROM` for bulk insertion of points, run with
+//! `DATABASE_URL` pointing to a database with PostGIS.
+#[macro_use]
+extern crate diesel;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use diesel::pg::{CopyFormat, PgConnection};
+use diesel::prelude::ExecuteCopyFromDsl;
+use diesel::{Connection, RunQueryDsl};
+use dotenvy::dotenv;
+use std::env;
+
+use postgis_diesel::copy::BinaryCopy;
+use postgis_diesel::types::Point;
+
+table! {
+    use postgis_diesel::sql_types::*;
+    use diesel::sql_types::*;
+    gps_points (id) {
+        id -> Int4,
+        device_id -> Int4,
+        location -> Geometry,
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = gps_points, treat_none_as_default_value = false)]
+struct NewGpsPoint {
+    device_id: i32,
+    location: Point,
+}
+
+// Postgres accepts at most 65535 bind parameters per statement.
+const INSERT_CHUNK: usize = 10_000;
+
+fn establish_connection() -> PgConnection {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL not set");
+    let mut conn = PgConnection::establish(&database_url)
+        .unwrap_or_else(|_| panic!("Error connecting to {}", database_url));
+    diesel::sql_query("CREATE EXTENSION IF NOT EXISTS postgis")
+        .execute(&mut conn)
+        .unwrap();
+    diesel::sql_query(
+        "CREATE TEMPORARY TABLE gps_points
+(
+    id                SERIAL PRIMARY KEY,
+    device_id         integer NOT NULL DEFAULT 0,
+    location          geometry(Point,4326) NOT NULL
+)",
+    )
+    .execute(&mut conn)
+    .unwrap();
+    conn
+}
+
+fn new_gps_point(i: usize) -> NewGpsPoint {
+    let offset = i as f64 / 1000.0;
+    NewGpsPoint {
+        device_id: (i % 100) as i32,
+        location: Point::new(13.0 + offset, 52.0 + offset, Some(4326)),
+    }
+}
+
+fn truncate(conn: &mut PgConnection) {
+    diesel::sql_query("TRUNCATE gps_points")
+        .execute(conn)
+        .unwrap();
+}
+
+fn bulk_insert(c: &mut Criterion) {
+    let mut conn = establish_connection();
+    let mut group = c.benchmark_group("bulk_insert");
+    group.sample_size(10);
+    for count in [1_000, 100_000] {
+        let points: Vec<NewGpsPoint> = (0..count).map(new_gps_point).collect();
+        group.throughput(Throughput::Elements(count as u64));
+        group.bench_with_input(BenchmarkId::new("insert", count), &points, |b, points| {
+            b.iter(|| {
+                truncate(&mut conn);
+                for chunk in points.chunks(INSERT_CHUNK) {
+                    diesel::insert_into(gps_points::table)
+                        .values(chunk)
+                        .execute(&mut conn)
+                        .unwrap();
+                }
+            })
+        });
+        group.bench_with_input(
+            BenchmarkId::new("copy_from_insertable", count),
+            &points,
+            |b, points| {
+                b.iter(|| {
+                    truncate(&mut conn);
+                    diesel::copy_from(gps_points::table)
+                        .from_insertable(points)
+                        .execute(&mut conn)
+                        .unwrap();
+                })
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("copy_from_iterator", count),
+            &count,
+            |b, &count| {
+                b.iter(|| {
+                    truncate(&mut conn);
+                    // only the geometries are written, the device_id takes its default
+                    let rows = BinaryCopy::new((0..count).map(|i| (new_gps_point(i).location,)));
+                    diesel::copy_from(gps_points::table)
+                        .from_raw_data((gps_points::location,), |out| rows.write_to(out))
+                        .with_format(CopyFormat::Binary)
+                        .execute(&mut conn)
+                        .unwrap();
+                })
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bulk_insert);
+criterion_main!(benches);
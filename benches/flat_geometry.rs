@@ -0,0 +1,50 @@
+//! Compares the EWKB decoding and encoding of a large polygon as `Polygon<Point>` and as
+//! `FlatGeometry`.
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use postgis_diesel::types::{EwkbBuf, FlatGeometry, Point, Polygon};
+
+fn new_polygon(points: u32) -> Polygon<Point> {
+    let mut polygon = Polygon::new(Some(4326));
+    polygon
+        .add_points((0..points).map(|i| {
+            let angle = f64::from(i) / f64::from(points) * std::f64::consts::TAU;
+            Point::new(angle.cos(), angle.sin(), Some(4326))
+        }))
+        .unwrap();
+    polygon.add_point(Point::new(1.0, 0.0, Some(4326))).unwrap();
+    polygon
+}
+
+fn flat_geometry(c: &mut Criterion) {
+    for points in [1_000, 100_000] {
+        let polygon = new_polygon(points);
+        let ewkb = EwkbBuf::from_geometry(&polygon).unwrap();
+        let flat: FlatGeometry = ewkb.decode().unwrap();
+
+        let mut group = c.benchmark_group("decode_polygon");
+        group.throughput(Throughput::Elements(u64::from(points)));
+        group.bench_with_input(BenchmarkId::new("polygon", points), &ewkb, |b, ewkb| {
+            b.iter(|| ewkb.decode::<Polygon<Point>>().unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("flat", points), &ewkb, |b, ewkb| {
+            b.iter(|| ewkb.decode::<FlatGeometry>().unwrap())
+        });
+        group.finish();
+
+        let mut group = c.benchmark_group("encode_polygon");
+        group.throughput(Throughput::Elements(u64::from(points)));
+        group.bench_with_input(
+            BenchmarkId::new("polygon", points),
+            &polygon,
+            |b, polygon| b.iter(|| EwkbBuf::from_geometry(polygon).unwrap()),
+        );
+        group.bench_with_input(BenchmarkId::new("flat", points), &flat, |b, flat| {
+            b.iter(|| EwkbBuf::from_geometry(flat).unwrap())
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, flat_geometry);
+criterion_main!(benches);
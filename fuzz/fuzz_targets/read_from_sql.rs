@@ -0,0 +1,46 @@
+//! Decodes arbitrary bytes as every geometry type, the decoded geometries have to be encoded and
+//! decoded again.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use postgis_diesel::types::*;
+
+macro_rules! decode {
+    ($data:expr, $($type:ty),+) => {
+        $(
+            if let Ok(geometry) = EwkbRef::new($data).decode::<$type>() {
+                let ewkb = EwkbBuf::from_geometry(&geometry).unwrap();
+                ewkb.decode::<$type>().unwrap();
+            }
+        )+
+    };
+}
+
+fuzz_target!(|data: &[u8]| {
+    decode!(
+        data,
+        Point,
+        PointZ,
+        PointM,
+        PointZM,
+        LineString<Point>,
+        LineString<PointZM>,
+        Polygon<Point>,
+        Polygon<PointZ>,
+        MultiPoint<Point>,
+        MultiPoint<PointM>,
+        MultiLineString<Point>,
+        MultiLineString<PointZ>,
+        MultiPolygon<Point>,
+        MultiPolygon<PointZM>,
+        GeometryCollection<Point>,
+        GeometryCollection<PointZ>,
+        GeometryContainer<Point>,
+        GeometryContainer<PointZ>,
+        GeometryContainer<PointM>,
+        GeometryContainer<PointZM>,
+        FlatGeometry
+    );
+    let ewkb = EwkbRef::new(data);
+    let _ = ewkb.bbox();
+});
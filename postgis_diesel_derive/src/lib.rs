@@ -0,0 +1,186 @@
+//! Derive macros of `postgis_diesel`, use them through its `derive` feature.
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Ident, LitStr, Type};
+
+/// Implements `postgis_diesel::types::GeoJsonFeature` for a struct with named fields.
+///
+/// One field must be marked with `#[geojson(geometry)]`, at most one with `#[geojson(id)]`, the
+/// other fields are the properties of the feature unless they are marked with
+/// `#[geojson(skip)]`. `#[geojson(rename = "name")]` gives another name to a property.
+#[proc_macro_derive(GeoJsonFeature, attributes(geojson))]
+pub fn derive_geojson_feature(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+enum Role {
+    Geometry,
+    Id,
+    Skip,
+    Property(String),
+}
+
+struct Field {
+    ident: Ident,
+    ty: Type,
+    role: Role,
+}
+
+fn parse_field(field: &syn::Field) -> syn::Result<Field> {
+    let ident = field.ident.clone().expect("named field");
+    let mut role = None;
+    let mut rename = None;
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("geojson")) {
+        attr.parse_nested_meta(|meta| {
+            let new_role = if meta.path.is_ident("geometry") {
+                Role::Geometry
+            } else if meta.path.is_ident("id") {
+                Role::Id
+            } else if meta.path.is_ident("skip") {
+                Role::Skip
+            } else if meta.path.is_ident("rename") {
+                rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                return Ok(());
+            } else {
+                return Err(meta.error("expected `geometry`, `id`, `skip` or `rename`"));
+            };
+            if role.replace(new_role).is_some() {
+                return Err(meta.error("only one of `geometry`, `id` and `skip` is allowed"));
+            }
+            Ok(())
+        })?;
+    }
+    let role = match (role, rename) {
+        (None, rename) => Role::Property(rename.unwrap_or_else(|| ident.to_string())),
+        (Some(_), Some(_)) => {
+            return Err(Error::new_spanned(
+                field,
+                "`rename` is only allowed for properties",
+            ))
+        }
+        (Some(role), None) => role,
+    };
+    Ok(Field {
+        ident,
+        ty: field.ty.clone(),
+        role,
+    })
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let named = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(named) => &named.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "GeoJsonFeature needs a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "GeoJsonFeature can only be derived for structs",
+            ))
+        }
+    };
+    let fields = named
+        .iter()
+        .map(parse_field)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let mut geometry = None;
+    let mut id = None;
+    for field in &fields {
+        let (slot, name) = match field.role {
+            Role::Geometry => (&mut geometry, "geometry"),
+            Role::Id => (&mut id, "id"),
+            _ => continue,
+        };
+        if slot.replace(field).is_some() {
+            return Err(Error::new_spanned(
+                &field.ident,
+                format!("only one field may be marked with `#[geojson({})]`", name),
+            ));
+        }
+    }
+    let geometry = geometry.ok_or_else(|| {
+        Error::new_spanned(
+            &input.ident,
+            "a field must be marked with `#[geojson(geometry)]`",
+        )
+    })?;
+
+    let private = quote!(::postgis_diesel::types::__private);
+    let geometry_ident = &geometry.ident;
+    let geometry_ty = &geometry.ty;
+    let point = quote!(<#geometry_ty as ::postgis_diesel::types::FeatureGeometry>::Point);
+
+    let into_id = match id {
+        Some(id) => {
+            let ident = &id.ident;
+            quote!(#private::feature_id(&self.#ident)?)
+        }
+        None => quote!(::std::option::Option::None),
+    };
+    let insert_properties = fields.iter().filter_map(|field| match &field.role {
+        Role::Property(name) => {
+            let ident = &field.ident;
+            Some(quote!(#private::insert_property(&mut properties, #name, &self.#ident)?;))
+        }
+        _ => None,
+    });
+    let from_fields = fields.iter().map(|field| {
+        let ident = &field.ident;
+        match &field.role {
+            Role::Geometry => quote!(#ident: #private::from_geometry(feature.geometry)?),
+            Role::Id => quote!(#ident: #private::from_feature_id(feature.id)?),
+            Role::Skip => quote!(#ident: ::std::default::Default::default()),
+            Role::Property(name) => {
+                quote!(#ident: #private::remove_property(&mut properties, #name)?)
+            }
+        }
+    });
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::postgis_diesel::types::GeoJsonFeature for #name #ty_generics
+        #where_clause
+        {
+            type Point = #point;
+
+            fn into_feature(
+                self,
+            ) -> ::std::result::Result<
+                ::postgis_diesel::types::Feature<Self::Point, ::postgis_diesel::types::FeatureProperties>,
+                #private::serde_json::Error,
+            > {
+                let mut properties = ::postgis_diesel::types::FeatureProperties::new();
+                #(#insert_properties)*
+                ::std::result::Result::Ok(::postgis_diesel::types::Feature {
+                    id: #into_id,
+                    bbox: ::std::option::Option::None,
+                    geometry: #private::geometry(self.#geometry_ident),
+                    properties: ::std::option::Option::Some(properties),
+                    foreign_members: ::std::default::Default::default(),
+                })
+            }
+
+            fn from_feature(
+                feature: ::postgis_diesel::types::Feature<Self::Point, ::postgis_diesel::types::FeatureProperties>,
+            ) -> ::std::result::Result<Self, #private::serde_json::Error> {
+                #[allow(unused_mut, unused_variables)]
+                let mut properties = feature.properties.unwrap_or_default();
+                ::std::result::Result::Ok(#name {
+                    #(#from_fields,)*
+                })
+            }
+        }
+    })
+}
@@ -0,0 +1,277 @@
+//! Bulk insertion with `COPY FROM` for the Postgres backend.
+//!
+//! The geometry types are plain `ToSql` fields, so structs deriving `Insertable` may be given to
+//! `diesel::copy_from(..).from_insertable(..)`, as long as they set
+//! `#[diesel(treat_none_as_default_value = false)]`. Every field is then encoded by the `ToSql` of
+//! the SQL type of its column, but the whole batch has to be in a `Vec` or a slice.
+//!
+//! [`BinaryCopy`] writes the rows of an iterator in the binary format of `COPY FROM` instead, one
+//! row at a time, for `from_raw_data`. It only writes geometry columns: every row is a tuple of
+//! [`CopyField`], implemented for the geometry types and their `Option`, in the order of the
+//! target columns. The columns left out of the target take their default. Rows with values of
+//! other types go through `from_insertable`, a chunk of the iterator at a time if it is too large
+//! to be collected.
+//! ```no_run
+//! #[macro_use] extern crate diesel;
+//! use diesel::pg::{CopyFormat, PgConnection};
+//! use diesel::prelude::*;
+//! use postgis_diesel::copy::BinaryCopy;
+//! use postgis_diesel::types::Point;
+//! table! {
+//!     use postgis_diesel::sql_types::*;
+//!     use diesel::sql_types::*;
+//!     gps_points (id) {
+//!         id -> Int4,
+//!         device_id -> Nullable<Int4>,
+//!         location -> Geometry,
+//!     }
+//! }
+//! #[derive(Insertable)]
+//! #[diesel(table_name = gps_points, treat_none_as_default_value = false)]
+//! struct NewGpsPoint {
+//!     device_id: Option<i32>,
+//!     location: Point,
+//! }
+//! fn load(conn: &mut PgConnection, readings: impl Iterator<Item = (f64, f64)>) -> QueryResult<()> {
+//!     let batch = vec![NewGpsPoint {
+//!         device_id: Some(1),
+//!         location: Point::new(13.4, 52.5, Some(4326)),
+//!     }];
+//!     diesel::copy_from(gps_points::table)
+//!         .from_insertable(&batch)
+//!         .execute(conn)?;
+//!     // the device_id of these rows is NULL, the default of the column
+//!     let rows = BinaryCopy::new(readings.map(|(x, y)| (Point::new(x, y, Some(4326)),)));
+//!     diesel::copy_from(gps_points::table)
+//!         .from_raw_data((gps_points::location,), |out| rows.write_to(out))
+//!         .with_format(CopyFormat::Binary)
+//!         .execute(conn)?;
+//!     Ok(())
+//! }
+//! # fn main() {}
+//! ```
+#![cfg(feature = "postgres")]
+
+use std::cell::RefCell;
+use std::convert::TryFrom;
+use std::io::Write;
+
+use byteorder::{NetworkEndian, WriteBytesExt};
+use diesel::result::Error;
+use diesel::serialize::IsNull;
+use diesel::QueryResult;
+
+const COPY_SIGNATURE: [u8; 11] = *b"PGCOPY\n\xff\r\n\0";
+
+/// Value of a geometry column in the binary format of `COPY FROM`, which is the EWKB with the
+/// SRID also written by `ToSql`.
+///
+/// It is not implemented for the other column types, whose binary format belongs to their
+/// `ToSql`, see the [module documentation](self) for how to insert them.
+pub trait CopyField {
+    /// Appends the value to `out`, nothing is written for `IsNull::Yes`.
+    fn write_field(&self, out: &mut Vec<u8>) -> std::io::Result<IsNull>;
+}
+
+/// Row in the binary format of `COPY FROM`, implemented for tuples of [`CopyField`].
+pub trait CopyRow {
+    /// Number of fields of the row.
+    const COLUMN_COUNT: i16;
+
+    /// Appends the field count, then the length and value of every field, to `out`.
+    fn write_row(&self, out: &mut Vec<u8>) -> std::io::Result<()>;
+}
+
+/// Writes the rows of an iterator as the data of `COPY FROM ... (FORMAT binary)`.
+///
+/// The callback given to `from_raw_data` has to be `Fn`, so the iterator is taken out of a
+/// `RefCell` by the first call of [`BinaryCopy::write_to`].
+#[derive(Debug)]
+pub struct BinaryCopy<I> {
+    rows: RefCell<Option<I>>,
+}
+
+impl<I> BinaryCopy<I>
+where
+    I: Iterator,
+    I::Item: CopyRow,
+{
+    pub fn new<R>(rows: R) -> Self
+    where
+        R: IntoIterator<IntoIter = I>,
+    {
+        BinaryCopy {
+            rows: RefCell::new(Some(rows.into_iter())),
+        }
+    }
+
+    /// Writes the header, every row and the trailer to `out`.
+    pub fn write_to(&self, out: &mut dyn Write) -> QueryResult<()> {
+        let rows = self.rows.borrow_mut().take().ok_or_else(|| {
+            Error::SerializationError("Rows of the binary copy are already written".into())
+        })?;
+        write_binary_copy(rows, out).map_err(|e| Error::SerializationError(Box::new(e)))
+    }
+}
+
+fn write_binary_copy<I>(rows: I, out: &mut dyn Write) -> std::io::Result<()>
+where
+    I: Iterator,
+    I::Item: CopyRow,
+{
+    out.write_all(&COPY_SIGNATURE)?;
+    // flags and header extension length
+    out.write_i32::<NetworkEndian>(0)?;
+    out.write_i32::<NetworkEndian>(0)?;
+    let mut buffer = Vec::new();
+    for row in rows {
+        row.write_row(&mut buffer)?;
+        out.write_all(&buffer)?;
+        buffer.clear();
+    }
+    out.write_i16::<NetworkEndian>(-1)
+}
+
+fn write_length_prefixed<F>(field: &F, out: &mut Vec<u8>) -> std::io::Result<()>
+where
+    F: CopyField + ?Sized,
+{
+    // the length is filled in once the value is written, -1 stands for NULL
+    let start = out.len();
+    out.write_i32::<NetworkEndian>(-1)?;
+    if field.write_field(out)? == IsNull::No {
+        let length = i32::try_from(out.len() - start - 4).map_err(|_| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "Field is too large")
+        })?;
+        out[start..start + 4].copy_from_slice(&length.to_be_bytes());
+    }
+    Ok(())
+}
+
+impl<T> CopyField for Option<T>
+where
+    T: CopyField,
+{
+    fn write_field(&self, out: &mut Vec<u8>) -> std::io::Result<IsNull> {
+        match self {
+            Some(value) => value.write_field(out),
+            None => Ok(IsNull::Yes),
+        }
+    }
+}
+
+impl<T> CopyField for &T
+where
+    T: CopyField + ?Sized,
+{
+    fn write_field(&self, out: &mut Vec<u8>) -> std::io::Result<IsNull> {
+        (**self).write_field(out)
+    }
+}
+
+/// Macro implementing `CopyField` for the geometry types, written as EWKB with the SRID.
+macro_rules! impl_copy_field_for_geometry {
+	($([$($generics:tt)*] $type:ty),+) => {
+		$(
+			impl<$($generics)*> CopyField for $type {
+				fn write_field(&self, out: &mut Vec<u8>) -> std::io::Result<IsNull> {
+					use crate::write_to_read_from_sql::WriteToSql;
+					self.write_to_sql(true, out)?;
+					Ok(IsNull::No)
+				}
+			}
+		)*
+	};
+}
+
+impl_copy_field_for_geometry!(
+    [P: crate::types::PointT] crate::types::MultiPoint<P>,
+    [P: crate::types::PointT] crate::types::MultiLineString<P>,
+    [P: crate::types::PointT] crate::types::MultiPolygon<P>,
+    [P: crate::types::PointT] crate::types::GeometryCollection<P>,
+    [P: crate::types::PointT] crate::types::GeometryContainer<P>,
+    [P: crate::types::PointT] crate::types::LineString<P>,
+    [P: crate::types::PointT] crate::types::Polygon<P>,
+    [] crate::types::Point,
+    [] crate::types::PointZ,
+    [] crate::types::PointM,
+    [] crate::types::PointZM,
+    [] crate::types::FlatGeometry
+);
+
+macro_rules! impl_copy_row_for_tuple {
+	($($count:literal => ($($field:ident),+)),+) => {
+		$(
+			impl<$($field),+> CopyRow for ($($field,)+)
+			where
+				$($field: CopyField,)+
+			{
+				const COLUMN_COUNT: i16 = $count;
+
+				#[allow(non_snake_case)]
+				fn write_row(&self, out: &mut Vec<u8>) -> std::io::Result<()> {
+					let ($($field,)+) = self;
+					out.write_i16::<NetworkEndian>(Self::COLUMN_COUNT)?;
+					$(write_length_prefixed($field, out)?;)+
+					Ok(())
+				}
+			}
+		)+
+	};
+}
+
+impl_copy_row_for_tuple!(
+    1 => (A),
+    2 => (A, B),
+    3 => (A, B, C),
+    4 => (A, B, C, D),
+    5 => (A, B, C, D, E),
+    6 => (A, B, C, D, E, F),
+    7 => (A, B, C, D, E, F, G),
+    8 => (A, B, C, D, E, F, G, H),
+    9 => (A, B, C, D, E, F, G, H, I),
+    10 => (A, B, C, D, E, F, G, H, I, J),
+    11 => (A, B, C, D, E, F, G, H, I, J, K),
+    12 => (A, B, C, D, E, F, G, H, I, J, K, L)
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Point;
+
+    #[test]
+    fn test_binary_copy_format() {
+        let rows = BinaryCopy::new(vec![
+            (
+                Point::new(3.0, 4.0, Some(4326)),
+                Some(Point::new(1.0, 2.0, Some(4326))),
+            ),
+            (Point::new(3.0, 4.0, Some(4326)), None),
+        ]);
+        let mut out = Vec::new();
+        rows.write_to(&mut out).unwrap();
+        let mut expected = b"PGCOPY\n\xff\r\n\0".to_vec();
+        expected.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 0]);
+        let mut point = Vec::new();
+        Point::new(3.0, 4.0, Some(4326))
+            .write_field(&mut point)
+            .unwrap();
+        let mut other = Vec::new();
+        Point::new(1.0, 2.0, Some(4326))
+            .write_field(&mut other)
+            .unwrap();
+        // first row, a point is 25 bytes of EWKB
+        expected.extend_from_slice(&[0, 2, 0, 0, 0, 25]);
+        expected.extend_from_slice(&point);
+        expected.extend_from_slice(&[0, 0, 0, 25]);
+        expected.extend_from_slice(&other);
+        // second row, with a NULL point
+        expected.extend_from_slice(&[0, 2, 0, 0, 0, 25]);
+        expected.extend_from_slice(&point);
+        expected.extend_from_slice(&[0xff, 0xff, 0xff, 0xff]);
+        expected.extend_from_slice(&[0xff, 0xff]);
+        assert_eq!(expected, out);
+        assert!(rows.write_to(&mut Vec::new()).is_err());
+    }
+}
@@ -1,4 +1,4 @@
-//! Error enumeration which may occur during the creation of geometries.
+//! Error enumerations which may occur during the creation and the decoding of geometries.
 
 #[derive(Debug)]
 /// Enum representing errors that can occur when reading geometries from SQL.
@@ -27,3 +27,118 @@ impl std::fmt::Display for Error {
 }
 
 impl std::error::Error for Error {}
+
+impl From<Error> for std::io::Error {
+    fn from(err: Error) -> std::io::Error {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, err)
+    }
+}
+
+/// Error returned when a geometry can't be converted to or from a `geo-types` geometry.
+#[cfg(feature = "geo-types")]
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeoTypesError {
+    /// A point has a Z or an M value, which `geo-types` geometries can't hold. `to_2d` drops them.
+    ZOrM { z: Option<f64>, m: Option<f64> },
+    /// A `geo-types` polygon has interiors but an empty exterior.
+    InteriorsWithoutExterior,
+}
+
+#[cfg(feature = "geo-types")]
+impl std::fmt::Display for GeoTypesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GeoTypesError::ZOrM { z, m } => write!(
+                f,
+                "geo-types can't hold Z {:?} or M {:?}, use `to_2d` to drop them",
+                z, m
+            ),
+            GeoTypesError::InteriorsWithoutExterior => {
+                write!(f, "polygon with interiors but an empty exterior")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "geo-types")]
+impl std::error::Error for GeoTypesError {}
+
+/// Error returned when EWKB bytes can't be decoded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodeError {
+    /// Offset of the byte at which decoding stopped, which is the start of the value that is
+    /// truncated or invalid.
+    pub offset: usize,
+    /// Why decoding stopped.
+    pub reason: DecodeErrorReason,
+}
+
+/// Reason of a [`DecodeError`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeErrorReason {
+    /// The bytes end in the middle of a value.
+    UnexpectedEnd,
+    /// The byte order is neither 0 (big endian) nor 1 (little endian).
+    InvalidByteOrder(u8),
+    /// The geometry type code, with its flags, is not one of the seven EWKB geometry types.
+    UnknownGeometryType(u32),
+    /// The geometry type is not the one of the decoded Rust type.
+    UnexpectedGeometryType {
+        expected: crate::ewkb::GeometryType,
+        actual: crate::ewkb::GeometryType,
+    },
+    /// Geometry collections are nested deeper than `MAX_NESTING_DEPTH`.
+    TooDeeplyNested,
+    /// Bytes are left after the geometry.
+    TrailingBytes(usize),
+    /// The values can't make a geometry of the decoded Rust type.
+    InvalidGeometry(String),
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid EWKB at byte {}: {}", self.offset, self.reason)
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl std::fmt::Display for DecodeErrorReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeErrorReason::UnexpectedEnd => write!(f, "unexpected end of input"),
+            DecodeErrorReason::InvalidByteOrder(byte_order) => {
+                write!(f, "invalid byte order {}", byte_order)
+            }
+            DecodeErrorReason::UnknownGeometryType(g_type) => {
+                write!(f, "unknown geometry type {:#x}", g_type)
+            }
+            DecodeErrorReason::UnexpectedGeometryType { expected, actual } => write!(
+                f,
+                "expected geometry type `{:?}`, but got `{:?}`",
+                expected, actual
+            ),
+            DecodeErrorReason::TooDeeplyNested => {
+                write!(f, "geometry collections are nested too deeply")
+            }
+            DecodeErrorReason::TrailingBytes(count) => {
+                write!(f, "{} bytes left after the geometry", count)
+            }
+            DecodeErrorReason::InvalidGeometry(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
+impl std::error::Error for DecodeErrorReason {}
+
+impl From<DecodeErrorReason> for std::io::Error {
+    fn from(reason: DecodeErrorReason) -> std::io::Error {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, reason)
+    }
+}
+
+impl From<DecodeError> for std::io::Error {
+    fn from(err: DecodeError) -> std::io::Error {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, err)
+    }
+}
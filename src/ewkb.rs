@@ -1,6 +1,9 @@
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
-#[derive(Debug, PartialEq)]
+use crate::points::Dimension;
+
+/// Type of an EWKB geometry.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum GeometryType {
     Point = 1,
     LineString,
@@ -11,6 +14,8 @@ pub enum GeometryType {
     GeometryCollection,
 }
 
+pub const EWKB_Z: u32 = 0x80000000;
+pub const EWKB_M: u32 = 0x40000000;
 pub const SRID: u32 = 0x20000000;
 pub const LITTLE_ENDIAN: u8 = 1;
 pub const BIG_ENDIAN: u8 = 0;
@@ -71,7 +76,21 @@ pub trait EwkbSerializable {
             g_type &= !SRID;
             srid = Some(cursor.read_u32::<T>()?);
         }
-        Ok(EwkbHeader { g_type, srid })
+        // ISO WKB, written by GDAL among others, gives the dimensions in thousands of the code
+        let code = g_type & !Dimension::ZM;
+        if (1000..4000).contains(&code) {
+            let dimension = match code / 1000 {
+                1 => Dimension::Z,
+                2 => Dimension::M,
+                _ => Dimension::ZM,
+            };
+            g_type = (g_type & Dimension::ZM) | dimension | (code % 1000);
+        }
+        Ok(EwkbHeader {
+            g_type,
+            srid,
+            depth: 0,
+        })
     }
 }
 
@@ -79,23 +98,8 @@ pub trait EwkbSerializable {
 pub struct EwkbHeader {
     pub g_type: u32,
     pub srid: Option<u32>,
-}
-
-impl EwkbHeader {
-    pub fn expect(self, expected_type: GeometryType) -> Result<Self, std::io::Error> {
-        if GeometryType::from(self.g_type) != expected_type {
-            Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                format!(
-                    "Expected geometry type `{:?}`, but got `{:?}`",
-                    GeometryType::from(self.g_type),
-                    expected_type,
-                ),
-            ))
-        } else {
-            Ok(self)
-        }
-    }
+    /// Number of geometry collections around the geometry.
+    pub depth: usize,
 }
 
 #[cfg(test)]
@@ -0,0 +1,714 @@
+//! Submodule implementing the `EwkbRef` borrowed view of EWKB bytes and the `EwkbBuf` owned bytes.
+
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+
+use crate::errors::{DecodeError, DecodeErrorReason};
+use crate::ewkb::{GeometryType, BIG_ENDIAN, LITTLE_ENDIAN, SRID};
+use crate::points::Dimension;
+use crate::types::*;
+use crate::write_to_read_from_sql::{ReadFromSql, WriteToSql, MAX_NESTING_DEPTH};
+
+fn unexpected_end() -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::UnexpectedEof,
+        "Unexpected end of the EWKB bytes",
+    )
+}
+
+fn read_u32(bytes: &[u8], offset: usize, big_endian: bool) -> Result<u32, std::io::Error> {
+    let bytes = bytes.get(offset..offset + 4).ok_or_else(unexpected_end)?;
+    Ok(if big_endian {
+        BigEndian::read_u32(bytes)
+    } else {
+        LittleEndian::read_u32(bytes)
+    })
+}
+
+fn read_f64(bytes: &[u8], big_endian: bool) -> f64 {
+    if big_endian {
+        BigEndian::read_f64(bytes)
+    } else {
+        LittleEndian::read_f64(bytes)
+    }
+}
+
+/// Header of the geometry at the start of the bytes, `body` is the offset of what follows it.
+pub(crate) struct Header {
+    big_endian: bool,
+    geometry_type: GeometryType,
+    has_z: bool,
+    has_m: bool,
+    srid: Option<u32>,
+    body: usize,
+}
+
+impl Header {
+    fn read(bytes: &[u8]) -> Result<Self, std::io::Error> {
+        let big_endian = match bytes.first() {
+            Some(&BIG_ENDIAN) => true,
+            Some(&LITTLE_ENDIAN) => false,
+            Some(byte_order) => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("Invalid byte order `{}`", byte_order),
+                ))
+            }
+            None => return Err(unexpected_end()),
+        };
+        let g_type = read_u32(bytes, 1, big_endian)?;
+        let (srid, body) = if g_type & SRID == SRID {
+            (Some(read_u32(bytes, 5, big_endian)?), 9)
+        } else {
+            (None, 5)
+        };
+        // ISO WKB gives the dimensions in thousands of the code
+        let (code, dimension) = match g_type & !(SRID | Dimension::ZM) {
+            code @ 1000..=1999 => (code - 1000, Dimension::Z),
+            code @ 2000..=2999 => (code - 2000, Dimension::M),
+            code @ 3000..=3999 => (code - 3000, Dimension::ZM),
+            code => (code, g_type & Dimension::ZM),
+        };
+        let geometry_type = match code {
+            1 => GeometryType::Point,
+            2 => GeometryType::LineString,
+            3 => GeometryType::Polygon,
+            4 => GeometryType::MultiPoint,
+            5 => GeometryType::MultiLineString,
+            6 => GeometryType::MultiPolygon,
+            7 => GeometryType::GeometryCollection,
+            code => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("Unknown geometry type `{}`", code),
+                ))
+            }
+        };
+        Ok(Header {
+            big_endian,
+            geometry_type,
+            has_z: dimension & Dimension::Z == Dimension::Z,
+            has_m: dimension & Dimension::M == Dimension::M,
+            srid,
+            body,
+        })
+    }
+
+    fn stride(&self) -> usize {
+        coordinate_stride(self.has_z, self.has_m)
+    }
+
+    /// ISO WKB type code, which gives the dimensions in thousands of the code.
+    #[cfg(feature = "sqlite")]
+    pub(crate) fn iso_type(&self) -> u32 {
+        self.geometry_type as u32 + 1000 * u32::from(self.has_z) + 2000 * u32::from(self.has_m)
+    }
+
+    /// EWKB type code with the dimension flags, without the SRID flag.
+    #[cfg(feature = "sqlite")]
+    pub(crate) fn ewkb_type(&self) -> u32 {
+        let mut g_type = self.geometry_type as u32;
+        if self.has_z {
+            g_type |= Dimension::Z;
+        }
+        if self.has_m {
+            g_type |= Dimension::M;
+        }
+        g_type
+    }
+
+    fn expect(&self, expected: &[GeometryType]) -> Result<(), std::io::Error> {
+        if expected.contains(&self.geometry_type) {
+            Ok(())
+        } else {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "Expected geometry type {:?}, but got `{:?}`",
+                    expected, self.geometry_type
+                ),
+            ))
+        }
+    }
+
+    /// Reads the count at `offset`, and returns the `count * stride` bytes which follow it.
+    fn counted<'a>(
+        &self,
+        bytes: &'a [u8],
+        offset: usize,
+        stride: usize,
+    ) -> Result<&'a [u8], std::io::Error> {
+        let count = read_u32(bytes, offset, self.big_endian)?;
+        (count as usize)
+            .checked_mul(stride)
+            .and_then(|length| bytes.get(offset + 4..offset + 4 + length))
+            .ok_or_else(unexpected_end)
+    }
+}
+
+/// Writes the header of a geometry nested the given depth, for [`EwkbRef::write_wkb`].
+#[cfg(feature = "sqlite")]
+pub(crate) type WriteHeader<'f> =
+    dyn FnMut(&mut Vec<u8>, &Header, usize) -> Result<(), std::io::Error> + 'f;
+
+fn coordinate_stride(has_z: bool, has_m: bool) -> usize {
+    8 * (2 + usize::from(has_z) + usize::from(has_m))
+}
+
+/// Length of the geometry at the start of `bytes`, which is nested `depth` geometries deep.
+fn geometry_length(bytes: &[u8], depth: usize) -> Result<usize, std::io::Error> {
+    let header = Header::read(bytes)?;
+    match header.geometry_type {
+        GeometryType::Point => {
+            let end = header.body + header.stride();
+            if bytes.len() < end {
+                return Err(unexpected_end());
+            }
+            Ok(end)
+        }
+        GeometryType::LineString => {
+            let points = header.counted(bytes, header.body, header.stride())?;
+            Ok(header.body + 4 + points.len())
+        }
+        GeometryType::Polygon => {
+            let mut rings = EwkbRef { bytes }.rings()?;
+            for ring in &mut rings {
+                ring?;
+            }
+            Ok(bytes.len() - rings.bytes.len())
+        }
+        _ => {
+            let mut parts = EwkbRef { bytes }.parts_at(depth)?;
+            for part in &mut parts {
+                part?;
+            }
+            Ok(bytes.len() - parts.bytes.len())
+        }
+    }
+}
+
+impl<'a> EwkbRef<'a> {
+    /// Wraps the bytes, which are only checked when they are read.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        EwkbRef { bytes }
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    pub fn geometry_type(&self) -> Result<GeometryType, std::io::Error> {
+        Ok(Header::read(self.bytes)?.geometry_type)
+    }
+
+    /// SRID of the header, the geometries of a collection usually do not have one.
+    pub fn srid(&self) -> Result<Option<u32>, std::io::Error> {
+        Ok(Header::read(self.bytes)?.srid)
+    }
+
+    pub fn has_z(&self) -> Result<bool, std::io::Error> {
+        Ok(Header::read(self.bytes)?.has_z)
+    }
+
+    pub fn has_m(&self) -> Result<bool, std::io::Error> {
+        Ok(Header::read(self.bytes)?.has_m)
+    }
+
+    /// Coordinates of a point or a line string.
+    pub fn points(&self) -> Result<Coordinates<'a>, std::io::Error> {
+        let header = Header::read(self.bytes)?;
+        header.expect(&[GeometryType::Point, GeometryType::LineString])?;
+        let bytes = if header.geometry_type == GeometryType::Point {
+            self.bytes
+                .get(header.body..header.body + header.stride())
+                .ok_or_else(unexpected_end)?
+        } else {
+            header.counted(self.bytes, header.body, header.stride())?
+        };
+        Ok(Coordinates {
+            bytes,
+            big_endian: header.big_endian,
+            has_z: header.has_z,
+            has_m: header.has_m,
+        })
+    }
+
+    /// Rings of a polygon.
+    pub fn rings(&self) -> Result<Rings<'a>, std::io::Error> {
+        let header = Header::read(self.bytes)?;
+        header.expect(&[GeometryType::Polygon])?;
+        Ok(Rings {
+            remaining: read_u32(self.bytes, header.body, header.big_endian)?,
+            bytes: &self.bytes[header.body + 4..],
+            big_endian: header.big_endian,
+            has_z: header.has_z,
+            has_m: header.has_m,
+        })
+    }
+
+    /// Geometries of a multi geometry or of a geometry collection.
+    pub fn parts(&self) -> Result<Parts<'a>, std::io::Error> {
+        self.parts_at(0)
+    }
+
+    fn parts_at(&self, depth: usize) -> Result<Parts<'a>, std::io::Error> {
+        if depth >= MAX_NESTING_DEPTH {
+            return Err(DecodeErrorReason::TooDeeplyNested.into());
+        }
+        let header = Header::read(self.bytes)?;
+        header.expect(&[
+            GeometryType::MultiPoint,
+            GeometryType::MultiLineString,
+            GeometryType::MultiPolygon,
+            GeometryType::GeometryCollection,
+        ])?;
+        Ok(Parts {
+            remaining: read_u32(self.bytes, header.body, header.big_endian)?,
+            bytes: &self.bytes[header.body + 4..],
+            depth,
+        })
+    }
+
+    /// Calls `f` on every coordinate of the geometry, in the order of the bytes.
+    pub fn for_each_coordinate<F>(&self, mut f: F) -> Result<(), std::io::Error>
+    where
+        F: FnMut(Coordinate),
+    {
+        self.visit_coordinates(&mut f, 0)
+    }
+
+    fn visit_coordinates(
+        &self,
+        f: &mut dyn FnMut(Coordinate),
+        depth: usize,
+    ) -> Result<(), std::io::Error> {
+        match self.geometry_type()? {
+            GeometryType::Point | GeometryType::LineString => self.points()?.for_each(f),
+            GeometryType::Polygon => {
+                for ring in self.rings()? {
+                    ring?.for_each(&mut *f);
+                }
+            }
+            _ => {
+                for part in self.parts_at(depth)? {
+                    part?.visit_coordinates(f, depth + 1)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the geometry in the little-endian layout of WKB, where `write_header` writes the header
+    /// of the geometry and of each of its parts with their nesting depth, and `visit` sees every
+    /// coordinate. The formats stored by SQLite only differ from WKB by their headers.
+    #[cfg(feature = "sqlite")]
+    pub(crate) fn write_wkb(
+        &self,
+        out: &mut Vec<u8>,
+        write_header: &mut WriteHeader<'_>,
+        visit: &mut dyn FnMut(&Coordinate),
+    ) -> Result<(), std::io::Error> {
+        self.write_wkb_at(out, write_header, visit, 0)
+    }
+
+    #[cfg(feature = "sqlite")]
+    fn write_wkb_at(
+        &self,
+        out: &mut Vec<u8>,
+        write_header: &mut WriteHeader<'_>,
+        visit: &mut dyn FnMut(&Coordinate),
+        depth: usize,
+    ) -> Result<(), std::io::Error> {
+        let header = Header::read(self.bytes)?;
+        write_header(out, &header, depth)?;
+        let mut write_coordinates = |out: &mut Vec<u8>, coordinates: Coordinates| {
+            for coordinate in coordinates {
+                visit(&coordinate);
+                let ordinates = [
+                    Some(coordinate.x),
+                    Some(coordinate.y),
+                    coordinate.z,
+                    coordinate.m,
+                ];
+                for value in ordinates.iter().flatten() {
+                    out.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+        };
+        match header.geometry_type {
+            GeometryType::Point => write_coordinates(out, self.points()?),
+            GeometryType::LineString => {
+                let points = self.points()?;
+                out.extend_from_slice(&(points.len() as u32).to_le_bytes());
+                write_coordinates(out, points);
+            }
+            GeometryType::Polygon => {
+                let rings = self.rings()?;
+                out.extend_from_slice(&rings.remaining.to_le_bytes());
+                for ring in rings {
+                    let points = ring?;
+                    out.extend_from_slice(&(points.len() as u32).to_le_bytes());
+                    write_coordinates(out, points);
+                }
+            }
+            _ => {
+                let parts = self.parts_at(depth)?;
+                out.extend_from_slice(&parts.remaining.to_le_bytes());
+                for part in parts {
+                    part?.write_wkb_at(out, write_header, visit, depth + 1)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Bounding box of the X and Y coordinates, `None` for an empty geometry.
+    pub fn bbox(&self) -> Result<Option<BoundingBox>, std::io::Error> {
+        let mut bbox: Option<BoundingBox> = None;
+        self.for_each_coordinate(|coordinate| {
+            // an empty point has NaN coordinates
+            if coordinate.x.is_nan() || coordinate.y.is_nan() {
+                return;
+            }
+            let current = bbox.get_or_insert(BoundingBox {
+                xmin: coordinate.x,
+                ymin: coordinate.y,
+                xmax: coordinate.x,
+                ymax: coordinate.y,
+            });
+            current.xmin = current.xmin.min(coordinate.x);
+            current.ymin = current.ymin.min(coordinate.y);
+            current.xmax = current.xmax.max(coordinate.x);
+            current.ymax = current.ymax.max(coordinate.y);
+        })?;
+        Ok(bbox)
+    }
+
+    /// Decodes the bytes into one of the owned geometry types.
+    pub fn decode<G>(&self) -> Result<G, DecodeError>
+    where
+        G: ReadFromSql,
+    {
+        G::read_from_sql(self.bytes)
+    }
+
+    pub fn to_ewkb_buf(&self) -> EwkbBuf {
+        EwkbBuf {
+            bytes: self.bytes.to_vec(),
+        }
+    }
+}
+
+impl EwkbBuf {
+    /// Wraps the bytes, which are only checked when they are read.
+    pub fn new(bytes: Vec<u8>) -> Self {
+        EwkbBuf { bytes }
+    }
+
+    /// Encodes one of the owned geometry types.
+    pub fn from_geometry<G>(geometry: &G) -> Result<Self, std::io::Error>
+    where
+        G: WriteToSql,
+    {
+        let mut bytes = Vec::new();
+        geometry.write_to_sql(true, &mut bytes)?;
+        Ok(EwkbBuf { bytes })
+    }
+
+    pub fn as_ewkb_ref(&self) -> EwkbRef<'_> {
+        EwkbRef { bytes: &self.bytes }
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Decodes the bytes into one of the owned geometry types.
+    pub fn decode<G>(&self) -> Result<G, DecodeError>
+    where
+        G: ReadFromSql,
+    {
+        self.as_ewkb_ref().decode()
+    }
+}
+
+impl From<Vec<u8>> for EwkbBuf {
+    fn from(bytes: Vec<u8>) -> Self {
+        EwkbBuf::new(bytes)
+    }
+}
+
+impl AsRef<[u8]> for EwkbBuf {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl Coordinate {
+    pub fn to_point<P>(&self, srid: Option<u32>) -> Result<P, PointConstructorError>
+    where
+        P: PointT,
+    {
+        P::new_point(self.x, self.y, srid, self.z, self.m)
+    }
+}
+
+impl Iterator for Coordinates<'_> {
+    type Item = Coordinate;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let stride = coordinate_stride(self.has_z, self.has_m);
+        if self.bytes.len() < stride {
+            return None;
+        }
+        let (coordinate, rest) = self.bytes.split_at(stride);
+        self.bytes = rest;
+        let mut values = coordinate
+            .chunks_exact(8)
+            .map(|value| read_f64(value, self.big_endian));
+        let x = values.next()?;
+        let y = values.next()?;
+        let z = if self.has_z { values.next() } else { None };
+        let m = if self.has_m { values.next() } else { None };
+        Some(Coordinate { x, y, z, m })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bytes.len() / coordinate_stride(self.has_z, self.has_m);
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Coordinates<'_> {}
+
+impl<'a> Iterator for Rings<'a> {
+    type Item = Result<Coordinates<'a>, std::io::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let stride = coordinate_stride(self.has_z, self.has_m);
+        let ring = read_u32(self.bytes, 0, self.big_endian).and_then(|count| {
+            (count as usize)
+                .checked_mul(stride)
+                .and_then(|length| self.bytes.get(4..4 + length))
+                .ok_or_else(unexpected_end)
+        });
+        match ring {
+            Ok(ring) => {
+                self.bytes = &self.bytes[4 + ring.len()..];
+                Some(Ok(Coordinates {
+                    bytes: ring,
+                    big_endian: self.big_endian,
+                    has_z: self.has_z,
+                    has_m: self.has_m,
+                }))
+            }
+            Err(e) => {
+                self.remaining = 0;
+                Some(Err(e))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining as usize))
+    }
+}
+
+impl<'a> Iterator for Parts<'a> {
+    type Item = Result<EwkbRef<'a>, std::io::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        match geometry_length(self.bytes, self.depth + 1) {
+            Ok(length) => {
+                let (part, rest) = self.bytes.split_at(length);
+                self.bytes = rest;
+                Some(Ok(EwkbRef { bytes: part }))
+            }
+            Err(e) => {
+                self.remaining = 0;
+                Some(Err(e))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining as usize))
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl diesel::deserialize::FromSql<crate::sql_types::Geometry, diesel::pg::Pg> for EwkbBuf {
+    fn from_sql(bytes: diesel::pg::PgValue) -> diesel::deserialize::Result<Self> {
+        Ok(EwkbBuf::new(bytes.as_bytes().to_vec()))
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl diesel::serialize::ToSql<crate::sql_types::Geometry, diesel::pg::Pg> for EwkbBuf {
+    fn to_sql(
+        &self,
+        out: &mut diesel::serialize::Output<diesel::pg::Pg>,
+    ) -> diesel::serialize::Result {
+        use std::io::Write;
+        out.write_all(&self.bytes)?;
+        Ok(diesel::serialize::IsNull::No)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl diesel::deserialize::FromSql<crate::sql_types::Geometry, diesel::sqlite::Sqlite> for EwkbBuf {
+    fn from_sql(
+        mut bytes: diesel::sqlite::SqliteValue<'_, '_, '_>,
+    ) -> diesel::deserialize::Result<Self> {
+        Ok(EwkbBuf::new(bytes.read_blob().to_vec()))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl diesel::serialize::ToSql<crate::sql_types::Geometry, diesel::sqlite::Sqlite> for EwkbBuf {
+    fn to_sql<'b>(
+        &'b self,
+        out: &mut diesel::serialize::Output<'b, '_, diesel::sqlite::Sqlite>,
+    ) -> diesel::serialize::Result {
+        out.set_value(self.bytes.as_slice());
+        Ok(diesel::serialize::IsNull::No)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_ring(offset: f64) -> Vec<PointZ> {
+        vec![
+            PointZ::new(offset, offset, 1.0, Some(4326)),
+            PointZ::new(offset + 1.0, offset, 2.0, Some(4326)),
+            PointZ::new(offset + 1.0, offset + 1.0, 3.0, Some(4326)),
+            PointZ::new(offset, offset, 1.0, Some(4326)),
+        ]
+    }
+
+    #[test]
+    fn test_ewkb_ref_polygon() {
+        let mut polygon = Polygon::new(Some(4326));
+        polygon.add_points(new_ring(0.0)).unwrap();
+        polygon.add_ring().add_points(new_ring(0.25)).unwrap();
+        let bytes = EwkbBuf::from_geometry(&polygon).unwrap();
+        let ewkb = bytes.as_ewkb_ref();
+        assert_eq!(GeometryType::Polygon, ewkb.geometry_type().unwrap());
+        assert!(ewkb.has_z().unwrap());
+        assert!(!ewkb.has_m().unwrap());
+        assert!(ewkb.points().is_err());
+        let rings: Vec<Vec<Coordinate>> = ewkb
+            .rings()
+            .unwrap()
+            .map(|ring| ring.unwrap().collect())
+            .collect();
+        assert_eq!(2, rings.len());
+        assert_eq!(
+            Coordinate {
+                x: 1.25,
+                y: 0.25,
+                z: Some(2.0),
+                m: None
+            },
+            rings[1][1]
+        );
+        assert_eq!(
+            PointZ::new(1.25, 0.25, 2.0, Some(4326)),
+            rings[1][1].to_point(Some(4326)).unwrap()
+        );
+        assert_eq!(polygon, bytes.decode::<Polygon<PointZ>>().unwrap());
+    }
+
+    #[test]
+    fn test_ewkb_ref_collection() {
+        let mut line = LineString::new(None);
+        line.add_points([Point::new(-1.0, 5.0, None), Point::new(2.0, 3.0, None)])
+            .unwrap();
+        let mut multiline = MultiLineString::new(None);
+        multiline.lines.push(line.clone());
+        let collection = GeometryCollection {
+            geometries: vec![
+                GeometryContainer::Point(Point::new(4.0, -2.0, None)),
+                GeometryContainer::MultiLineString(multiline),
+                GeometryContainer::LineString(line.clone()),
+            ],
+            srid: Some(3857),
+        };
+        let bytes = EwkbBuf::from_geometry(&collection).unwrap();
+        let ewkb = bytes.as_ewkb_ref();
+        assert_eq!(Some(3857), ewkb.srid().unwrap());
+        let parts: Vec<EwkbRef> = ewkb.parts().unwrap().map(Result::unwrap).collect();
+        assert_eq!(3, parts.len());
+        assert_eq!(
+            GeometryType::MultiLineString,
+            parts[1].geometry_type().unwrap()
+        );
+        assert_eq!(line, parts[2].decode::<LineString<Point>>().unwrap());
+        assert_eq!(
+            Some(BoundingBox {
+                xmin: -1.0,
+                ymin: -2.0,
+                xmax: 4.0,
+                ymax: 5.0
+            }),
+            ewkb.bbox().unwrap()
+        );
+        // the bytes are read lazily, truncation is found when reading the last part
+        let truncated = EwkbRef::new(&bytes.as_ref()[..bytes.as_ref().len() - 1]);
+        let mut parts = truncated.parts().unwrap();
+        assert!(parts.next().unwrap().is_ok());
+        assert!(parts.next().unwrap().is_ok());
+        assert!(parts.next().unwrap().is_err());
+        assert!(parts.next().is_none());
+        assert!(truncated.bbox().is_err());
+    }
+
+    #[test]
+    fn test_ewkb_ref_nested_collections() {
+        let mut bytes = Vec::new();
+        for _ in 0..200_000 {
+            bytes.extend_from_slice(&[1, 7, 0, 0, 0, 1, 0, 0, 0]);
+        }
+        bytes.extend_from_slice(
+            &EwkbBuf::from_geometry(&Point::new(1.0, 2.0, None))
+                .unwrap()
+                .bytes,
+        );
+        let ewkb = EwkbRef::new(&bytes);
+        let reason = |error: std::io::Error| {
+            error
+                .into_inner()
+                .and_then(|inner| inner.downcast::<DecodeErrorReason>().ok())
+                .map(|reason| *reason)
+        };
+        assert_eq!(
+            Some(DecodeErrorReason::TooDeeplyNested),
+            reason(ewkb.bbox().unwrap_err())
+        );
+        let mut parts = ewkb.parts().unwrap();
+        assert_eq!(
+            Some(DecodeErrorReason::TooDeeplyNested),
+            reason(parts.next().unwrap().unwrap_err())
+        );
+        // collections nested below the limit are read
+        let shallow = EwkbRef::new(&bytes[9 * (200_000 - MAX_NESTING_DEPTH)..]);
+        assert!(shallow.bbox().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_ewkb_ref_invalid_header() {
+        assert!(EwkbRef::new(&[]).geometry_type().is_err());
+        assert!(EwkbRef::new(&[2, 1, 0, 0, 0]).geometry_type().is_err());
+        assert!(EwkbRef::new(&[1, 8, 0, 0, 0]).geometry_type().is_err());
+        assert!(EwkbRef::new(&[1, 1, 0, 0, 0]).points().is_err());
+    }
+}
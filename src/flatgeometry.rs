@@ -0,0 +1,565 @@
+use byteorder::{BigEndian, LittleEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::errors::DecodeErrorReason;
+use crate::ewkb::{EwkbHeader, EwkbSerializable, GeometryType, BIG_ENDIAN, LITTLE_ENDIAN};
+use crate::points::Dimension;
+use crate::types::{BoundingBox, FlatGeometry};
+use crate::write_to_read_from_sql::{capacity, read_part_header, ReadFromSql, WriteToSql};
+
+impl FlatGeometry {
+    pub fn new(geometry_type: GeometryType, has_z: bool, has_m: bool, srid: Option<u32>) -> Self {
+        FlatGeometry {
+            geometry_type,
+            has_z,
+            has_m,
+            coordinates: Vec::new(),
+            ring_offsets: vec![0],
+            polygon_offsets: vec![0],
+            srid,
+        }
+    }
+
+    /// Number of values of every coordinate, from 2 to 4.
+    pub fn dimensions(&self) -> usize {
+        2 + usize::from(self.has_z) + usize::from(self.has_m)
+    }
+
+    pub fn num_coordinates(&self) -> usize {
+        self.coordinates.len() / self.dimensions()
+    }
+
+    /// Values of the coordinate `index`, `None` when it is out of range.
+    pub fn coordinate(&self, index: usize) -> Option<&[f64]> {
+        let dimensions = self.dimensions();
+        let start = index.checked_mul(dimensions)?;
+        self.coordinates.get(start..start.checked_add(dimensions)?)
+    }
+
+    pub fn push_coordinate(&mut self, x: f64, y: f64, z: Option<f64>, m: Option<f64>) {
+        self.coordinates.push(x);
+        self.coordinates.push(y);
+        if self.has_z {
+            self.coordinates.push(z.unwrap_or(f64::NAN));
+        }
+        if self.has_m {
+            self.coordinates.push(m.unwrap_or(f64::NAN));
+        }
+    }
+
+    /// Ends the current ring or line at the last pushed coordinate.
+    pub fn end_ring(&mut self) {
+        let end = self.num_coordinates();
+        self.ring_offsets.push(end);
+    }
+
+    /// Ends the current polygon of a multi polygon at the last ended ring.
+    pub fn end_polygon(&mut self) {
+        let end = self.num_rings();
+        self.polygon_offsets.push(end);
+    }
+
+    pub fn num_rings(&self) -> usize {
+        self.ring_offsets.len().saturating_sub(1)
+    }
+
+    pub fn num_polygons(&self) -> usize {
+        self.polygon_offsets.len().saturating_sub(1)
+    }
+
+    /// Values of the coordinates of the ring or line `index`, `None` when it is out of range or
+    /// its offsets do not delimit coordinates of the geometry.
+    pub fn ring(&self, index: usize) -> Option<&[f64]> {
+        let dimensions = self.dimensions();
+        let start = self.ring_offsets.get(index)?.checked_mul(dimensions)?;
+        let end = self
+            .ring_offsets
+            .get(index.checked_add(1)?)?
+            .checked_mul(dimensions)?;
+        self.coordinates.get(start..end)
+    }
+
+    /// Bounding box of the X and Y coordinates, `None` for an empty geometry.
+    pub fn bbox(&self) -> Option<BoundingBox> {
+        let mut bbox: Option<BoundingBox> = None;
+        for coordinate in self.coordinates.chunks_exact(self.dimensions()) {
+            let (x, y) = (coordinate[0], coordinate[1]);
+            if x.is_nan() || y.is_nan() {
+                continue;
+            }
+            let current = bbox.get_or_insert(BoundingBox {
+                xmin: x,
+                ymin: y,
+                xmax: x,
+                ymax: y,
+            });
+            current.xmin = current.xmin.min(x);
+            current.ymin = current.ymin.min(y);
+            current.xmax = current.xmax.max(x);
+            current.ymax = current.ymax.max(y);
+        }
+        bbox
+    }
+
+    fn dimension_flags(&self) -> u32 {
+        let mut dimension = Dimension::NONE;
+        if self.has_z {
+            dimension |= Dimension::Z;
+        }
+        if self.has_m {
+            dimension |= Dimension::M;
+        }
+        dimension
+    }
+
+    fn write_part_header<Writer>(
+        &self,
+        geometry_type: GeometryType,
+        out: &mut Writer,
+    ) -> Result<(), std::io::Error>
+    where
+        Writer: std::io::Write,
+    {
+        out.write_u8(LITTLE_ENDIAN)?;
+        out.write_u32::<LittleEndian>(geometry_type as u32 | self.dimension_flags())
+    }
+
+    fn write_values<Writer>(values: &[f64], out: &mut Writer) -> Result<(), std::io::Error>
+    where
+        Writer: std::io::Write,
+    {
+        for value in values {
+            out.write_f64::<LittleEndian>(*value)?;
+        }
+        Ok(())
+    }
+
+    fn write_rings<Writer>(&self, rings: &[usize], out: &mut Writer) -> Result<(), std::io::Error>
+    where
+        Writer: std::io::Write,
+    {
+        out.write_u32::<LittleEndian>((rings.len() - 1) as u32)?;
+        for ring in rings.windows(2) {
+            out.write_u32::<LittleEndian>((ring[1] - ring[0]) as u32)?;
+            let dimensions = self.dimensions();
+            Self::write_values(
+                &self.coordinates[ring[0] * dimensions..ring[1] * dimensions],
+                out,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn read_coordinates<Endianness, Reader>(
+        &mut self,
+        count: u32,
+        reader: &mut Reader,
+    ) -> Result<(), std::io::Error>
+    where
+        Reader: std::io::Read,
+        Endianness: byteorder::ByteOrder,
+    {
+        let dimensions = self.dimensions();
+        self.coordinates.reserve(dimensions * capacity(count));
+        let mut coordinate = [0.0; 4];
+        for _ in 0..count {
+            reader.read_f64_into::<Endianness>(&mut coordinate[..dimensions])?;
+            self.coordinates
+                .extend_from_slice(&coordinate[..dimensions]);
+        }
+        Ok(())
+    }
+
+    /// Reads the body of a point, a line string or a polygon.
+    fn read_simple<Endianness, Reader>(
+        &mut self,
+        geometry_type: GeometryType,
+        reader: &mut Reader,
+    ) -> Result<(), std::io::Error>
+    where
+        Reader: std::io::Read,
+        Endianness: byteorder::ByteOrder,
+    {
+        match geometry_type {
+            GeometryType::Point => self.read_coordinates::<Endianness, Reader>(1, reader),
+            GeometryType::LineString => {
+                let points_n = reader.read_u32::<Endianness>()?;
+                self.read_coordinates::<Endianness, Reader>(points_n, reader)
+            }
+            _ => self.read_rings::<Endianness, Reader>(reader),
+        }
+    }
+
+    /// Reads a part of a multi geometry, which has to have the dimensions of the geometry.
+    fn read_part<Reader>(
+        &mut self,
+        parent: EwkbHeader,
+        part_type: GeometryType,
+        reader: &mut Reader,
+    ) -> Result<(), std::io::Error>
+    where
+        Reader: std::io::Read,
+    {
+        let (byte_order, header) = read_part_header::<Self, Reader>(parent, reader)?;
+        if GeometryType::from(header.g_type) != part_type
+            || header.g_type & Dimension::ZM != self.dimension_flags()
+        {
+            return Err(DecodeErrorReason::InvalidGeometry(format!(
+                "Expected a {:?} part with the dimensions of the geometry",
+                part_type
+            ))
+            .into());
+        }
+        if byte_order == BIG_ENDIAN {
+            self.read_simple::<BigEndian, Reader>(part_type, reader)
+        } else {
+            self.read_simple::<LittleEndian, Reader>(part_type, reader)
+        }
+    }
+
+    fn read_rings<Endianness, Reader>(&mut self, reader: &mut Reader) -> Result<(), std::io::Error>
+    where
+        Reader: std::io::Read,
+        Endianness: byteorder::ByteOrder,
+    {
+        let rings_n = reader.read_u32::<Endianness>()?;
+        for _ in 0..rings_n {
+            let points_n = reader.read_u32::<Endianness>()?;
+            self.read_coordinates::<Endianness, Reader>(points_n, reader)?;
+            self.end_ring();
+        }
+        Ok(())
+    }
+}
+
+/// Checks that `offsets` is a non-empty, non-decreasing list ending at most at `end`.
+fn check_offsets(name: &str, offsets: &[usize], end: usize) -> Result<(), std::io::Error> {
+    let valid = matches!(offsets.last(), Some(last) if *last <= end)
+        && offsets.windows(2).all(|w| w[0] <= w[1]);
+    if valid {
+        Ok(())
+    } else {
+        Err(invalid_flat_geometry(format!(
+            "{} {:?} don't fit {} elements",
+            name, offsets, end
+        )))
+    }
+}
+
+fn invalid_flat_geometry(reason: String) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        format!("Invalid FlatGeometry: {}", reason),
+    )
+}
+
+fn unsupported_collection() -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        "FlatGeometry does not support geometry collections",
+    )
+}
+
+impl EwkbSerializable for FlatGeometry {
+    fn expected_geometry_variant(geometry_type_hint: u32) -> GeometryType {
+        GeometryType::from(geometry_type_hint)
+    }
+
+    fn geometry_type(&self) -> u32 {
+        self.geometry_type as u32 | self.dimension_flags()
+    }
+
+    fn srid(&self) -> Option<u32> {
+        self.srid
+    }
+}
+
+impl FlatGeometry {
+    /// Checks the offsets and the number of values used to write the geometry, as the fields are
+    /// public and may be inconsistent.
+    fn check(&self) -> Result<(), std::io::Error> {
+        let dimensions = self.dimensions();
+        if !self
+            .coordinates
+            .chunks_exact(dimensions)
+            .remainder()
+            .is_empty()
+        {
+            return Err(invalid_flat_geometry(format!(
+                "{} values aren't coordinates of {} values",
+                self.coordinates.len(),
+                dimensions
+            )));
+        }
+        match self.geometry_type {
+            GeometryType::Point if self.coordinates.len() > dimensions => Err(
+                invalid_flat_geometry("a point has a single coordinate".to_string()),
+            ),
+            GeometryType::Polygon | GeometryType::MultiLineString => {
+                check_offsets("ring_offsets", &self.ring_offsets, self.num_coordinates())
+            }
+            GeometryType::MultiPolygon => {
+                check_offsets("ring_offsets", &self.ring_offsets, self.num_coordinates())?;
+                check_offsets(
+                    "polygon_offsets",
+                    &self.polygon_offsets,
+                    self.ring_offsets.len() - 1,
+                )
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl WriteToSql for FlatGeometry {
+    fn write_body<Writer>(&self, out: &mut Writer) -> Result<(), std::io::Error>
+    where
+        Writer: std::io::Write,
+    {
+        self.check()?;
+        let dimensions = self.dimensions();
+        match self.geometry_type {
+            GeometryType::Point => {
+                if self.coordinates.is_empty() {
+                    // empty point
+                    Self::write_values(&[f64::NAN; 4][..dimensions], out)
+                } else {
+                    Self::write_values(&self.coordinates[..dimensions], out)
+                }
+            }
+            GeometryType::LineString => {
+                out.write_u32::<LittleEndian>(self.num_coordinates() as u32)?;
+                Self::write_values(&self.coordinates, out)
+            }
+            GeometryType::Polygon => self.write_rings(&self.ring_offsets, out),
+            GeometryType::MultiPoint => {
+                out.write_u32::<LittleEndian>(self.num_coordinates() as u32)?;
+                for point in self.coordinates.chunks_exact(dimensions) {
+                    self.write_part_header(GeometryType::Point, out)?;
+                    Self::write_values(point, out)?;
+                }
+                Ok(())
+            }
+            GeometryType::MultiLineString => {
+                out.write_u32::<LittleEndian>(self.num_rings() as u32)?;
+                for line in self.ring_offsets.windows(2) {
+                    self.write_part_header(GeometryType::LineString, out)?;
+                    out.write_u32::<LittleEndian>((line[1] - line[0]) as u32)?;
+                    Self::write_values(
+                        &self.coordinates[line[0] * dimensions..line[1] * dimensions],
+                        out,
+                    )?;
+                }
+                Ok(())
+            }
+            GeometryType::MultiPolygon => {
+                out.write_u32::<LittleEndian>(self.num_polygons() as u32)?;
+                for polygon in self.polygon_offsets.windows(2) {
+                    self.write_part_header(GeometryType::Polygon, out)?;
+                    self.write_rings(&self.ring_offsets[polygon[0]..=polygon[1]], out)?;
+                }
+                Ok(())
+            }
+            GeometryType::GeometryCollection => Err(unsupported_collection()),
+        }
+    }
+}
+
+impl ReadFromSql for FlatGeometry {
+    fn read_body<Endianness, Reader>(
+        header: EwkbHeader,
+        reader: &mut Reader,
+    ) -> Result<Self, std::io::Error>
+    where
+        Reader: std::io::Read,
+        Endianness: byteorder::ByteOrder,
+    {
+        let mut geometry = FlatGeometry::new(
+            GeometryType::from(header.g_type),
+            header.g_type & Dimension::Z == Dimension::Z,
+            header.g_type & Dimension::M == Dimension::M,
+            header.srid,
+        );
+        match geometry.geometry_type {
+            GeometryType::Point | GeometryType::LineString | GeometryType::Polygon => {
+                geometry.read_simple::<Endianness, Reader>(geometry.geometry_type, reader)?
+            }
+            GeometryType::MultiPoint => {
+                let points_n = reader.read_u32::<Endianness>()?;
+                for _ in 0..points_n {
+                    geometry.read_part(header, GeometryType::Point, reader)?;
+                }
+            }
+            GeometryType::MultiLineString => {
+                let lines_n = reader.read_u32::<Endianness>()?;
+                for _ in 0..lines_n {
+                    geometry.read_part(header, GeometryType::LineString, reader)?;
+                    geometry.end_ring();
+                }
+            }
+            GeometryType::MultiPolygon => {
+                let polygons_n = reader.read_u32::<Endianness>()?;
+                for _ in 0..polygons_n {
+                    geometry.read_part(header, GeometryType::Polygon, reader)?;
+                    geometry.end_polygon();
+                }
+            }
+            GeometryType::GeometryCollection => return Err(unsupported_collection()),
+        }
+        Ok(geometry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::*;
+
+    fn convert<G, H>(geometry: &G) -> H
+    where
+        G: WriteToSql,
+        H: ReadFromSql,
+    {
+        let mut buffer = Vec::new();
+        geometry.write_to_sql(true, &mut buffer).unwrap();
+        H::read_from_sql(&buffer).unwrap()
+    }
+
+    fn new_ring(offset: f64) -> Vec<PointZ> {
+        vec![
+            PointZ::new(offset, offset, 1.0, Some(4326)),
+            PointZ::new(offset + 1.0, offset, 2.0, Some(4326)),
+            PointZ::new(offset + 1.0, offset + 1.0, 3.0, Some(4326)),
+            PointZ::new(offset, offset, 1.0, Some(4326)),
+        ]
+    }
+
+    fn new_polygon(offset: f64) -> Polygon<PointZ> {
+        let mut polygon = Polygon::new(Some(4326));
+        polygon.add_points(new_ring(offset)).unwrap();
+        polygon
+            .add_ring()
+            .add_points(new_ring(offset + 0.25))
+            .unwrap();
+        polygon
+    }
+
+    #[test]
+    fn test_flat_polygon() {
+        let polygon = new_polygon(0.0);
+        let flat: FlatGeometry = convert(&polygon);
+        assert_eq!(GeometryType::Polygon, flat.geometry_type);
+        assert_eq!(
+            (true, false, Some(4326)),
+            (flat.has_z, flat.has_m, flat.srid)
+        );
+        assert_eq!(vec![0, 4, 8], flat.ring_offsets);
+        assert_eq!(&[1.25, 0.25, 2.0], flat.ring(1).unwrap().get(3..6).unwrap());
+        assert_eq!(Some(&[0.0, 0.0, 1.0][..]), flat.coordinate(0));
+        assert_eq!(None, flat.ring(2));
+        assert_eq!(None, flat.coordinate(8));
+        assert_eq!(polygon, convert::<_, Polygon<PointZ>>(&flat));
+    }
+
+    #[test]
+    fn test_flat_multi_polygon() {
+        let multipolygon = MultiPolygon {
+            polygons: vec![new_polygon(0.0), new_polygon(10.0)],
+            srid: Some(4326),
+        };
+        let flat: FlatGeometry = convert(&multipolygon);
+        assert_eq!(vec![0, 4, 8, 12, 16], flat.ring_offsets);
+        assert_eq!(vec![0, 2, 4], flat.polygon_offsets);
+        assert_eq!(
+            Some(BoundingBox {
+                xmin: 0.0,
+                ymin: 0.0,
+                xmax: 11.25,
+                ymax: 11.25
+            }),
+            flat.bbox()
+        );
+        assert_eq!(multipolygon, convert::<_, MultiPolygon<PointZ>>(&flat));
+    }
+
+    #[test]
+    fn test_flat_points_and_lines() {
+        let point = PointM::new(1.0, 2.0, 3.0, None);
+        let flat: FlatGeometry = convert(&point);
+        assert_eq!(vec![1.0, 2.0, 3.0], flat.coordinates);
+        assert_eq!(point, convert::<_, PointM>(&flat));
+
+        let mut multipoint = MultiPoint::new(None);
+        multipoint.add_points([Point::new(1.0, 2.0, None), Point::new(3.0, 4.0, None)]);
+        let flat: FlatGeometry = convert(&multipoint);
+        assert_eq!(2, flat.num_coordinates());
+        assert_eq!(multipoint, convert::<_, MultiPoint<Point>>(&flat));
+
+        let mut multiline = MultiLineString::new(None);
+        multiline
+            .add_points([Point::new(1.0, 2.0, None), Point::new(3.0, 4.0, None)])
+            .unwrap();
+        multiline
+            .add_line()
+            .add_points([Point::new(5.0, 6.0, None), Point::new(7.0, 8.0, None)])
+            .unwrap();
+        let flat: FlatGeometry = convert(&multiline);
+        assert_eq!(vec![0, 2, 4], flat.ring_offsets);
+        assert_eq!(multiline, convert::<_, MultiLineString<Point>>(&flat));
+
+        let mut line = FlatGeometry::new(GeometryType::LineString, false, false, Some(4326));
+        line.push_coordinate(1.0, 2.0, None, None);
+        line.push_coordinate(3.0, 4.0, None, None);
+        let mut expected = LineString::new(Some(4326));
+        expected
+            .add_points([
+                Point::new(1.0, 2.0, Some(4326)),
+                Point::new(3.0, 4.0, Some(4326)),
+            ])
+            .unwrap();
+        assert_eq!(expected, convert::<_, LineString<Point>>(&line));
+    }
+
+    #[test]
+    fn test_invalid_flat_geometry() {
+        let mut buffer = Vec::new();
+        let mut polygon = FlatGeometry::new(GeometryType::Polygon, false, false, None);
+        polygon.push_coordinate(0.0, 0.0, None, None);
+        polygon.ring_offsets = vec![];
+        assert!(polygon.write_to_sql(true, &mut buffer).is_err());
+        polygon.ring_offsets = vec![0, 2];
+        assert!(polygon.write_to_sql(true, &mut buffer).is_err());
+        polygon.ring_offsets = vec![1, 0];
+        assert!(polygon.write_to_sql(true, &mut buffer).is_err());
+        // the accessors do not panic on the offsets either
+        assert_eq!(None, polygon.ring(0));
+        polygon.ring_offsets = vec![0, 2];
+        assert_eq!(None, polygon.ring(0));
+        assert_eq!(None, polygon.coordinate(usize::MAX));
+        polygon.ring_offsets = vec![];
+        assert_eq!(0, polygon.num_rings());
+
+        let mut point = FlatGeometry::new(GeometryType::Point, true, true, None);
+        point.coordinates = vec![1.0, 2.0];
+        assert!(point.write_to_sql(true, &mut buffer).is_err());
+        point.coordinates = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+        assert!(point.write_to_sql(true, &mut buffer).is_err());
+
+        let mut multipolygon = FlatGeometry::new(GeometryType::MultiPolygon, false, false, None);
+        multipolygon.ring_offsets = vec![];
+        assert!(multipolygon.write_to_sql(true, &mut buffer).is_err());
+        multipolygon.ring_offsets = vec![0];
+        multipolygon.polygon_offsets = vec![0, 1];
+        let error = multipolygon.write_to_sql(true, &mut buffer).unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidInput, error.kind());
+    }
+
+    #[test]
+    fn test_flat_geometry_collection() {
+        let collection = GeometryCollection::<Point>::new(None);
+        let mut buffer = Vec::new();
+        collection.write_to_sql(true, &mut buffer).unwrap();
+        assert!(FlatGeometry::read_from_sql(&buffer).is_err());
+        let flat = FlatGeometry::new(GeometryType::GeometryCollection, false, false, None);
+        assert!(flat.write_to_sql(true, &mut Vec::new()).is_err());
+    }
+}
@@ -1,3 +1,16 @@
+//! PostGIS functions.
+//!
+//! The functions taking `Geometry` arguments have no geography overload in PostGIS, wrap geography
+//! arguments with [`as_geometry`], which casts them to geometry. The other functions are generic
+//! over [`GeoType`] and accept both.
+//!
+//! MySQL shares these functions with PostGIS, so they may be used with the `mysql` feature:
+//!
+//! | Kind | Functions |
+//! |------|-----------|
+//! | Topological relationships | [`st_contains()`], [`st_crosses()`], [`st_disjoint()`], [`st_equals()`], [`st_intersects()`], [`st_overlaps()`], [`st_touches()`], [`st_within()`] |
+//! | Accessors | [`st_x()`], [`st_y()`], [`st_srid()`] |
+//! | Measurements | [`st_area()`], [`st_distance()`], [`st_length()`] |
 #![cfg(feature = "diesel")]
 use crate::sql_types::*;
 use diesel::sql_types::*;
@@ -113,8 +126,11 @@ diesel::define_sql_function! {
 }
 diesel::define_sql_function! {
     /// Divides geometry into parts until a part can be represented using no more than max_vertices.
+    ///
+    /// PostGIS has no geography overload, so unlike in previous versions the argument is
+    /// `Geometry` and not generic over [`GeoType`].
     #[sql_name="ST_Subdivide"]
-    fn st_subdivide<G: GeoType>(geometry: G, max_vertices: Integer, grid_size: Float8) -> G;
+    fn st_subdivide(geometry: Geometry, max_vertices: Integer, grid_size: Float8) -> Geometry;
 }
 diesel::define_sql_function! {
     /// Return the X coordinate of the point, or NULL if not available. Input must be a point.
@@ -126,3 +142,177 @@ diesel::define_sql_function! {
     #[sql_name="ST_Y"]
     fn st_y(geometry: Geometry) -> Double;
 }
+
+//Measurement******************************************************************************
+
+diesel::define_sql_function! {
+    /// Returns the area of a polygonal geometry. For geometry, the area is in units of the spatial reference. For
+    /// geography, the area is in square meters.
+    #[sql_name="ST_Area"]
+    fn st_area<G: GeoType>(geometry: G) -> Double;
+}
+diesel::define_sql_function! {
+    /// Returns the minimum 2D distance between two geometries. For geometry, the distance is in units of the spatial
+    /// reference. For geography, the distance is in meters.
+    #[sql_name="ST_Distance"]
+    fn st_distance<G: GeoType>(left: G, right: G) -> Double;
+}
+diesel::define_sql_function! {
+    /// Returns the 2D length of a linear geometry, polygons have a length of 0.
+    #[sql_name="ST_Length"]
+    fn st_length<G: GeoType>(geometry: G) -> Double;
+}
+diesel::define_sql_function! {
+    /// Returns the spatial reference identifier of the geometry, 0 if it has none.
+    #[sql_name="ST_SRID"]
+    fn st_srid<G: GeoType>(geometry: G) -> Integer;
+}
+
+//Casts************************************************************************************
+
+/// SQL types which may be explicitly cast to `geometry`.
+#[cfg(feature = "postgres")]
+pub trait CastsToGeometry {
+    /// Resulting SQL type, keeps the nullability of the source type.
+    type Output: diesel::expression::TypedExpressionType;
+}
+
+#[cfg(feature = "postgres")]
+impl CastsToGeometry for Geography {
+    type Output = Geometry;
+}
+
+#[cfg(feature = "postgres")]
+impl CastsToGeometry for Nullable<Geography> {
+    type Output = Nullable<Geometry>;
+}
+
+/// Expression which renders as `(expr)::geometry`, created by [`as_geometry`].
+#[cfg(feature = "postgres")]
+#[derive(Debug, Clone, Copy, diesel::query_builder::QueryId, diesel::expression::ValidGrouping)]
+pub struct GeometryCast<E> {
+    expr: E,
+}
+
+#[cfg(feature = "postgres")]
+impl<E> diesel::Expression for GeometryCast<E>
+where
+    E: diesel::Expression,
+    E::SqlType: CastsToGeometry,
+{
+    type SqlType = <E::SqlType as CastsToGeometry>::Output;
+}
+
+#[cfg(feature = "postgres")]
+impl<E, QS> diesel::AppearsOnTable<QS> for GeometryCast<E>
+where
+    E: diesel::AppearsOnTable<QS>,
+    Self: diesel::Expression,
+{
+}
+
+#[cfg(feature = "postgres")]
+impl<E, QS> diesel::SelectableExpression<QS> for GeometryCast<E>
+where
+    E: diesel::SelectableExpression<QS>,
+    Self: diesel::AppearsOnTable<QS>,
+{
+}
+
+#[cfg(feature = "postgres")]
+impl<E> diesel::query_builder::QueryFragment<diesel::pg::Pg> for GeometryCast<E>
+where
+    E: diesel::query_builder::QueryFragment<diesel::pg::Pg>,
+{
+    fn walk_ast<'b>(
+        &'b self,
+        mut out: diesel::query_builder::AstPass<'_, 'b, diesel::pg::Pg>,
+    ) -> diesel::QueryResult<()> {
+        out.push_sql("(");
+        self.expr.walk_ast(out.reborrow())?;
+        out.push_sql(")::geometry");
+        Ok(())
+    }
+}
+
+/// Casts a geography expression to geometry, so it may be passed to the functions which have no geography overload.
+/// ```
+/// use diesel::IntoSql;
+/// use postgis_diesel::functions::{as_geometry, st_contains};
+/// use postgis_diesel::sql_types::Geography;
+/// use postgis_diesel::types::Point;
+/// let point = Point::new(1.0, 1.0, Some(4326)).into_sql::<Geography>();
+/// let _ = st_contains(as_geometry(point), as_geometry(point));
+/// ```
+#[cfg(feature = "postgres")]
+pub fn as_geometry<E>(expr: E) -> GeometryCast<E>
+where
+    E: diesel::Expression,
+    E::SqlType: CastsToGeometry,
+{
+    GeometryCast { expr }
+}
+
+/// Expression which renders as the wrapped `TypedGeometry` expression, created by [`untyped`].
+#[derive(Debug, Clone, Copy, diesel::query_builder::QueryId, diesel::expression::ValidGrouping)]
+pub struct Untyped<E> {
+    expr: E,
+}
+
+impl<E> diesel::Expression for Untyped<E>
+where
+    E: diesel::Expression,
+    E::SqlType: UntypedGeometry,
+{
+    type SqlType = <E::SqlType as UntypedGeometry>::Untyped;
+}
+
+impl<E, QS> diesel::AppearsOnTable<QS> for Untyped<E>
+where
+    E: diesel::AppearsOnTable<QS>,
+    Self: diesel::Expression,
+{
+}
+
+impl<E, QS> diesel::SelectableExpression<QS> for Untyped<E>
+where
+    E: diesel::SelectableExpression<QS>,
+    Self: diesel::AppearsOnTable<QS>,
+{
+}
+
+impl<E, DB> diesel::query_builder::QueryFragment<DB> for Untyped<E>
+where
+    DB: diesel::backend::Backend,
+    E: diesel::query_builder::QueryFragment<DB>,
+{
+    fn walk_ast<'b>(
+        &'b self,
+        out: diesel::query_builder::AstPass<'_, 'b, DB>,
+    ) -> diesel::QueryResult<()> {
+        self.expr.walk_ast(out)
+    }
+}
+
+/// Uses a `TypedGeometry` expression as plain `Geometry`, no cast is needed as both are stored the same way.
+/// ```
+/// #[macro_use] extern crate diesel;
+/// use postgis_diesel::functions::{st_within, untyped};
+/// use postgis_diesel::types::{Point, Polygon};
+/// table! {
+///     use postgis_diesel::sql_types::*;
+///     use diesel::sql_types::*;
+///     places (id) {
+///         id -> Int4,
+///         location -> TypedGeometry<PointKind, 4326>,
+///     }
+/// }
+/// let _ = st_within(untyped(places::location), Polygon::<Point>::new(Some(4326)));
+/// ```
+pub fn untyped<E>(expr: E) -> Untyped<E>
+where
+    E: diesel::Expression,
+    E::SqlType: UntypedGeometry,
+{
+    Untyped { expr }
+}
@@ -1,3 +1,8 @@
+//! PostGIS functions accepting and returning nullable values.
+//!
+//! As in [`functions`](crate::functions), wrap geography arguments of the functions taking
+//! `Geometry` with [`as_geometry`]. The functions [listed](crate::functions) as shared with MySQL
+//! may be used with the `mysql` feature here too.
 #![cfg(feature = "diesel")]
 use crate::sql_types::*;
 use diesel::sql_types::*;
@@ -113,8 +118,11 @@ diesel::define_sql_function! {
 }
 diesel::define_sql_function! {
     /// Divides geometry into parts until a part can be represented using no more than max_vertices.
+    ///
+    /// PostGIS has no geography overload, so unlike in previous versions the argument is
+    /// `Nullable<Geometry>` and not generic over [`GeoType`].
     #[sql_name="ST_Subdivide"]
-    fn st_subdivide<G: GeoType>(geometry: Nullable<G>, max_vertices: Integer, grid_size: Float8) -> Nullable<G>;
+    fn st_subdivide(geometry: Nullable<Geometry>, max_vertices: Integer, grid_size: Float8) -> Nullable<Geometry>;
 }
 diesel::define_sql_function! {
     /// Return the X coordinate of the point, or NULL if not available. Input must be a point.
@@ -126,3 +134,32 @@ diesel::define_sql_function! {
     #[sql_name="ST_Y"]
     fn st_y(geometry: Nullable<Geometry>) -> Nullable<Double>;
 }
+
+//Measurement******************************************************************************
+
+diesel::define_sql_function! {
+    /// Returns the area of a polygonal geometry. For geometry, the area is in units of the spatial reference. For
+    /// geography, the area is in square meters.
+    #[sql_name="ST_Area"]
+    fn st_area<G: GeoType>(geometry: Nullable<G>) -> Nullable<Double>;
+}
+diesel::define_sql_function! {
+    /// Returns the minimum 2D distance between two geometries. For geometry, the distance is in units of the spatial
+    /// reference. For geography, the distance is in meters.
+    #[sql_name="ST_Distance"]
+    fn st_distance<G: GeoType>(left: Nullable<G>, right: Nullable<G>) -> Nullable<Double>;
+}
+diesel::define_sql_function! {
+    /// Returns the 2D length of a linear geometry, polygons have a length of 0.
+    #[sql_name="ST_Length"]
+    fn st_length<G: GeoType>(geometry: Nullable<G>) -> Nullable<Double>;
+}
+diesel::define_sql_function! {
+    /// Returns the spatial reference identifier of the geometry, 0 if it has none.
+    #[sql_name="ST_SRID"]
+    fn st_srid<G: GeoType>(geometry: Nullable<G>) -> Nullable<Integer>;
+}
+
+#[cfg(feature = "postgres")]
+pub use crate::functions::as_geometry;
+pub use crate::functions::untyped;
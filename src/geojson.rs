@@ -1,29 +1,339 @@
 use serde::de::*;
 use serde::ser::*;
 use serde::*;
+use std::cell::Cell;
 use std::fmt;
 use std::marker::PhantomData;
 
+use crate::serde::geojson::{GeoJson, GeoJsonDeserialize, GeoJsonSerialize};
 use crate::types::*;
 
 const WGS84_SRID: Option<u32> = Some(4326);
 
-macro_rules! check_srid_wgs84 {
-    ($x:ident) => {
-        if $x.srid.is_some() && $x.srid != WGS84_SRID {
-            return Err(ser::Error::custom(format!(
-                "Invalid SRID {}",
-                $x.srid.unwrap()
-            )));
+/// Which SRIDs are written to and read from GeoJSON.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SridPolicy {
+    /// RFC 7946, which only has WGS 84: geometries with another SRID than 4326 can't be written,
+    /// and geometries read have SRID 4326.
+    #[default]
+    Strict,
+    /// Geometries with any SRID are written, with a `crs` member of the 2008 GeoJSON
+    /// specification naming it when `crs` is true. Geometries read without `crs` member have SRID
+    /// 4326.
+    AllowAny { crs: bool },
+    /// Geometries with any SRID are written, without `crs` member, and geometries read without
+    /// `crs` member have this SRID.
+    DefaultSrid(Option<u32>),
+}
+
+/// How the M ordinate of `PointM` and `PointZM` is written to and read from GeoJSON, whose
+/// positions have no place for it.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum MeasurePolicy {
+    /// M is the fourth ordinate of the positions, after Z. `PointM` has no Z to put before it, so
+    /// its M is written and read like with `Properties`.
+    #[default]
+    Ordinate,
+    /// M is written in the `m` array of a `properties` member of the geometry, in the order of
+    /// the points, and the positions have no M.
+    Properties,
+    /// M is not written, and is NaN when reading.
+    Drop,
+}
+
+/// Options of the GeoJSON serialization and deserialization, see [`with_geojson_options`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct GeoJsonOptions {
+    /// How the M ordinate is written and read.
+    pub measures: MeasurePolicy,
+    /// Which SRIDs are written and read.
+    pub srid: SridPolicy,
+    /// Writes the `bbox` of features and feature collections without one, computed from their
+    /// geometries.
+    pub compute_bbox: bool,
+    /// Ignores the members of geometries which are not defined by GeoJSON, instead of failing.
+    pub lenient: bool,
+}
+
+const DEFAULT_OPTIONS: GeoJsonOptions = GeoJsonOptions {
+    measures: MeasurePolicy::Ordinate,
+    srid: SridPolicy::Strict,
+    compute_bbox: false,
+    lenient: false,
+};
+
+thread_local! {
+    static OPTIONS: Cell<GeoJsonOptions> = const { Cell::new(DEFAULT_OPTIONS) };
+}
+
+/// Runs `f` with `options` for the GeoJSON serialization and deserialization on the current
+/// thread. They only apply inside `f`, a value serialized later, e.g. by a web framework, carries
+/// its options with [`GeoJson::with_options`](crate::serde::geojson::GeoJson::with_options).
+/// ```
+/// use postgis_diesel::serde::geojson::GeoJson;
+/// use postgis_diesel::types::{with_geojson_options, GeoJsonOptions, Point};
+/// let json = r#"{"type":"Point","coordinates":[7.4,46.9],"title":"Bern"}"#;
+/// assert!(serde_json::from_str::<GeoJson<Point>>(json).is_err());
+/// let options = GeoJsonOptions {
+///     lenient: true,
+///     ..Default::default()
+/// };
+/// let point: GeoJson<Point> =
+///     with_geojson_options(options, || serde_json::from_str(json)).unwrap();
+/// assert_eq!(Point::new(7.4, 46.9, Some(4326)), point.0);
+/// ```
+pub fn with_geojson_options<F, R>(options: GeoJsonOptions, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    struct Restore(GeoJsonOptions);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            OPTIONS.with(|current| current.set(self.0));
         }
+    }
+
+    let _restore = Restore(OPTIONS.with(|current| current.replace(options)));
+    f()
+}
+
+/// Runs `f` with `policy` for the GeoJSON serialization and deserialization of the M ordinate on
+/// the current thread, the other options are unchanged.
+/// ```
+/// use postgis_diesel::serde::geojson::GeoJson;
+/// use postgis_diesel::types::{with_measure_policy, LineString, MeasurePolicy, PointM};
+/// let mut track = LineString::new(Some(4326));
+/// track
+///     .add_points([
+///         PointM::new(7.4, 46.9, 1700000000.0, Some(4326)),
+///         PointM::new(7.5, 47.0, 1700000060.0, Some(4326)),
+///     ])
+///     .unwrap();
+/// let json = with_measure_policy(MeasurePolicy::Properties, || {
+///     serde_json::to_string(&GeoJson(&track))
+/// })
+/// .unwrap();
+/// assert_eq!(
+///     r#"{"type":"LineString","coordinates":[[7.4,46.9],[7.5,47.0]],"properties":{"m":[1700000000.0,1700000060.0]}}"#,
+///     json
+/// );
+/// ```
+pub fn with_measure_policy<F, R>(policy: MeasurePolicy, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let options = GeoJsonOptions {
+        measures: policy,
+        ..options()
     };
+    with_geojson_options(options, f)
+}
+
+pub(crate) fn options() -> GeoJsonOptions {
+    OPTIONS.with(Cell::get)
+}
+
+fn measure_policy() -> MeasurePolicy {
+    options().measures
+}
+
+/// Policy of the M values of the geometry `G`, `Ordinate` is `Properties` for points with M but no
+/// Z, whose positions have no place for M.
+fn measure_policy_of<G: GeoJsonGeometry<V>, V>() -> MeasurePolicy {
+    match measure_policy() {
+        MeasurePolicy::Ordinate if G::M_WITHOUT_Z => MeasurePolicy::Properties,
+        policy => policy,
+    }
+}
+
+/// Legacy `crs` member naming the coordinate reference system of an object.
+#[derive(Serialize, Deserialize)]
+struct Crs {
+    #[serde(rename = "type")]
+    crs_type: String,
+    properties: CrsProperties,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CrsProperties {
+    name: String,
+}
+
+impl Crs {
+    fn epsg(srid: u32) -> Self {
+        Crs {
+            crs_type: "name".to_string(),
+            properties: CrsProperties {
+                name: format!("urn:ogc:def:crs:EPSG::{}", srid),
+            },
+        }
+    }
+
+    /// SRID of an EPSG or OGC CRS84 name, like `urn:ogc:def:crs:EPSG::2056` or `EPSG:2056`.
+    fn srid(&self) -> Result<u32, String> {
+        let name = &self.properties.name;
+        if self.crs_type != "name" {
+            return Err(format!("unsupported crs type `{}`", self.crs_type));
+        }
+        if name.ends_with("CRS84") {
+            return Ok(4326);
+        }
+        name.rsplit(':')
+            .next()
+            .filter(|_| name.contains("EPSG"))
+            .and_then(|code| code.parse().ok())
+            .ok_or_else(|| format!("unsupported crs name `{}`", name))
+    }
+}
+
+/// SRID of a geometry read with the `crs` member `crs`.
+fn read_srid<E: de::Error>(crs: Option<Crs>) -> Result<Option<u32>, E> {
+    let srid = crs.map(|crs| crs.srid().map_err(E::custom)).transpose()?;
+    match options().srid {
+        SridPolicy::Strict => match srid {
+            Some(srid) if Some(srid) != WGS84_SRID => Err(E::custom(format!(
+                "Invalid SRID {}, GeoJSON only has WGS 84",
+                srid
+            ))),
+            _ => Ok(WGS84_SRID),
+        },
+        SridPolicy::AllowAny { .. } => Ok(srid.or(WGS84_SRID)),
+        SridPolicy::DefaultSrid(default) => Ok(srid.or(default)),
+    }
+}
+
+fn check_srid<E: ser::Error>(srid: Option<u32>) -> Result<(), E> {
+    match (options().srid, srid) {
+        (SridPolicy::Strict, Some(srid)) if Some(srid) != WGS84_SRID => {
+            Err(E::custom(format!("Invalid SRID {}", srid)))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Writes the `crs` member of a geometry for `SridPolicy::AllowAny { crs: true }`.
+fn serialize_crs<S: SerializeStruct>(state: &mut S, srid: Option<u32>) -> Result<(), S::Error> {
+    if let (SridPolicy::AllowAny { crs: true }, Some(srid)) = (options().srid, srid) {
+        state.serialize_field("crs", &Crs::epsg(srid))?;
+    }
+    Ok(())
+}
+
+/// Options for the members of a geometry collection, which have the `crs` of the collection.
+fn without_crs(options: GeoJsonOptions) -> GeoJsonOptions {
+    match options.srid {
+        SridPolicy::AllowAny { crs: true } => GeoJsonOptions {
+            srid: SridPolicy::AllowAny { crs: false },
+            ..options
+        },
+        _ => options,
+    }
+}
+
+/// Options for the members of an object with `srid`, which is the SRID of those without `crs`.
+fn with_parent_srid(srid: Option<u32>) -> GeoJsonOptions {
+    match options().srid {
+        // the SRID of the parent is WGS 84, the members may only have it too
+        SridPolicy::Strict => options(),
+        SridPolicy::AllowAny { .. } | SridPolicy::DefaultSrid(_) => GeoJsonOptions {
+            srid: SridPolicy::DefaultSrid(srid),
+            ..options()
+        },
+    }
+}
+
+/// Options for the features of a collection with `foreign_members`, whose `crs` is the SRID of
+/// the geometries without one.
+pub(crate) fn collection_options<E: de::Error>(
+    foreign_members: &serde_json::Map<String, serde_json::Value>,
+) -> Result<GeoJsonOptions, E> {
+    Ok(foreign_members_srid(foreign_members)?.unwrap_or_else(options))
+}
+
+/// SRID of the `crs` foreign member of a feature or a feature collection.
+fn foreign_members_srid<E: de::Error>(
+    foreign_members: &serde_json::Map<String, serde_json::Value>,
+) -> Result<Option<GeoJsonOptions>, E> {
+    foreign_members
+        .get("crs")
+        .map(|crs| {
+            let crs = Crs::deserialize(crs).map_err(E::custom)?;
+            Ok(with_parent_srid(read_srid(Some(crs))?))
+        })
+        .transpose()
+}
+
+/// The `properties` member of a geometry holding its M values.
+#[derive(Serialize, Deserialize)]
+struct MeasureProperties {
+    m: Vec<f64>,
 }
 
 pub trait GeoJsonGeometry<V> {
+    /// Whether the points have M but no Z.
+    const M_WITHOUT_Z: bool = false;
+
     fn to_geo_coordinates(&self) -> Vec<V>;
-    fn from_geo_coordinates(coordinates: Vec<V>) -> Result<Self, PointConstructorError>
+    fn from_geo_coordinates(
+        coordinates: Vec<V>,
+        srid: Option<u32>,
+    ) -> Result<Self, PointConstructorError>
     where
         Self: Sized;
+    /// M values of the points, empty for points without M.
+    fn measures(&self) -> Vec<f64>;
+    /// Replaces the M values of the points, in the order of [`GeoJsonGeometry::measures`].
+    fn set_measures(&mut self, measures: Vec<f64>) -> Result<(), PointConstructorError>;
+}
+
+fn measures_of<'a, T>(points: impl Iterator<Item = &'a T>) -> Vec<f64>
+where
+    T: PointT + 'a,
+{
+    points.filter_map(|point| point.get_m()).collect()
+}
+
+fn set_measures_of<'a, T>(
+    points: impl Iterator<Item = &'a mut T>,
+    measures: Vec<f64>,
+) -> Result<(), PointConstructorError>
+where
+    T: PointT + 'a,
+{
+    let count = |reason: &str| PointConstructorError {
+        reason: format!("{} M values than points", reason),
+    };
+    let mut measures = measures.into_iter();
+    for point in points.filter(|point| point.get_m().is_some()) {
+        let m = measures.next().ok_or_else(|| count("fewer"))?;
+        *point = T::new_point(
+            point.get_x(),
+            point.get_y(),
+            point.get_srid(),
+            point.get_z(),
+            Some(m),
+        )?;
+    }
+    if measures.next().is_some() {
+        return Err(count("more"));
+    }
+    Ok(())
+}
+
+/// Writes the `properties` member of a geometry with M values for `MeasurePolicy::Properties`.
+fn serialize_measures<S, G, V>(state: &mut S, geometry: &G) -> Result<(), S::Error>
+where
+    S: SerializeStruct,
+    G: GeoJsonGeometry<V>,
+{
+    if measure_policy_of::<G, V>() == MeasurePolicy::Properties {
+        let m = geometry.measures();
+        if !m.is_empty() {
+            state.serialize_field("properties", &MeasureProperties { m })?;
+        }
+    }
+    Ok(())
 }
 
 struct GeometryVisitor<T, V> {
@@ -57,7 +367,9 @@ impl<'de, T: GeoJsonGeometry<V>, V: Deserialize<'de>> Visitor<'de> for GeometryV
         M: MapAccess<'de>,
         V: Deserialize<'de>,
     {
-        let mut point: Option<T> = None;
+        let mut coordinates: Option<Vec<V>> = None;
+        let mut properties: Option<MeasureProperties> = None;
+        let mut crs: Option<Crs> = None;
         while let Some(key) = access.next_key::<String>()? {
             match key.as_str() {
                 "type" => {
@@ -70,25 +382,43 @@ impl<'de, T: GeoJsonGeometry<V>, V: Deserialize<'de>> Visitor<'de> for GeometryV
                     }
                 }
                 "coordinates" => {
-                    let v = access.next_value::<Vec<V>>()?;
-                    match T::from_geo_coordinates(v) {
-                        Ok(p) => point = Some(p),
-                        Err(err) => {
-                            return Err(de::Error::custom(format!("invalid coordinates: {}", err)));
-                        }
-                    }
+                    coordinates = Some(access.next_value::<Vec<V>>()?);
+                }
+                "crs" => {
+                    crs = Some(access.next_value::<Crs>()?);
+                }
+                "properties" if measure_policy_of::<T, V>() == MeasurePolicy::Properties => {
+                    properties = Some(access.next_value::<MeasureProperties>()?);
+                }
+                // the geometry types have no field for a bounding box, so it is dropped, the one of
+                // the enclosing feature is kept
+                "properties" | "bbox" => {
+                    access.next_value::<IgnoredAny>()?;
+                }
+                _ if options().lenient => {
+                    access.next_value::<IgnoredAny>()?;
                 }
                 _ => {
-                    return Err(de::Error::unknown_field(&key, &["type", "coordinates"]));
+                    return Err(de::Error::unknown_field(
+                        &key,
+                        &["type", "coordinates", "properties", "bbox", "crs"],
+                    ));
                 }
             }
         }
 
-        if point.is_none() {
-            return Err(de::Error::missing_field("coordinates"));
+        let coordinates = coordinates.ok_or_else(|| de::Error::missing_field("coordinates"))?;
+        let mut point = T::from_geo_coordinates(coordinates, read_srid(crs)?)
+            .map_err(|err| de::Error::custom(format!("invalid coordinates: {}", err)))?;
+        // the positions were read without M, which is NaN until it is read from the properties
+        if measure_policy_of::<T, V>() == MeasurePolicy::Properties && !point.measures().is_empty()
+        {
+            let properties = properties.ok_or_else(|| de::Error::missing_field("properties"))?;
+            point
+                .set_measures(properties.m)
+                .map_err(|err| de::Error::custom(format!("invalid M values: {}", err)))?;
         }
-
-        Ok(point.unwrap())
+        Ok(point)
     }
 }
 
@@ -97,31 +427,44 @@ impl GeoJsonGeometry<f64> for Point {
         vec![self.x, self.y]
     }
 
-    fn from_geo_coordinates(coordinates: Vec<f64>) -> Result<Self, PointConstructorError> {
+    fn from_geo_coordinates(
+        coordinates: Vec<f64>,
+        srid: Option<u32>,
+    ) -> Result<Self, PointConstructorError> {
         if coordinates.len() != 2 {
             return Err(PointConstructorError {
                 reason: format!("invalid size {:?} for Point", coordinates.len()).to_string(),
             });
         }
-        Ok(Point::new(coordinates[0], coordinates[1], WGS84_SRID))
+        Ok(Point::new(coordinates[0], coordinates[1], srid))
+    }
+
+    fn measures(&self) -> Vec<f64> {
+        Vec::new()
+    }
+
+    fn set_measures(&mut self, measures: Vec<f64>) -> Result<(), PointConstructorError> {
+        set_measures_of(std::iter::once(self), measures)
     }
 }
 
-impl Serialize for Point {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+impl GeoJsonSerialize for Point {
+    fn serialize_geojson<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        check_srid_wgs84!(self);
+        check_srid(self.srid)?;
         let mut state = serializer.serialize_struct("Point", 2)?;
         state.serialize_field("type", "Point")?;
         state.serialize_field("coordinates", &self.to_geo_coordinates())?;
+        serialize_measures(&mut state, self)?;
+        serialize_crs(&mut state, self.srid)?;
         state.end()
     }
 }
 
-impl<'de> Deserialize<'de> for Point {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+impl GeoJsonDeserialize for Point {
+    fn deserialize_geojson<'de, D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -134,7 +477,10 @@ impl GeoJsonGeometry<f64> for PointZ {
         vec![self.x, self.y, self.z]
     }
 
-    fn from_geo_coordinates(coordinates: Vec<f64>) -> Result<Self, PointConstructorError> {
+    fn from_geo_coordinates(
+        coordinates: Vec<f64>,
+        srid: Option<u32>,
+    ) -> Result<Self, PointConstructorError> {
         if coordinates.len() != 3 {
             return Err(PointConstructorError {
                 reason: format!("invalid size {:?} for PointZ", coordinates.len()).to_string(),
@@ -144,26 +490,36 @@ impl GeoJsonGeometry<f64> for PointZ {
             coordinates[0],
             coordinates[1],
             coordinates[2],
-            WGS84_SRID,
+            srid,
         ))
     }
+
+    fn measures(&self) -> Vec<f64> {
+        Vec::new()
+    }
+
+    fn set_measures(&mut self, measures: Vec<f64>) -> Result<(), PointConstructorError> {
+        set_measures_of(std::iter::once(self), measures)
+    }
 }
 
-impl Serialize for PointZ {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+impl GeoJsonSerialize for PointZ {
+    fn serialize_geojson<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        check_srid_wgs84!(self);
+        check_srid(self.srid)?;
         let mut state = serializer.serialize_struct("Point", 2)?;
         state.serialize_field("type", "Point")?;
         state.serialize_field("coordinates", &self.to_geo_coordinates())?;
+        serialize_measures(&mut state, self)?;
+        serialize_crs(&mut state, self.srid)?;
         state.end()
     }
 }
 
-impl<'de> Deserialize<'de> for PointZ {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+impl GeoJsonDeserialize for PointZ {
+    fn deserialize_geojson<'de, D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -171,7 +527,125 @@ impl<'de> Deserialize<'de> for PointZ {
     }
 }
 
+/// The positions never have M, which is in the properties or dropped, see [`MeasurePolicy`].
+impl GeoJsonGeometry<f64> for PointM {
+    const M_WITHOUT_Z: bool = true;
+
+    fn to_geo_coordinates(&self) -> Vec<f64> {
+        vec![self.x, self.y]
+    }
+
+    fn from_geo_coordinates(
+        coordinates: Vec<f64>,
+        srid: Option<u32>,
+    ) -> Result<Self, PointConstructorError> {
+        if coordinates.len() != 2 {
+            return Err(PointConstructorError {
+                reason: format!("invalid size {:?} for PointM", coordinates.len()),
+            });
+        }
+        Ok(PointM::new(coordinates[0], coordinates[1], f64::NAN, srid))
+    }
+
+    fn measures(&self) -> Vec<f64> {
+        vec![self.m]
+    }
+
+    fn set_measures(&mut self, measures: Vec<f64>) -> Result<(), PointConstructorError> {
+        set_measures_of(std::iter::once(self), measures)
+    }
+}
+
+impl GeoJsonSerialize for PointM {
+    fn serialize_geojson<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        check_srid(self.srid)?;
+        let mut state = serializer.serialize_struct("Point", 2)?;
+        state.serialize_field("type", "Point")?;
+        state.serialize_field("coordinates", &self.to_geo_coordinates())?;
+        serialize_measures(&mut state, self)?;
+        serialize_crs(&mut state, self.srid)?;
+        state.end()
+    }
+}
+
+impl GeoJsonDeserialize for PointM {
+    fn deserialize_geojson<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(GeometryVisitor::<PointM, f64>::new("Point"))
+    }
+}
+
+impl GeoJsonGeometry<f64> for PointZM {
+    fn to_geo_coordinates(&self) -> Vec<f64> {
+        match measure_policy() {
+            MeasurePolicy::Ordinate => vec![self.x, self.y, self.z, self.m],
+            MeasurePolicy::Properties | MeasurePolicy::Drop => vec![self.x, self.y, self.z],
+        }
+    }
+
+    fn from_geo_coordinates(
+        coordinates: Vec<f64>,
+        srid: Option<u32>,
+    ) -> Result<Self, PointConstructorError> {
+        let m = match (measure_policy(), coordinates.len()) {
+            (MeasurePolicy::Ordinate, 4) => coordinates[3],
+            (MeasurePolicy::Properties, 3) | (MeasurePolicy::Drop, 3) => f64::NAN,
+            (_, len) => {
+                return Err(PointConstructorError {
+                    reason: format!("invalid size {:?} for PointZM", len),
+                });
+            }
+        };
+        Ok(PointZM::new(
+            coordinates[0],
+            coordinates[1],
+            coordinates[2],
+            m,
+            srid,
+        ))
+    }
+
+    fn measures(&self) -> Vec<f64> {
+        vec![self.m]
+    }
+
+    fn set_measures(&mut self, measures: Vec<f64>) -> Result<(), PointConstructorError> {
+        set_measures_of(std::iter::once(self), measures)
+    }
+}
+
+impl GeoJsonSerialize for PointZM {
+    fn serialize_geojson<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        check_srid(self.srid)?;
+        let mut state = serializer.serialize_struct("Point", 2)?;
+        state.serialize_field("type", "Point")?;
+        state.serialize_field("coordinates", &self.to_geo_coordinates())?;
+        serialize_measures(&mut state, self)?;
+        serialize_crs(&mut state, self.srid)?;
+        state.end()
+    }
+}
+
+impl GeoJsonDeserialize for PointZM {
+    fn deserialize_geojson<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(GeometryVisitor::<PointZM, f64>::new("Point"))
+    }
+}
+
 impl<T: GeoJsonGeometry<f64> + PointT> GeoJsonGeometry<Vec<f64>> for MultiPoint<T> {
+    const M_WITHOUT_Z: bool = T::M_WITHOUT_Z;
+
     fn to_geo_coordinates(&self) -> Vec<Vec<f64>> {
         self.points
             .iter()
@@ -179,35 +653,48 @@ impl<T: GeoJsonGeometry<f64> + PointT> GeoJsonGeometry<Vec<f64>> for MultiPoint<
             .collect::<Vec<Vec<f64>>>()
     }
 
-    fn from_geo_coordinates(coordinates: Vec<Vec<f64>>) -> Result<Self, PointConstructorError> {
+    fn from_geo_coordinates(
+        coordinates: Vec<Vec<f64>>,
+        srid: Option<u32>,
+    ) -> Result<Self, PointConstructorError> {
         let mut multi_point = MultiPoint::<T> {
             points: vec![],
-            srid: WGS84_SRID,
+            srid,
         };
         for p in coordinates {
-            multi_point.points.push(T::from_geo_coordinates(p)?);
+            multi_point.points.push(T::from_geo_coordinates(p, srid)?);
         }
         Ok(multi_point)
     }
+
+    fn measures(&self) -> Vec<f64> {
+        measures_of(self.points.iter())
+    }
+
+    fn set_measures(&mut self, measures: Vec<f64>) -> Result<(), PointConstructorError> {
+        set_measures_of(self.points.iter_mut(), measures)
+    }
 }
 
-impl<T: GeoJsonGeometry<f64> + PointT> Serialize for MultiPoint<T> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+impl<T: GeoJsonGeometry<f64> + PointT> GeoJsonSerialize for MultiPoint<T> {
+    fn serialize_geojson<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        check_srid_wgs84!(self);
+        check_srid(self.srid)?;
         let coordinates = self.to_geo_coordinates();
 
         let mut state = serializer.serialize_struct("MultiPoint", 2)?;
         state.serialize_field("type", "MultiPoint")?;
         state.serialize_field("coordinates", &coordinates)?;
+        serialize_measures(&mut state, self)?;
+        serialize_crs(&mut state, self.srid)?;
         state.end()
     }
 }
 
-impl<'de, T: GeoJsonGeometry<f64> + PointT> Deserialize<'de> for MultiPoint<T> {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+impl<T: GeoJsonGeometry<f64> + PointT> GeoJsonDeserialize for MultiPoint<T> {
+    fn deserialize_geojson<'de, D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -218,6 +705,8 @@ impl<'de, T: GeoJsonGeometry<f64> + PointT> Deserialize<'de> for MultiPoint<T> {
 }
 
 impl<T: GeoJsonGeometry<f64> + PointT> GeoJsonGeometry<Vec<f64>> for LineString<T> {
+    const M_WITHOUT_Z: bool = T::M_WITHOUT_Z;
+
     fn to_geo_coordinates(&self) -> Vec<Vec<f64>> {
         self.points
             .iter()
@@ -225,35 +714,48 @@ impl<T: GeoJsonGeometry<f64> + PointT> GeoJsonGeometry<Vec<f64>> for LineString<
             .collect::<Vec<Vec<f64>>>()
     }
 
-    fn from_geo_coordinates(coordinates: Vec<Vec<f64>>) -> Result<Self, PointConstructorError> {
+    fn from_geo_coordinates(
+        coordinates: Vec<Vec<f64>>,
+        srid: Option<u32>,
+    ) -> Result<Self, PointConstructorError> {
         let mut multi_point = LineString::<T> {
             points: vec![],
-            srid: WGS84_SRID,
+            srid,
         };
         for p in coordinates {
-            multi_point.points.push(T::from_geo_coordinates(p)?);
+            multi_point.points.push(T::from_geo_coordinates(p, srid)?);
         }
         Ok(multi_point)
     }
+
+    fn measures(&self) -> Vec<f64> {
+        measures_of(self.points.iter())
+    }
+
+    fn set_measures(&mut self, measures: Vec<f64>) -> Result<(), PointConstructorError> {
+        set_measures_of(self.points.iter_mut(), measures)
+    }
 }
 
-impl<T: GeoJsonGeometry<f64> + PointT> Serialize for LineString<T> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+impl<T: GeoJsonGeometry<f64> + PointT> GeoJsonSerialize for LineString<T> {
+    fn serialize_geojson<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        check_srid_wgs84!(self);
+        check_srid(self.srid)?;
         let coordinates = self.to_geo_coordinates();
 
         let mut state = serializer.serialize_struct("LineString", 2)?;
         state.serialize_field("type", "LineString")?;
         state.serialize_field("coordinates", &coordinates)?;
+        serialize_measures(&mut state, self)?;
+        serialize_crs(&mut state, self.srid)?;
         state.end()
     }
 }
 
-impl<'de, T: GeoJsonGeometry<f64> + PointT> Deserialize<'de> for LineString<T> {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+impl<T: GeoJsonGeometry<f64> + PointT> GeoJsonDeserialize for LineString<T> {
+    fn deserialize_geojson<'de, D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -264,6 +766,8 @@ impl<'de, T: GeoJsonGeometry<f64> + PointT> Deserialize<'de> for LineString<T> {
 }
 
 impl<T: GeoJsonGeometry<f64> + PointT> GeoJsonGeometry<Vec<Vec<f64>>> for MultiLineString<T> {
+    const M_WITHOUT_Z: bool = T::M_WITHOUT_Z;
+
     fn to_geo_coordinates(&self) -> Vec<Vec<Vec<f64>>> {
         self.lines
             .iter()
@@ -273,37 +777,53 @@ impl<T: GeoJsonGeometry<f64> + PointT> GeoJsonGeometry<Vec<Vec<f64>>> for MultiL
 
     fn from_geo_coordinates(
         coordinates: Vec<Vec<Vec<f64>>>,
+        srid: Option<u32>,
     ) -> Result<Self, PointConstructorError> {
         let mut multi_line_string = MultiLineString::<T> {
             lines: vec![],
-            srid: WGS84_SRID,
+            srid,
         };
         for line in coordinates {
             multi_line_string
                 .lines
-                .push(LineString::from_geo_coordinates(line)?);
+                .push(LineString::from_geo_coordinates(line, srid)?);
         }
         Ok(multi_line_string)
     }
+
+    fn measures(&self) -> Vec<f64> {
+        measures_of(self.lines.iter().flat_map(|line| line.points.iter()))
+    }
+
+    fn set_measures(&mut self, measures: Vec<f64>) -> Result<(), PointConstructorError> {
+        set_measures_of(
+            self.lines
+                .iter_mut()
+                .flat_map(|line| line.points.iter_mut()),
+            measures,
+        )
+    }
 }
 
-impl<T: GeoJsonGeometry<f64> + PointT> Serialize for MultiLineString<T> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+impl<T: GeoJsonGeometry<f64> + PointT> GeoJsonSerialize for MultiLineString<T> {
+    fn serialize_geojson<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        check_srid_wgs84!(self);
+        check_srid(self.srid)?;
         let coordinates = self.to_geo_coordinates();
 
         let mut state = serializer.serialize_struct("MultiLineString", 2)?;
         state.serialize_field("type", "MultiLineString")?;
         state.serialize_field("coordinates", &coordinates)?;
+        serialize_measures(&mut state, self)?;
+        serialize_crs(&mut state, self.srid)?;
         state.end()
     }
 }
 
-impl<'de, T: GeoJsonGeometry<f64> + PointT> Deserialize<'de> for MultiLineString<T> {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+impl<T: GeoJsonGeometry<f64> + PointT> GeoJsonDeserialize for MultiLineString<T> {
+    fn deserialize_geojson<'de, D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -314,6 +834,8 @@ impl<'de, T: GeoJsonGeometry<f64> + PointT> Deserialize<'de> for MultiLineString
 }
 
 impl<T: GeoJsonGeometry<f64> + PointT> GeoJsonGeometry<Vec<Vec<f64>>> for Polygon<T> {
+    const M_WITHOUT_Z: bool = T::M_WITHOUT_Z;
+
     fn to_geo_coordinates(&self) -> Vec<Vec<Vec<f64>>> {
         self.rings
             .iter()
@@ -323,38 +845,51 @@ impl<T: GeoJsonGeometry<f64> + PointT> GeoJsonGeometry<Vec<Vec<f64>>> for Polygo
 
     fn from_geo_coordinates(
         coordinates: Vec<Vec<Vec<f64>>>,
+        srid: Option<u32>,
     ) -> Result<Self, PointConstructorError> {
         let mut polygon = Polygon::<T> {
             rings: vec![],
-            srid: WGS84_SRID,
+            srid,
         };
         for ring in coordinates {
             polygon.add_ring();
             for p in ring {
-                polygon.add_point(T::from_geo_coordinates(p)?).unwrap();
+                polygon
+                    .add_point(T::from_geo_coordinates(p, srid)?)
+                    .unwrap();
             }
         }
         Ok(polygon)
     }
+
+    fn measures(&self) -> Vec<f64> {
+        measures_of(self.rings.iter().flatten())
+    }
+
+    fn set_measures(&mut self, measures: Vec<f64>) -> Result<(), PointConstructorError> {
+        set_measures_of(self.rings.iter_mut().flatten(), measures)
+    }
 }
 
-impl<T: GeoJsonGeometry<f64> + PointT> Serialize for Polygon<T> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+impl<T: GeoJsonGeometry<f64> + PointT> GeoJsonSerialize for Polygon<T> {
+    fn serialize_geojson<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        check_srid_wgs84!(self);
+        check_srid(self.srid)?;
         let coordinates = self.to_geo_coordinates();
 
         let mut state = serializer.serialize_struct("Polygon", 2)?;
         state.serialize_field("type", "Polygon")?;
         state.serialize_field("coordinates", &coordinates)?;
+        serialize_measures(&mut state, self)?;
+        serialize_crs(&mut state, self.srid)?;
         state.end()
     }
 }
 
-impl<'de, T: GeoJsonGeometry<f64> + PointT> Deserialize<'de> for Polygon<T> {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+impl<T: GeoJsonGeometry<f64> + PointT> GeoJsonDeserialize for Polygon<T> {
+    fn deserialize_geojson<'de, D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -363,6 +898,8 @@ impl<'de, T: GeoJsonGeometry<f64> + PointT> Deserialize<'de> for Polygon<T> {
 }
 
 impl<T: GeoJsonGeometry<f64> + PointT> GeoJsonGeometry<Vec<Vec<Vec<f64>>>> for MultiPolygon<T> {
+    const M_WITHOUT_Z: bool = T::M_WITHOUT_Z;
+
     fn to_geo_coordinates(&self) -> Vec<Vec<Vec<Vec<f64>>>> {
         self.polygons
             .iter()
@@ -372,36 +909,56 @@ impl<T: GeoJsonGeometry<f64> + PointT> GeoJsonGeometry<Vec<Vec<Vec<f64>>>> for M
 
     fn from_geo_coordinates(
         coordinates: Vec<Vec<Vec<Vec<f64>>>>,
+        srid: Option<u32>,
     ) -> Result<Self, PointConstructorError> {
         let mut multi_polygon = MultiPolygon::<T> {
             polygons: vec![],
-            srid: WGS84_SRID,
+            srid,
         };
         for coordinate in coordinates {
-            let polygon = Polygon::<T>::from_geo_coordinates(coordinate)?;
+            let polygon = Polygon::<T>::from_geo_coordinates(coordinate, srid)?;
             multi_polygon.polygons.push(polygon);
         }
         Ok(multi_polygon)
     }
+
+    fn measures(&self) -> Vec<f64> {
+        measures_of(
+            self.polygons
+                .iter()
+                .flat_map(|polygon| polygon.rings.iter().flatten()),
+        )
+    }
+
+    fn set_measures(&mut self, measures: Vec<f64>) -> Result<(), PointConstructorError> {
+        set_measures_of(
+            self.polygons
+                .iter_mut()
+                .flat_map(|polygon| polygon.rings.iter_mut().flatten()),
+            measures,
+        )
+    }
 }
 
-impl<T: GeoJsonGeometry<f64> + PointT> Serialize for MultiPolygon<T> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+impl<T: GeoJsonGeometry<f64> + PointT> GeoJsonSerialize for MultiPolygon<T> {
+    fn serialize_geojson<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        check_srid_wgs84!(self);
+        check_srid(self.srid)?;
         let coordinates = self.to_geo_coordinates();
 
         let mut state = serializer.serialize_struct("MultiPolygon", 2)?;
         state.serialize_field("type", "MultiPolygon")?;
         state.serialize_field("coordinates", &coordinates)?;
+        serialize_measures(&mut state, self)?;
+        serialize_crs(&mut state, self.srid)?;
         state.end()
     }
 }
 
-impl<'de, T: GeoJsonGeometry<f64> + PointT> Deserialize<'de> for MultiPolygon<T> {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+impl<T: GeoJsonGeometry<f64> + PointT> GeoJsonDeserialize for MultiPolygon<T> {
+    fn deserialize_geojson<'de, D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
@@ -411,31 +968,88 @@ impl<'de, T: GeoJsonGeometry<f64> + PointT> Deserialize<'de> for MultiPolygon<T>
     }
 }
 
-impl<T: GeoJsonGeometry<f64> + PointT + Serialize> Serialize for GeometryContainer<T> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+impl<T: GeoJsonGeometry<f64> + PointT + GeoJsonSerialize> GeoJsonSerialize
+    for GeometryContainer<T>
+{
+    fn serialize_geojson<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
         match self {
-            GeometryContainer::Point(g) => g.serialize(serializer),
-            GeometryContainer::MultiPoint(g) => g.serialize(serializer),
-            GeometryContainer::LineString(g) => g.serialize(serializer),
-            GeometryContainer::MultiLineString(g) => g.serialize(serializer),
-            GeometryContainer::Polygon(g) => g.serialize(serializer),
-            GeometryContainer::MultiPolygon(g) => g.serialize(serializer),
-            GeometryContainer::GeometryCollection(g) => g.serialize(serializer),
+            GeometryContainer::Point(g) => g.serialize_geojson(serializer),
+            GeometryContainer::MultiPoint(g) => g.serialize_geojson(serializer),
+            GeometryContainer::LineString(g) => g.serialize_geojson(serializer),
+            GeometryContainer::MultiLineString(g) => g.serialize_geojson(serializer),
+            GeometryContainer::Polygon(g) => g.serialize_geojson(serializer),
+            GeometryContainer::MultiPolygon(g) => g.serialize_geojson(serializer),
+            GeometryContainer::GeometryCollection(g) => g.serialize_geojson(serializer),
         }
     }
 }
 
-impl<T: GeoJsonGeometry<f64> + PointT + Serialize> Serialize for GeometryCollection<T> {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+impl<T: GeoJsonGeometry<f64> + PointT + GeoJsonDeserialize> GeoJsonDeserialize
+    for GeometryContainer<T>
+{
+    fn deserialize_geojson<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // the geometry is kept as JSON until its type is known
+        let geometry = serde_json::Value::deserialize(deserializer)?;
+        let geometry_type = match geometry.get("type") {
+            Some(serde_json::Value::String(geometry_type)) => geometry_type.clone(),
+            Some(_) => return Err(de::Error::custom("invalid type, expected a string")),
+            None => return Err(de::Error::missing_field("type")),
+        };
+        match geometry_type.as_str() {
+            "Point" => T::deserialize_geojson(geometry).map(GeometryContainer::Point),
+            "MultiPoint" => {
+                MultiPoint::deserialize_geojson(geometry).map(GeometryContainer::MultiPoint)
+            }
+            "LineString" => {
+                LineString::deserialize_geojson(geometry).map(GeometryContainer::LineString)
+            }
+            "MultiLineString" => MultiLineString::deserialize_geojson(geometry)
+                .map(GeometryContainer::MultiLineString),
+            "Polygon" => Polygon::deserialize_geojson(geometry).map(GeometryContainer::Polygon),
+            "MultiPolygon" => {
+                MultiPolygon::deserialize_geojson(geometry).map(GeometryContainer::MultiPolygon)
+            }
+            "GeometryCollection" => GeometryCollection::deserialize_geojson(geometry)
+                .map(GeometryContainer::GeometryCollection),
+            other => {
+                return Err(de::Error::unknown_variant(
+                    other,
+                    &[
+                        "Point",
+                        "LineString",
+                        "Polygon",
+                        "MultiPoint",
+                        "MultiLineString",
+                        "MultiPolygon",
+                        "GeometryCollection",
+                    ],
+                ))
+            }
+        }
+        .map_err(de::Error::custom)
+    }
+}
+
+impl<T: GeoJsonGeometry<f64> + PointT + GeoJsonSerialize> GeoJsonSerialize
+    for GeometryCollection<T>
+{
+    fn serialize_geojson<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
+        check_srid(self.srid)?;
         let mut state = serializer.serialize_struct("GeometryCollection", 2)?;
         state.serialize_field("type", "GeometryCollection")?;
-        state.serialize_field("geometries", &self.geometries)?;
+        with_geojson_options(without_crs(options()), || {
+            state.serialize_field("geometries", &GeoJson(&self.geometries))
+        })?;
+        serialize_crs(&mut state, self.srid)?;
         state.end()
     }
 }
@@ -452,7 +1066,7 @@ impl<T> GeometryCollectionVisitor<T> {
     }
 }
 
-impl<'de, T: GeoJsonGeometry<f64> + PointT + Deserialize<'de>> Visitor<'de>
+impl<'de, T: GeoJsonGeometry<f64> + PointT + GeoJsonDeserialize> Visitor<'de>
     for GeometryCollectionVisitor<T>
 {
     // The type that our Visitor is going to produce.
@@ -470,7 +1084,9 @@ impl<'de, T: GeoJsonGeometry<f64> + PointT + Deserialize<'de>> Visitor<'de>
     where
         M: MapAccess<'de>,
     {
-        let mut geometries: Option<Vec<GeometryContainer<T>>> = None;
+        // the members are kept as JSON until the crs of the collection is known
+        let mut geometries: Option<Vec<serde_json::Value>> = None;
+        let mut crs: Option<Crs> = None;
         while let Some(key) = access.next_key::<String>()? {
             match key.as_str() {
                 "type" => {
@@ -483,68 +1099,407 @@ impl<'de, T: GeoJsonGeometry<f64> + PointT + Deserialize<'de>> Visitor<'de>
                     }
                 }
                 "geometries" => {
-                    let v = access.next_value::<Vec<GeometryContainer<T>>>()?;
-                    geometries = Some(v);
+                    geometries = Some(access.next_value()?);
+                }
+                "crs" => {
+                    crs = Some(access.next_value::<Crs>()?);
+                }
+                "bbox" => {
+                    access.next_value::<IgnoredAny>()?;
+                }
+                _ if options().lenient => {
+                    access.next_value::<IgnoredAny>()?;
                 }
                 _ => {
-                    return Err(de::Error::unknown_field(&key, &["type", "coordinates"]));
+                    return Err(de::Error::unknown_field(
+                        &key,
+                        &["type", "geometries", "bbox", "crs"],
+                    ));
                 }
             }
         }
 
-        if geometries.is_none() {
-            return Err(de::Error::missing_field("geometries"));
+        let geometries = geometries.ok_or_else(|| de::Error::missing_field("geometries"))?;
+        let srid = read_srid(crs)?;
+        let geometries = with_geojson_options(with_parent_srid(srid), || {
+            geometries
+                .into_iter()
+                .map(GeometryContainer::<T>::deserialize_geojson)
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(de::Error::custom)?;
+        Ok(GeometryCollection::<T> { geometries, srid })
+    }
+}
+
+impl<T: GeoJsonGeometry<f64> + PointT + GeoJsonDeserialize> GeoJsonDeserialize
+    for GeometryCollection<T>
+{
+    fn deserialize_geojson<'de, D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(GeometryCollectionVisitor::<T>::new())
+    }
+}
+
+impl From<String> for FeatureId {
+    fn from(id: String) -> Self {
+        FeatureId::String(id)
+    }
+}
+
+impl From<&str> for FeatureId {
+    fn from(id: &str) -> Self {
+        FeatureId::String(id.to_string())
+    }
+}
+
+macro_rules! impl_feature_id_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for FeatureId {
+                fn from(id: $t) -> Self {
+                    FeatureId::Number(id.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_feature_id_from_integer!(i32, u32, i64, u64);
+
+fn geometry_points<'a, T: PointT>(geometry: &'a GeometryContainer<T>, points: &mut Vec<&'a T>) {
+    match geometry {
+        GeometryContainer::Point(g) => points.push(g),
+        GeometryContainer::MultiPoint(g) => points.extend(&g.points),
+        GeometryContainer::LineString(g) => points.extend(&g.points),
+        GeometryContainer::MultiLineString(g) => {
+            points.extend(g.lines.iter().flat_map(|line| &line.points))
         }
+        GeometryContainer::Polygon(g) => points.extend(g.rings.iter().flatten()),
+        GeometryContainer::MultiPolygon(g) => points.extend(
+            g.polygons
+                .iter()
+                .flat_map(|polygon| polygon.rings.iter().flatten()),
+        ),
+        GeometryContainer::GeometryCollection(g) => {
+            for geometry in &g.geometries {
+                geometry_points(geometry, points);
+            }
+        }
+    }
+}
 
-        Ok(GeometryCollection::<T> {
-            geometries: geometries.unwrap(),
-            srid: WGS84_SRID,
+/// GeoJSON `bbox` of the points, with Z when all of them have it, `None` without points.
+fn bbox_of<T: PointT>(points: &[&T]) -> Option<Vec<f64>> {
+    let has_z = points.iter().all(|point| point.get_z().is_some());
+    let mut bbox: Option<(Vec<f64>, Vec<f64>)> = None;
+    for point in points {
+        let mut position = vec![point.get_x(), point.get_y()];
+        if has_z {
+            position.extend(point.get_z());
+        }
+        if position.iter().any(|value| value.is_nan()) {
+            continue;
+        }
+        match &mut bbox {
+            None => bbox = Some((position.clone(), position)),
+            Some((min, max)) => {
+                for (i, value) in position.into_iter().enumerate() {
+                    min[i] = min[i].min(value);
+                    max[i] = max[i].max(value);
+                }
+            }
+        }
+    }
+    bbox.map(|(mut min, max)| {
+        min.extend(max);
+        min
+    })
+}
+
+fn features_bbox<'a, T, P>(features: impl Iterator<Item = &'a Feature<T, P>>) -> Option<Vec<f64>>
+where
+    T: PointT + 'a,
+    P: Serialize + 'a,
+{
+    let mut points = Vec::new();
+    for geometry in features.filter_map(|feature| feature.geometry.as_ref()) {
+        geometry_points(geometry, &mut points);
+    }
+    bbox_of(&points)
+}
+
+fn serialize_foreign_members<M>(
+    map: &mut M,
+    foreign_members: &serde_json::Map<String, serde_json::Value>,
+    reserved: &[&str],
+) -> Result<(), M::Error>
+where
+    M: SerializeMap,
+{
+    for (key, value) in foreign_members {
+        if !reserved.contains(&key.as_str()) {
+            map.serialize_entry(key, value)?;
+        }
+    }
+    Ok(())
+}
+
+/// Members of a Feature, the foreign members are those left after the GeoJSON ones.
+#[derive(Deserialize)]
+#[serde(bound(deserialize = "P: Deserialize<'de>"))]
+struct FeatureMembers<P> {
+    #[serde(rename = "type")]
+    geojson_type: String,
+    #[serde(default)]
+    id: Option<FeatureId>,
+    #[serde(default)]
+    bbox: Option<Vec<f64>>,
+    /// Kept as JSON until the `crs` foreign member is known.
+    #[serde(default)]
+    geometry: Option<serde_json::Value>,
+    #[serde(default)]
+    properties: Option<P>,
+    #[serde(flatten)]
+    foreign_members: serde_json::Map<String, serde_json::Value>,
+}
+
+impl<'de, T, P> Deserialize<'de> for Feature<T, P>
+where
+    T: GeoJsonGeometry<f64> + PointT + GeoJsonDeserialize,
+    P: Serialize + Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let members = FeatureMembers::<P>::deserialize(deserializer)?;
+        if members.geojson_type != "Feature" {
+            return Err(de::Error::custom(format_args!(
+                "unknown type `{}`, expected Feature",
+                members.geojson_type
+            )));
+        }
+        let options = collection_options(&members.foreign_members)?;
+        let geometry = members.geometry;
+        let geometry = with_geojson_options(options, || {
+            geometry
+                .filter(|geometry| !geometry.is_null())
+                .map(GeometryContainer::<T>::deserialize_geojson)
+                .transpose()
+        })
+        .map_err(de::Error::custom)?;
+        Ok(Feature {
+            id: members.id,
+            bbox: members.bbox,
+            geometry,
+            properties: members.properties,
+            foreign_members: members.foreign_members,
         })
     }
 }
 
-impl<'de, T: GeoJsonGeometry<f64> + PointT + Deserialize<'de>> Deserialize<'de>
-    for GeometryCollection<T>
+/// Members of a FeatureCollection, the foreign members are those left after the GeoJSON ones.
+#[derive(Deserialize)]
+struct FeatureCollectionMembers {
+    #[serde(rename = "type")]
+    geojson_type: String,
+    #[serde(default)]
+    bbox: Option<Vec<f64>>,
+    /// Kept as JSON until the `crs` foreign member is known.
+    features: Vec<serde_json::Value>,
+    #[serde(flatten)]
+    foreign_members: serde_json::Map<String, serde_json::Value>,
+}
+
+impl<'de, T, P> Deserialize<'de> for FeatureCollection<T, P>
+where
+    T: GeoJsonGeometry<f64> + PointT + GeoJsonDeserialize,
+    P: Serialize + Deserialize<'de>,
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_map(GeometryCollectionVisitor::<T>::new())
+        let members = FeatureCollectionMembers::deserialize(deserializer)?;
+        if members.geojson_type != "FeatureCollection" {
+            return Err(de::Error::custom(format_args!(
+                "unknown type `{}`, expected FeatureCollection",
+                members.geojson_type
+            )));
+        }
+        let options = collection_options(&members.foreign_members)?;
+        let features = members.features;
+        let features = with_geojson_options(options, || {
+            features
+                .into_iter()
+                .map(Feature::<T, P>::deserialize)
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(de::Error::custom)?;
+        Ok(FeatureCollection {
+            bbox: members.bbox,
+            features,
+            foreign_members: members.foreign_members,
+        })
     }
 }
 
-impl<T: GeoJsonGeometry<f64> + PointT + Serialize, P: Serialize> Serialize for Feature<T, P> {
+impl<T: GeoJsonGeometry<f64> + PointT + GeoJsonSerialize, P: Serialize> Serialize
+    for Feature<T, P>
+{
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("Feature", 2)?;
-        state.serialize_field("type", "Feature")?;
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", "Feature")?;
         if let Some(id) = &self.id {
-            state.serialize_field("id", &id)?;
+            map.serialize_entry("id", id)?;
         }
-        state.serialize_field("geometry", &self.geometry)?;
-        state.serialize_field("properties", &self.properties)?;
-        state.end()
+        let bbox = match &self.bbox {
+            None if options().compute_bbox => features_bbox(std::iter::once(self)),
+            bbox => bbox.clone(),
+        };
+        if let Some(bbox) = bbox {
+            map.serialize_entry("bbox", &bbox)?;
+        }
+        map.serialize_entry("geometry", &GeoJson(&self.geometry))?;
+        map.serialize_entry("properties", &self.properties)?;
+        serialize_foreign_members(
+            &mut map,
+            &self.foreign_members,
+            &["type", "id", "bbox", "geometry", "properties"],
+        )?;
+        map.end()
     }
 }
 
-impl<T: GeoJsonGeometry<f64> + PointT + Serialize, P: Serialize> Serialize
+impl<T: GeoJsonGeometry<f64> + PointT + GeoJsonSerialize, P: Serialize> Serialize
     for FeatureCollection<T, P>
 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let mut state = serializer.serialize_struct("FeatureCollection", 2)?;
-        state.serialize_field("type", "FeatureCollection")?;
-        state.serialize_field("features", &self.features)?;
-        state.end()
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", "FeatureCollection")?;
+        let bbox = match &self.bbox {
+            None if options().compute_bbox => features_bbox(self.features.iter()),
+            bbox => bbox.clone(),
+        };
+        if let Some(bbox) = bbox {
+            map.serialize_entry("bbox", &bbox)?;
+        }
+        map.serialize_entry("features", &self.features)?;
+        serialize_foreign_members(
+            &mut map,
+            &self.foreign_members,
+            &["type", "bbox", "features"],
+        )?;
+        map.end()
+    }
+}
+
+impl<T, P> GeoJsonSerialize for Feature<T, P>
+where
+    T: GeoJsonGeometry<f64> + PointT + GeoJsonSerialize,
+    P: Serialize,
+{
+    fn serialize_geojson<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.serialize(serializer)
+    }
+}
+
+impl<T, P> GeoJsonDeserialize for Feature<T, P>
+where
+    T: GeoJsonGeometry<f64> + PointT + GeoJsonDeserialize,
+    P: Serialize + DeserializeOwned,
+{
+    fn deserialize_geojson<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::deserialize(deserializer)
+    }
+}
+
+impl<T, P> GeoJsonSerialize for FeatureCollection<T, P>
+where
+    T: GeoJsonGeometry<f64> + PointT + GeoJsonSerialize,
+    P: Serialize,
+{
+    fn serialize_geojson<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.serialize(serializer)
+    }
+}
+
+impl<T, P> GeoJsonDeserialize for FeatureCollection<T, P>
+where
+    T: GeoJsonGeometry<f64> + PointT + GeoJsonDeserialize,
+    P: Serialize + DeserializeOwned,
+{
+    fn deserialize_geojson<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::deserialize(deserializer)
     }
 }
 
+/// Implements `Serialize` and `Deserialize` of the geometry types with GeoJSON for the
+/// `serde_geojson` feature.
+#[cfg(feature = "serde_geojson")]
+macro_rules! impl_serde_with_geojson {
+    ($($geometry:ident),*; $($generic_geometry:ident),*) => {
+        $(
+            impl Serialize for $geometry {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    self.serialize_geojson(serializer)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $geometry {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    Self::deserialize_geojson(deserializer)
+                }
+            }
+        )*
+        $(
+            impl<T> Serialize for $generic_geometry<T>
+            where
+                Self: GeoJsonSerialize,
+            {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    self.serialize_geojson(serializer)
+                }
+            }
+
+            impl<'de, T> Deserialize<'de> for $generic_geometry<T>
+            where
+                Self: GeoJsonDeserialize,
+            {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    Self::deserialize_geojson(deserializer)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "serde_geojson")]
+impl_serde_with_geojson!(
+    Point,
+    PointZ,
+    PointM,
+    PointZM;
+    MultiPoint,
+    LineString,
+    MultiLineString,
+    Polygon,
+    MultiPolygon,
+    GeometryContainer,
+    GeometryCollection
+);
+
 #[cfg(test)]
+#[cfg(feature = "serde_geojson")]
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
@@ -728,21 +1683,27 @@ mod tests {
 
         let feature1 = Feature::<Point, TestFeatureProperties> {
             id: None,
+            bbox: None,
             geometry: Some(GeometryContainer::Point(point)),
             properties: Some(TestFeatureProperties {
                 name: "Test".to_string(),
                 size: 123,
             }),
+            foreign_members: Default::default(),
         };
 
         let feature2 = Feature::<Point, _> {
-            id: Some("Test".to_string()),
+            id: Some("Test".into()),
+            bbox: None,
             geometry: None,
             properties: None,
+            foreign_members: Default::default(),
         };
 
         let feature_collection = FeatureCollection::<Point, _> {
+            bbox: None,
             features: vec![feature1, feature2],
+            foreign_members: Default::default(),
         };
 
         let expected_json =
@@ -757,13 +1718,345 @@ mod tests {
     fn test_non_wgs84_serde() {
         let point = Point::new(72.0, 64.0, Some(4324));
         let point_json = serde_json::to_string(&point);
-        assert_eq!(true, point_json.is_err());
+        assert!(point_json.is_err());
     }
 
     #[test]
     fn test_no_srid_serde() {
         let point = Point::new(72.0, 64.0, None);
         let point_json = serde_json::to_string(&point);
-        assert_eq!(true, point_json.is_ok());
+        assert!(point_json.is_ok());
+    }
+
+    #[test]
+    fn test_pointm_serde() {
+        let point = PointM::new(72.0, 64.0, 7.0, WGS84_SRID);
+        // without Z before it, M is in the properties with the default `MeasurePolicy::Ordinate`
+        let expected_json =
+            "{\"type\":\"Point\",\"coordinates\":[72.0,64.0],\"properties\":{\"m\":[7.0]}}";
+        let point_from_json = serde_json::from_str(expected_json).unwrap();
+        assert_eq!(point, point_from_json);
+        let point_json = serde_json::to_string(&point).unwrap();
+        assert_eq!(expected_json, point_json);
+        assert!(serde_json::from_str::<PointM>(
+            "{\"type\":\"Point\",\"coordinates\":[72.0,64.0,0.0,7.0]}"
+        )
+        .is_err());
+        assert!(serde_json::from_str::<PointM>(
+            "{\"type\":\"Point\",\"coordinates\":[72.0,64.0,7.0]}"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_pointzm_serde() {
+        let point = PointZM::new(72.0, 64.0, 52.0, 7.0, WGS84_SRID);
+        let expected_json = "{\"type\":\"Point\",\"coordinates\":[72.0,64.0,52.0,7.0]}";
+        let point_from_json = serde_json::from_str(expected_json).unwrap();
+        assert_eq!(point, point_from_json);
+        let point_json = serde_json::to_string(&point).unwrap();
+        assert_eq!(expected_json, point_json);
+    }
+
+    #[test]
+    fn test_measure_policy_properties_serde() {
+        let mut line_string = LineString::<PointZM>::new(WGS84_SRID);
+        line_string
+            .add_point(PointZM::new(1.0, 2.0, 3.0, 10.0, WGS84_SRID))
+            .unwrap();
+        line_string
+            .add_point(PointZM::new(4.0, 5.0, 6.0, 20.0, WGS84_SRID))
+            .unwrap();
+
+        let expected_json = "{\"type\":\"LineString\",\"coordinates\":[[1.0,2.0,3.0],[4.0,5.0,6.0]],\"properties\":{\"m\":[10.0,20.0]}}";
+        with_measure_policy(MeasurePolicy::Properties, || {
+            let line_string_json = serde_json::to_string(&line_string).unwrap();
+            assert_eq!(expected_json, line_string_json);
+            let line_string_from_json = serde_json::from_str(expected_json).unwrap();
+            assert_eq!(line_string, line_string_from_json);
+
+            let fewer = "{\"type\":\"LineString\",\"coordinates\":[[1.0,2.0,3.0],[4.0,5.0,6.0]],\"properties\":{\"m\":[10.0]}}";
+            assert!(serde_json::from_str::<LineString<PointZM>>(fewer).is_err());
+            let missing = "{\"type\":\"LineString\",\"coordinates\":[[1.0,2.0,3.0],[4.0,5.0,6.0]]}";
+            assert!(serde_json::from_str::<LineString<PointZM>>(missing).is_err());
+        });
+        // the policy is restored after the call
+        assert_eq!(MeasurePolicy::Ordinate, measure_policy());
+    }
+
+    #[test]
+    fn test_measure_policy_properties_collection_serde() {
+        let mut polygon = Polygon::<PointM>::new(WGS84_SRID);
+        polygon
+            .add_points([
+                PointM::new(0.0, 0.0, 1.0, WGS84_SRID),
+                PointM::new(1.0, 0.0, 2.0, WGS84_SRID),
+                PointM::new(0.0, 1.0, 3.0, WGS84_SRID),
+                PointM::new(0.0, 0.0, 1.0, WGS84_SRID),
+            ])
+            .unwrap();
+        let collection = GeometryCollection::<PointM> {
+            geometries: vec![
+                GeometryContainer::Point(PointM::new(5.0, 5.0, 4.0, WGS84_SRID)),
+                GeometryContainer::Polygon(polygon),
+            ],
+            srid: WGS84_SRID,
+        };
+
+        let expected_json = "{\"type\":\"GeometryCollection\",\"geometries\":[{\"type\":\"Point\",\"coordinates\":[5.0,5.0],\"properties\":{\"m\":[4.0]}},{\"type\":\"Polygon\",\"coordinates\":[[[0.0,0.0],[1.0,0.0],[0.0,1.0],[0.0,0.0]]],\"properties\":{\"m\":[1.0,2.0,3.0,1.0]}}]}";
+        with_measure_policy(MeasurePolicy::Properties, || {
+            let collection_json = serde_json::to_string(&collection).unwrap();
+            assert_eq!(expected_json, collection_json);
+            let collection_from_json = serde_json::from_str(expected_json).unwrap();
+            assert_eq!(collection, collection_from_json);
+        });
+    }
+
+    #[test]
+    fn test_measure_policy_drop_serde() {
+        let mut multi_point = MultiPoint::<PointM>::new(WGS84_SRID);
+        multi_point.add_point(PointM::new(1.0, 2.0, 3.0, WGS84_SRID));
+
+        let expected_json = "{\"type\":\"MultiPoint\",\"coordinates\":[[1.0,2.0]]}";
+        with_measure_policy(MeasurePolicy::Drop, || {
+            let multi_point_json = serde_json::to_string(&multi_point).unwrap();
+            assert_eq!(expected_json, multi_point_json);
+            let multi_point_from_json =
+                serde_json::from_str::<MultiPoint<PointM>>(expected_json).unwrap();
+            assert_eq!(
+                (1.0, 2.0),
+                (
+                    multi_point_from_json.points[0].x,
+                    multi_point_from_json.points[0].y
+                )
+            );
+            assert!(multi_point_from_json.points[0].m.is_nan());
+        });
+    }
+
+    #[test]
+    fn test_feature_foreign_members_serde() {
+        let json = "{\"type\":\"FeatureCollection\",\"bbox\":[1.0,2.0,3.0,4.0],\"features\":[{\"type\":\"Feature\",\"id\":42,\"bbox\":[1.0,2.0,3.0,4.0],\"geometry\":{\"type\":\"LineString\",\"coordinates\":[[1.0,2.0],[3.0,4.0]]},\"properties\":null,\"title\":\"Road\",\"tags\":[\"a\",\"b\"]}],\"generator\":\"test\"}";
+        let feature_collection: FeatureCollection<Point, serde_json::Value> =
+            serde_json::from_str(json).unwrap();
+        assert_eq!(Some(vec![1.0, 2.0, 3.0, 4.0]), feature_collection.bbox);
+        assert_eq!(
+            Some(&serde_json::json!("test")),
+            feature_collection.foreign_members.get("generator")
+        );
+        assert!(!feature_collection.foreign_members.contains_key("type"));
+        let feature = &feature_collection.features[0];
+        assert_eq!(Some(FeatureId::from(42)), feature.id);
+        assert_eq!(
+            vec!["tags", "title"],
+            feature.foreign_members.keys().collect::<Vec<_>>()
+        );
+        // foreign members are written after the GeoJSON members, in the order of the map
+        let expected_json = "{\"type\":\"FeatureCollection\",\"bbox\":[1.0,2.0,3.0,4.0],\"features\":[{\"type\":\"Feature\",\"id\":42,\"bbox\":[1.0,2.0,3.0,4.0],\"geometry\":{\"type\":\"LineString\",\"coordinates\":[[1.0,2.0],[3.0,4.0]]},\"properties\":null,\"tags\":[\"a\",\"b\"],\"title\":\"Road\"}],\"generator\":\"test\"}";
+        assert_eq!(
+            expected_json,
+            serde_json::to_string(&feature_collection).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_geometry_bbox_dropped_serde() {
+        let json = "{\"type\":\"LineString\",\"bbox\":[1.0,2.0,3.0,4.0],\"coordinates\":[[1.0,2.0],[3.0,4.0]]}";
+        let line_string: LineString<Point> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            "{\"type\":\"LineString\",\"coordinates\":[[1.0,2.0],[3.0,4.0]]}",
+            serde_json::to_string(&line_string).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_feature_id_serde() {
+        for (json, id) in [
+            ("\"road-1\"", FeatureId::from("road-1")),
+            ("7", FeatureId::from(7)),
+            ("-7", FeatureId::from(-7)),
+            (
+                "1.5",
+                FeatureId::Number(serde_json::Number::from_f64(1.5).unwrap()),
+            ),
+        ] {
+            assert_eq!(id, serde_json::from_str::<FeatureId>(json).unwrap());
+            assert_eq!(json, serde_json::to_string(&id).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_compute_bbox_serde() {
+        let mut line_string = LineString::<PointZ>::new(WGS84_SRID);
+        line_string
+            .add_points([
+                PointZ::new(3.0, -1.0, 10.0, WGS84_SRID),
+                PointZ::new(-2.0, 4.0, 20.0, WGS84_SRID),
+            ])
+            .unwrap();
+        let feature = Feature::<PointZ, ()> {
+            id: None,
+            bbox: None,
+            geometry: Some(GeometryContainer::LineString(line_string)),
+            properties: None,
+            foreign_members: Default::default(),
+        };
+        let empty = Feature::<PointZ, ()> {
+            id: None,
+            bbox: None,
+            geometry: None,
+            properties: None,
+            foreign_members: Default::default(),
+        };
+        let feature_collection = FeatureCollection {
+            bbox: None,
+            features: vec![feature, empty],
+            foreign_members: Default::default(),
+        };
+        assert!(!serde_json::to_string(&feature_collection)
+            .unwrap()
+            .contains("bbox"));
+
+        let options = GeoJsonOptions {
+            compute_bbox: true,
+            ..Default::default()
+        };
+        let json =
+            with_geojson_options(options, || serde_json::to_string(&feature_collection)).unwrap();
+        let expected_json = "{\"type\":\"FeatureCollection\",\"bbox\":[-2.0,-1.0,10.0,3.0,4.0,20.0],\"features\":[{\"type\":\"Feature\",\"bbox\":[-2.0,-1.0,10.0,3.0,4.0,20.0],\"geometry\":{\"type\":\"LineString\",\"coordinates\":[[3.0,-1.0,10.0],[-2.0,4.0,20.0]]},\"properties\":null},{\"type\":\"Feature\",\"geometry\":null,\"properties\":null}]}";
+        assert_eq!(expected_json, json);
+    }
+
+    #[test]
+    fn test_lenient_serde() {
+        let json = "{\"type\":\"GeometryCollection\",\"bbox\":[1.0,2.0,1.0,2.0],\"geometries\":[{\"type\":\"Point\",\"coordinates\":[1.0,2.0],\"bbox\":[1.0,2.0,1.0,2.0],\"name\":\"a\"}],\"name\":\"b\"}";
+        assert!(serde_json::from_str::<GeometryCollection<Point>>(json).is_err());
+        let options = GeoJsonOptions {
+            lenient: true,
+            ..Default::default()
+        };
+        let geometry_collection: GeometryCollection<Point> =
+            with_geojson_options(options, || serde_json::from_str(json)).unwrap();
+        assert_eq!(
+            vec![GeometryContainer::Point(Point::new(1.0, 2.0, WGS84_SRID))],
+            geometry_collection.geometries
+        );
+        // bbox is a GeoJSON member, accepted without the lenient mode
+        let point: Point = serde_json::from_str(
+            "{\"type\":\"Point\",\"bbox\":[1.0,2.0,1.0,2.0],\"coordinates\":[1.0,2.0]}",
+        )
+        .unwrap();
+        assert_eq!(Point::new(1.0, 2.0, WGS84_SRID), point);
+    }
+
+    const CH1903_SRID: Option<u32> = Some(2056);
+
+    fn with_srid_policy<R>(srid: SridPolicy, f: impl FnOnce() -> R) -> R {
+        let options = GeoJsonOptions {
+            srid,
+            ..Default::default()
+        };
+        with_geojson_options(options, f)
+    }
+
+    #[test]
+    fn test_strict_srid_serde() {
+        let json = "{\"type\":\"Point\",\"coordinates\":[2600000.0,1200000.0],\"crs\":{\"type\":\"name\",\"properties\":{\"name\":\"urn:ogc:def:crs:EPSG::2056\"}}}";
+        assert!(serde_json::from_str::<Point>(json).is_err());
+        let json = "{\"type\":\"Point\",\"coordinates\":[7.4,46.9],\"crs\":{\"type\":\"name\",\"properties\":{\"name\":\"urn:ogc:def:crs:OGC:1.3:CRS84\"}}}";
+        assert_eq!(
+            Point::new(7.4, 46.9, WGS84_SRID),
+            serde_json::from_str(json).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_allow_any_srid_serde() {
+        let point = Point::new(2600000.0, 1200000.0, CH1903_SRID);
+        let json = "{\"type\":\"Point\",\"coordinates\":[2600000.0,1200000.0],\"crs\":{\"type\":\"name\",\"properties\":{\"name\":\"urn:ogc:def:crs:EPSG::2056\"}}}";
+        with_srid_policy(SridPolicy::AllowAny { crs: true }, || {
+            assert_eq!(json, serde_json::to_string(&point).unwrap());
+            assert_eq!(point, serde_json::from_str(json).unwrap());
+            // GeoJSON without crs is WGS 84
+            let point: Point =
+                serde_json::from_str("{\"type\":\"Point\",\"coordinates\":[7.4,46.9]}").unwrap();
+            assert_eq!(WGS84_SRID, point.srid);
+        });
+        with_srid_policy(SridPolicy::AllowAny { crs: false }, || {
+            assert_eq!(
+                "{\"type\":\"Point\",\"coordinates\":[2600000.0,1200000.0]}",
+                serde_json::to_string(&point).unwrap()
+            );
+            let point: Point = serde_json::from_str(
+                "{\"type\":\"Point\",\"coordinates\":[1.0,2.0],\"crs\":{\"type\":\"name\",\"properties\":{\"name\":\"EPSG:2056\"}}}",
+            )
+            .unwrap();
+            assert_eq!(CH1903_SRID, point.srid);
+            let link = "{\"type\":\"Point\",\"coordinates\":[1.0,2.0],\"crs\":{\"type\":\"link\",\"properties\":{\"name\":\"EPSG:2056\"}}}";
+            assert!(serde_json::from_str::<Point>(link).is_err());
+        });
+    }
+
+    #[test]
+    fn test_default_srid_serde() {
+        let mut line_string = LineString::<Point>::new(CH1903_SRID);
+        line_string
+            .add_points([
+                Point::new(2600000.0, 1200000.0, CH1903_SRID),
+                Point::new(2600100.0, 1200100.0, CH1903_SRID),
+            ])
+            .unwrap();
+        let json = "{\"type\":\"LineString\",\"coordinates\":[[2600000.0,1200000.0],[2600100.0,1200100.0]]}";
+        assert!(serde_json::to_string(&line_string).is_err());
+        with_srid_policy(SridPolicy::DefaultSrid(CH1903_SRID), || {
+            assert_eq!(json, serde_json::to_string(&line_string).unwrap());
+            assert_eq!(line_string, serde_json::from_str(json).unwrap());
+        });
+    }
+
+    #[test]
+    fn test_geometry_collection_crs_serde() {
+        let collection = GeometryCollection::<Point> {
+            geometries: vec![
+                GeometryContainer::Point(Point::new(2600000.0, 1200000.0, CH1903_SRID)),
+                GeometryContainer::Point(Point::new(2600100.0, 1200100.0, CH1903_SRID)),
+            ],
+            srid: CH1903_SRID,
+        };
+        // the crs is written once, on the collection, and comes after the members
+        let json = "{\"type\":\"GeometryCollection\",\"geometries\":[{\"type\":\"Point\",\"coordinates\":[2600000.0,1200000.0]},{\"type\":\"Point\",\"coordinates\":[2600100.0,1200100.0]}],\"crs\":{\"type\":\"name\",\"properties\":{\"name\":\"urn:ogc:def:crs:EPSG::2056\"}}}";
+        with_srid_policy(SridPolicy::AllowAny { crs: true }, || {
+            assert_eq!(json, serde_json::to_string(&collection).unwrap());
+            assert_eq!(collection, serde_json::from_str(json).unwrap());
+            // the crs of a member takes precedence
+            let json = "{\"type\":\"GeometryCollection\",\"crs\":{\"type\":\"name\",\"properties\":{\"name\":\"EPSG:2056\"}},\"geometries\":[{\"type\":\"Point\",\"coordinates\":[7.4,46.9],\"crs\":{\"type\":\"name\",\"properties\":{\"name\":\"EPSG:4326\"}}}]}";
+            let collection: GeometryCollection<Point> = serde_json::from_str(json).unwrap();
+            assert_eq!(CH1903_SRID, collection.srid);
+            assert_eq!(
+                GeometryContainer::Point(Point::new(7.4, 46.9, WGS84_SRID)),
+                collection.geometries[0]
+            );
+        });
+    }
+
+    #[test]
+    fn test_feature_collection_crs_serde() {
+        // as written by ogr2ogr for a projected layer
+        let json = "{\"type\":\"FeatureCollection\",\"crs\":{\"type\":\"name\",\"properties\":{\"name\":\"urn:ogc:def:crs:EPSG::2056\"}},\"features\":[{\"type\":\"Feature\",\"geometry\":{\"type\":\"Point\",\"coordinates\":[2600000.0,1200000.0]},\"properties\":null}]}";
+        assert!(serde_json::from_str::<FeatureCollection<Point, ()>>(json).is_err());
+        let feature_collection: FeatureCollection<Point, ()> =
+            with_srid_policy(SridPolicy::AllowAny { crs: false }, || {
+                serde_json::from_str(json)
+            })
+            .unwrap();
+        assert_eq!(
+            Some(GeometryContainer::Point(Point::new(
+                2600000.0,
+                1200000.0,
+                CH1903_SRID
+            ))),
+            feature_collection.features[0].geometry
+        );
+        assert!(feature_collection.foreign_members.contains_key("crs"));
     }
 }
@@ -0,0 +1,231 @@
+use serde::de::Error as _;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::types::*;
+
+/// Properties of the features built by [`GeoJsonFeature`], one member per field.
+pub type FeatureProperties = Map<String, Value>;
+
+/// Geometry field of a struct deriving [`GeoJsonFeature`].
+pub trait FeatureGeometry: Sized {
+    /// Point type of the geometry.
+    type Point;
+
+    fn into_geometry(self) -> Option<GeometryContainer<Self::Point>>;
+
+    /// Returns `None` if the geometry is missing or of another type.
+    fn from_geometry(geometry: Option<GeometryContainer<Self::Point>>) -> Option<Self>;
+}
+
+macro_rules! impl_feature_geometry_for_point {
+    ($($point:ident),*) => {
+        $(
+            impl FeatureGeometry for $point {
+                type Point = $point;
+
+                fn into_geometry(self) -> Option<GeometryContainer<Self::Point>> {
+                    Some(GeometryContainer::Point(self))
+                }
+
+                fn from_geometry(geometry: Option<GeometryContainer<Self::Point>>) -> Option<Self> {
+                    match geometry {
+                        Some(GeometryContainer::Point(point)) => Some(point),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_feature_geometry_for_point!(Point, PointZ, PointM, PointZM);
+
+macro_rules! impl_feature_geometry {
+    ($($geometry:ident),*) => {
+        $(
+            impl<T> FeatureGeometry for $geometry<T> {
+                type Point = T;
+
+                fn into_geometry(self) -> Option<GeometryContainer<Self::Point>> {
+                    Some(GeometryContainer::$geometry(self))
+                }
+
+                fn from_geometry(geometry: Option<GeometryContainer<Self::Point>>) -> Option<Self> {
+                    match geometry {
+                        Some(GeometryContainer::$geometry(geometry)) => Some(geometry),
+                        _ => None,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_feature_geometry!(
+    LineString,
+    Polygon,
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+    GeometryCollection
+);
+
+impl<T> FeatureGeometry for GeometryContainer<T> {
+    type Point = T;
+
+    fn into_geometry(self) -> Option<GeometryContainer<T>> {
+        Some(self)
+    }
+
+    fn from_geometry(geometry: Option<GeometryContainer<T>>) -> Option<Self> {
+        geometry
+    }
+}
+
+impl<G: FeatureGeometry> FeatureGeometry for Option<G> {
+    type Point = G::Point;
+
+    fn into_geometry(self) -> Option<GeometryContainer<Self::Point>> {
+        self.and_then(G::into_geometry)
+    }
+
+    fn from_geometry(geometry: Option<GeometryContainer<Self::Point>>) -> Option<Self> {
+        match geometry {
+            None => Some(None),
+            geometry => G::from_geometry(geometry).map(Some),
+        }
+    }
+}
+
+/// Struct converted to and from a GeoJSON [`Feature`], usually with `#[derive(GeoJsonFeature)]`
+/// and the `derive` feature.
+///
+/// The field marked with `#[geojson(geometry)]` is the geometry of the feature, the one marked
+/// with `#[geojson(id)]` its id and the other fields are its properties, except those marked
+/// with `#[geojson(skip)]`, which are set to their default value when the struct is built from a
+/// feature. `#[geojson(rename = "name")]` gives another name to the property of a field.
+/// ```
+/// # #[cfg(feature = "derive")]
+/// # fn main() {
+/// use postgis_diesel::types::{GeoJsonFeature, Point};
+/// #[derive(Debug, PartialEq, GeoJsonFeature)]
+/// struct Station {
+///     #[geojson(id)]
+///     id: i32,
+///     name: String,
+///     #[geojson(geometry)]
+///     location: Point,
+/// }
+/// let station = Station {
+///     id: 1,
+///     name: "Bern".to_string(),
+///     location: Point::new(7.44, 46.95, Some(4326)),
+/// };
+/// let feature = station.into_feature().unwrap();
+/// assert_eq!(
+///     r#"{"type":"Feature","id":1,"geometry":{"type":"Point","coordinates":[7.44,46.95]},"properties":{"name":"Bern"}}"#,
+///     serde_json::to_string(&feature).unwrap()
+/// );
+/// let station = Station::from_feature(feature).unwrap();
+/// assert_eq!("Bern", station.name);
+/// # }
+/// # #[cfg(not(feature = "derive"))]
+/// # fn main() {}
+/// ```
+pub trait GeoJsonFeature: Sized {
+    /// Point type of the geometry.
+    type Point;
+
+    fn into_feature(self) -> Result<Feature<Self::Point, FeatureProperties>, serde_json::Error>;
+
+    fn from_feature(
+        feature: Feature<Self::Point, FeatureProperties>,
+    ) -> Result<Self, serde_json::Error>;
+
+    fn into_feature_collection<I>(
+        values: I,
+    ) -> Result<FeatureCollection<Self::Point, FeatureProperties>, serde_json::Error>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        Ok(FeatureCollection {
+            bbox: None,
+            features: values
+                .into_iter()
+                .map(Self::into_feature)
+                .collect::<Result<_, _>>()?,
+            foreign_members: Map::new(),
+        })
+    }
+
+    fn from_feature_collection(
+        collection: FeatureCollection<Self::Point, FeatureProperties>,
+    ) -> Result<Vec<Self>, serde_json::Error> {
+        collection
+            .features
+            .into_iter()
+            .map(Self::from_feature)
+            .collect()
+    }
+}
+
+/// Functions called by `#[derive(GeoJsonFeature)]`.
+#[doc(hidden)]
+pub mod __private {
+    use super::*;
+
+    pub use serde_json;
+
+    pub fn feature_id<I: Serialize>(id: &I) -> Result<Option<FeatureId>, serde_json::Error> {
+        match serde_json::to_value(id)? {
+            Value::Null => Ok(None),
+            id => FeatureId::deserialize(id).map(Some),
+        }
+    }
+
+    pub fn from_feature_id<I>(id: Option<FeatureId>) -> Result<I, serde_json::Error>
+    where
+        I: for<'de> Deserialize<'de>,
+    {
+        let id = match id {
+            Some(id) => serde_json::to_value(id)?,
+            None => Value::Null,
+        };
+        I::deserialize(id).map_err(|e| serde_json::Error::custom(format_args!("id: {}", e)))
+    }
+
+    pub fn geometry<G: FeatureGeometry>(geometry: G) -> Option<GeometryContainer<G::Point>> {
+        geometry.into_geometry()
+    }
+
+    pub fn from_geometry<G: FeatureGeometry>(
+        geometry: Option<GeometryContainer<G::Point>>,
+    ) -> Result<G, serde_json::Error> {
+        G::from_geometry(geometry).ok_or_else(|| {
+            serde_json::Error::custom("missing geometry or geometry of another type")
+        })
+    }
+
+    pub fn insert_property<V: Serialize>(
+        properties: &mut FeatureProperties,
+        name: &str,
+        value: &V,
+    ) -> Result<(), serde_json::Error> {
+        properties.insert(name.to_string(), serde_json::to_value(value)?);
+        Ok(())
+    }
+
+    /// A missing property is read as `null`, for `Option` fields.
+    pub fn remove_property<V>(
+        properties: &mut FeatureProperties,
+        name: &str,
+    ) -> Result<V, serde_json::Error>
+    where
+        V: for<'de> Deserialize<'de>,
+    {
+        let value = properties.remove(name).unwrap_or(Value::Null);
+        V::deserialize(value)
+            .map_err(|e| serde_json::Error::custom(format_args!("property `{}`: {}", name, e)))
+    }
+}
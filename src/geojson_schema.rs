@@ -0,0 +1,739 @@
+//! Schemas of the GeoJSON representation (RFC 7946) of the geometry types, used instead of the
+//! schemas derived from the structs when the `serde_geojson` feature is enabled: JSON Schemas with
+//! `schemars` and OpenAPI schemas with `utoipa`.
+//!
+//! The schemas follow the [`GeoJsonOptions`] of the current thread when they are generated, like
+//! the serialization: the size of the positions of `PointM` and `PointZM` depends on the
+//! [`MeasurePolicy`], and geometries may have other members only with `lenient`.
+use serde_json::{json, Value};
+
+use crate::geojson::options;
+use crate::types::*;
+
+/// Point type written as a GeoJSON position.
+pub trait PositionSchema {
+    /// Number of numbers of the position.
+    fn dimensions() -> usize;
+
+    /// Whether the M values are written in a `properties` member.
+    fn has_measure_properties() -> bool {
+        false
+    }
+}
+
+impl PositionSchema for Point {
+    fn dimensions() -> usize {
+        2
+    }
+}
+
+impl PositionSchema for PointZ {
+    fn dimensions() -> usize {
+        3
+    }
+}
+
+/// The positions never have M, which `MeasurePolicy::Ordinate` writes in the properties too.
+impl PositionSchema for PointM {
+    fn dimensions() -> usize {
+        2
+    }
+
+    fn has_measure_properties() -> bool {
+        options().measures != MeasurePolicy::Drop
+    }
+}
+
+impl PositionSchema for PointZM {
+    fn dimensions() -> usize {
+        match options().measures {
+            MeasurePolicy::Ordinate => 4,
+            MeasurePolicy::Properties | MeasurePolicy::Drop => 3,
+        }
+    }
+
+    fn has_measure_properties() -> bool {
+        options().measures == MeasurePolicy::Properties
+    }
+}
+
+fn position<T: PositionSchema>() -> Value {
+    json!({
+        "type": "array",
+        "items": {"type": "number"},
+        "minItems": T::dimensions(),
+        "maxItems": T::dimensions(),
+    })
+}
+
+fn array_of(items: Value, min_items: usize) -> Value {
+    if min_items == 0 {
+        json!({"type": "array", "items": items})
+    } else {
+        json!({"type": "array", "items": items, "minItems": min_items})
+    }
+}
+
+fn bbox() -> Value {
+    json!({"type": "array", "items": {"type": "number"}, "minItems": 4})
+}
+
+fn crs() -> Value {
+    json!({
+        "type": "object",
+        "required": ["type", "properties"],
+        "properties": {
+            "type": {"type": "string", "enum": ["name"]},
+            "properties": {
+                "type": "object",
+                "required": ["name"],
+                "properties": {"name": {"type": "string"}},
+            },
+        },
+    })
+}
+
+/// Schema of a GeoJSON geometry object with the members `type` and `member`.
+fn object(geojson_type: &str, member: &str, value: Value, measures: bool) -> Value {
+    let mut properties = serde_json::Map::new();
+    properties.insert(
+        "type".to_string(),
+        json!({"type": "string", "enum": [geojson_type]}),
+    );
+    properties.insert(member.to_string(), value);
+    properties.insert("bbox".to_string(), bbox());
+    properties.insert("crs".to_string(), crs());
+    let mut required = vec!["type", member];
+    if measures {
+        properties.insert(
+            "properties".to_string(),
+            json!({
+                "type": "object",
+                "required": ["m"],
+                "properties": {"m": {"type": "array", "items": {"type": "number"}}},
+            }),
+        );
+        required.push("properties");
+    }
+    let mut schema = json!({
+        "type": "object",
+        "required": required,
+        "properties": properties,
+    });
+    if !options().lenient {
+        schema["additionalProperties"] = json!(false);
+    }
+    schema
+}
+
+/// Schema of a geometry of type `geojson_type` other than a GeometryCollection.
+fn geometry<T: PositionSchema>(geojson_type: &str) -> Value {
+    let position = position::<T>();
+    let coordinates = match geojson_type {
+        "Point" => position,
+        "MultiPoint" => array_of(position, 0),
+        "LineString" => array_of(position, 2),
+        "MultiLineString" => array_of(array_of(position, 2), 0),
+        "Polygon" => array_of(array_of(position, 4), 0),
+        "MultiPolygon" => array_of(array_of(array_of(position, 4), 0), 0),
+        _ => unreachable!("not a geometry with coordinates: {}", geojson_type),
+    };
+    object(
+        geojson_type,
+        "coordinates",
+        coordinates,
+        T::has_measure_properties(),
+    )
+}
+
+fn geometry_collection(geometry: Value) -> Value {
+    object(
+        "GeometryCollection",
+        "geometries",
+        array_of(geometry, 0),
+        false,
+    )
+}
+
+fn feature_id() -> Value {
+    json!({"type": ["string", "number"]})
+}
+
+fn feature(id: Value, geometry: Value, properties: Value) -> Value {
+    json!({
+        "type": "object",
+        "required": ["type", "geometry", "properties"],
+        "properties": {
+            "type": {"type": "string", "enum": ["Feature"]},
+            "id": id,
+            "bbox": bbox(),
+            "geometry": {"anyOf": [geometry, {"type": "null"}]},
+            "properties": {"anyOf": [properties, {"type": "null"}]},
+        },
+    })
+}
+
+fn feature_collection(feature: Value) -> Value {
+    json!({
+        "type": "object",
+        "required": ["type", "features"],
+        "properties": {
+            "type": {"type": "string", "enum": ["FeatureCollection"]},
+            "bbox": bbox(),
+            "features": array_of(feature, 0),
+        },
+    })
+}
+
+#[cfg(feature = "schemars")]
+mod json_schema {
+    use schemars::gen::SchemaGenerator;
+    use schemars::schema::Schema;
+    use schemars::JsonSchema;
+
+    use super::*;
+
+    fn to_schema(value: Value) -> Schema {
+        serde_json::from_value(value).expect("valid JSON Schema")
+    }
+
+    fn to_value(schema: Schema) -> Value {
+        serde_json::to_value(schema).expect("JSON Schema serializable")
+    }
+
+    macro_rules! impl_point_schema {
+        ($($point:ident),*) => {
+            $(
+                impl JsonSchema for $point {
+                    fn schema_name() -> String {
+                        stringify!($point).to_string()
+                    }
+
+                    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+                        to_schema(geometry::<Self>("Point"))
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_point_schema!(Point, PointZ, PointM, PointZM);
+
+    macro_rules! impl_geometry_schema {
+        ($($geometry:ident),*) => {
+            $(
+                impl<T: PositionSchema + JsonSchema> JsonSchema for $geometry<T> {
+                    fn schema_name() -> String {
+                        format!(concat!(stringify!($geometry), "_for_{}"), T::schema_name())
+                    }
+
+                    fn json_schema(_: &mut SchemaGenerator) -> Schema {
+                        to_schema(geometry::<T>(stringify!($geometry)))
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_geometry_schema!(
+        MultiPoint,
+        LineString,
+        MultiLineString,
+        Polygon,
+        MultiPolygon
+    );
+
+    impl<T: PositionSchema + JsonSchema> JsonSchema for GeometryContainer<T> {
+        fn schema_name() -> String {
+            format!("GeometryContainer_for_{}", T::schema_name())
+        }
+
+        fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+            to_schema(json!({
+                "oneOf": [
+                    to_value(generator.subschema_for::<T>()),
+                    to_value(generator.subschema_for::<LineString<T>>()),
+                    to_value(generator.subschema_for::<Polygon<T>>()),
+                    to_value(generator.subschema_for::<MultiPoint<T>>()),
+                    to_value(generator.subschema_for::<MultiLineString<T>>()),
+                    to_value(generator.subschema_for::<MultiPolygon<T>>()),
+                    to_value(generator.subschema_for::<GeometryCollection<T>>()),
+                ],
+            }))
+        }
+    }
+
+    impl<T: PositionSchema + JsonSchema> JsonSchema for GeometryCollection<T> {
+        fn schema_name() -> String {
+            format!("GeometryCollection_for_{}", T::schema_name())
+        }
+
+        fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+            let geometry = to_value(generator.subschema_for::<GeometryContainer<T>>());
+            to_schema(geometry_collection(geometry))
+        }
+    }
+
+    impl JsonSchema for FeatureId {
+        fn schema_name() -> String {
+            "FeatureId".to_string()
+        }
+
+        fn json_schema(_: &mut SchemaGenerator) -> Schema {
+            to_schema(feature_id())
+        }
+    }
+
+    impl<T, P> JsonSchema for Feature<T, P>
+    where
+        T: PositionSchema + JsonSchema,
+        P: JsonSchema + serde::Serialize,
+    {
+        fn schema_name() -> String {
+            format!("Feature_for_{}_and_{}", T::schema_name(), P::schema_name())
+        }
+
+        fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+            to_schema(feature(
+                to_value(generator.subschema_for::<FeatureId>()),
+                to_value(generator.subschema_for::<GeometryContainer<T>>()),
+                to_value(generator.subschema_for::<P>()),
+            ))
+        }
+    }
+
+    impl<T, P> JsonSchema for FeatureCollection<T, P>
+    where
+        T: PositionSchema + JsonSchema,
+        P: JsonSchema + serde::Serialize,
+    {
+        fn schema_name() -> String {
+            format!(
+                "FeatureCollection_for_{}_and_{}",
+                T::schema_name(),
+                P::schema_name()
+            )
+        }
+
+        fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+            let feature = to_value(generator.subschema_for::<Feature<T, P>>());
+            to_schema(feature_collection(feature))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use schemars::schema_for;
+
+        use super::*;
+
+        fn schema_of<T: JsonSchema>() -> Value {
+            serde_json::to_value(schema_for!(T)).unwrap()
+        }
+
+        #[test]
+        fn test_point_schema() {
+            let schema = schema_of::<Point>();
+            assert_eq!("Point", schema["title"]);
+            assert_eq!(json!(["coordinates", "type"]), schema["required"]);
+            assert_eq!(json!(false), schema["additionalProperties"]);
+            assert_eq!(json!(["Point"]), schema["properties"]["type"]["enum"]);
+            assert_eq!(
+                json!({"type": "array", "items": {"type": "number"}, "minItems": 2, "maxItems": 2}),
+                schema["properties"]["coordinates"]
+            );
+            assert_eq!(
+                json!(3),
+                schema_of::<PointZ>()["properties"]["coordinates"]["maxItems"]
+            );
+        }
+
+        #[test]
+        fn test_measure_policy_schema() {
+            let schema = schema_of::<LineString<PointZM>>();
+            assert_eq!("LineString_for_PointZM", schema["title"]);
+            assert_eq!(json!(2), schema["properties"]["coordinates"]["minItems"]);
+            assert_eq!(
+                json!(4),
+                schema["properties"]["coordinates"]["items"]["maxItems"]
+            );
+            assert_eq!(Value::Null, schema["properties"]["properties"]);
+
+            // the M of `PointM` is in the properties with the default policy too
+            let schema = schema_of::<PointM>();
+            assert_eq!(json!(2), schema["properties"]["coordinates"]["maxItems"]);
+            assert_eq!(json!(["m"]), schema["properties"]["properties"]["required"]);
+
+            let schema =
+                with_measure_policy(MeasurePolicy::Properties, schema_of::<Polygon<PointM>>);
+            assert_eq!(
+                json!(2),
+                schema["properties"]["coordinates"]["items"]["items"]["maxItems"]
+            );
+            assert_eq!(json!(["m"]), schema["properties"]["properties"]["required"]);
+            assert_eq!(
+                json!(["coordinates", "properties", "type"]),
+                schema["required"]
+            );
+        }
+
+        #[test]
+        fn test_lenient_schema() {
+            let options = GeoJsonOptions {
+                lenient: true,
+                ..Default::default()
+            };
+            let schema = with_geojson_options(options, schema_of::<MultiPoint<Point>>);
+            assert_eq!(Value::Null, schema["additionalProperties"]);
+        }
+
+        #[test]
+        fn test_feature_collection_schema() {
+            let schema = schema_of::<FeatureCollection<Point, FeatureProperties>>();
+            assert_eq!(
+                json!(["FeatureCollection"]),
+                schema["properties"]["type"]["enum"]
+            );
+            assert_eq!(
+                "#/definitions/Feature_for_Point_and_Map_of_AnyValue",
+                schema["properties"]["features"]["items"]["$ref"]
+            );
+            let definitions = &schema["definitions"];
+            let feature = &definitions["Feature_for_Point_and_Map_of_AnyValue"];
+            assert_eq!(
+                json!(["geometry", "properties", "type"]),
+                feature["required"]
+            );
+            assert_eq!(
+                "#/definitions/GeometryContainer_for_Point",
+                feature["properties"]["geometry"]["anyOf"][0]["$ref"]
+            );
+            assert_eq!(
+                json!(["string", "number"]),
+                definitions["FeatureId"]["type"]
+            );
+            let geometries = definitions["GeometryContainer_for_Point"]["oneOf"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|geometry| geometry["$ref"].as_str().unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(
+                vec![
+                    "#/definitions/Point",
+                    "#/definitions/LineString_for_Point",
+                    "#/definitions/Polygon_for_Point",
+                    "#/definitions/MultiPoint_for_Point",
+                    "#/definitions/MultiLineString_for_Point",
+                    "#/definitions/MultiPolygon_for_Point",
+                    "#/definitions/GeometryCollection_for_Point",
+                ],
+                geometries
+            );
+            assert_eq!(
+                "#/definitions/GeometryContainer_for_Point",
+                definitions["GeometryCollection_for_Point"]["properties"]["geometries"]["items"]
+                    ["$ref"]
+            );
+        }
+    }
+}
+
+#[cfg(feature = "utoipa")]
+mod openapi {
+    use std::borrow::Cow;
+
+    // Like the impls derived by utoipa, the schemas implement `ComposeSchema`, which implements
+    // `PartialSchema` and is needed for the fields of generic types of the derived impls.
+    use utoipa::__dev::ComposeSchema;
+    use utoipa::openapi::{RefOr, Schema};
+    use utoipa::{PartialSchema, ToSchema};
+
+    use super::*;
+
+    fn to_schema(value: Value) -> RefOr<Schema> {
+        serde_json::from_value(value).expect("valid OpenAPI schema")
+    }
+
+    fn to_value(schema: RefOr<Schema>) -> Value {
+        serde_json::to_value(schema).expect("OpenAPI schema serializable")
+    }
+
+    /// Reference to a component schema, named like the schemas of generic types derived by
+    /// utoipa.
+    fn reference<T: ToSchema>(name: &str) -> Value {
+        json!({"$ref": format!("#/components/schemas/{}_{}", name, T::name())})
+    }
+
+    /// Component schemas of the geometry containers and of the geometry collections, which refer
+    /// to each other.
+    fn geometry_schemas<T: PositionSchema + ToSchema>(schemas: &mut Vec<(String, RefOr<Schema>)>) {
+        schemas.push((
+            format!("GeometryContainer_{}", T::name()),
+            GeometryContainer::<T>::schema(),
+        ));
+        schemas.push((
+            format!("GeometryCollection_{}", T::name()),
+            GeometryCollection::<T>::schema(),
+        ));
+    }
+
+    macro_rules! impl_point_schema {
+        ($($point:ident),*) => {
+            $(
+                impl ComposeSchema for $point {
+                    fn compose(_: Vec<RefOr<Schema>>) -> RefOr<Schema> {
+                        to_schema(geometry::<Self>("Point"))
+                    }
+                }
+
+                impl ToSchema for $point {
+                    fn name() -> Cow<'static, str> {
+                        Cow::Borrowed(stringify!($point))
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_point_schema!(Point, PointZ, PointM, PointZM);
+
+    macro_rules! impl_geometry_schema {
+        ($($geometry:ident),*) => {
+            $(
+                impl<T: PositionSchema + ToSchema> ComposeSchema for $geometry<T> {
+                    fn compose(_: Vec<RefOr<Schema>>) -> RefOr<Schema> {
+                        to_schema(geometry::<T>(stringify!($geometry)))
+                    }
+                }
+
+                impl<T: PositionSchema + ToSchema> ToSchema for $geometry<T> {
+                    fn name() -> Cow<'static, str> {
+                        Cow::Borrowed(stringify!($geometry))
+                    }
+                }
+            )*
+        };
+    }
+
+    impl_geometry_schema!(
+        MultiPoint,
+        LineString,
+        MultiLineString,
+        Polygon,
+        MultiPolygon
+    );
+
+    impl<T: PositionSchema + ToSchema> ComposeSchema for GeometryContainer<T> {
+        fn compose(_: Vec<RefOr<Schema>>) -> RefOr<Schema> {
+            to_schema(json!({
+                "oneOf": [
+                    geometry::<T>("Point"),
+                    geometry::<T>("LineString"),
+                    geometry::<T>("Polygon"),
+                    geometry::<T>("MultiPoint"),
+                    geometry::<T>("MultiLineString"),
+                    geometry::<T>("MultiPolygon"),
+                    reference::<T>("GeometryCollection"),
+                ],
+            }))
+        }
+    }
+
+    impl<T: PositionSchema + ToSchema> ToSchema for GeometryContainer<T> {
+        fn name() -> Cow<'static, str> {
+            Cow::Borrowed("GeometryContainer")
+        }
+
+        fn schemas(schemas: &mut Vec<(String, RefOr<Schema>)>) {
+            geometry_schemas::<T>(schemas);
+        }
+    }
+
+    impl<T: PositionSchema + ToSchema> ComposeSchema for GeometryCollection<T> {
+        fn compose(_: Vec<RefOr<Schema>>) -> RefOr<Schema> {
+            to_schema(geometry_collection(reference::<T>("GeometryContainer")))
+        }
+    }
+
+    impl<T: PositionSchema + ToSchema> ToSchema for GeometryCollection<T> {
+        fn name() -> Cow<'static, str> {
+            Cow::Borrowed("GeometryCollection")
+        }
+
+        fn schemas(schemas: &mut Vec<(String, RefOr<Schema>)>) {
+            geometry_schemas::<T>(schemas);
+        }
+    }
+
+    impl ComposeSchema for FeatureId {
+        fn compose(_: Vec<RefOr<Schema>>) -> RefOr<Schema> {
+            to_schema(feature_id())
+        }
+    }
+
+    impl ToSchema for FeatureId {}
+
+    impl<T, P> ComposeSchema for Feature<T, P>
+    where
+        T: PositionSchema + ToSchema,
+        P: ToSchema + serde::Serialize,
+    {
+        fn compose(_: Vec<RefOr<Schema>>) -> RefOr<Schema> {
+            to_schema(feature(
+                feature_id(),
+                to_value(GeometryContainer::<T>::schema()),
+                to_value(P::schema()),
+            ))
+        }
+    }
+
+    impl<T, P> ToSchema for Feature<T, P>
+    where
+        T: PositionSchema + ToSchema,
+        P: ToSchema + serde::Serialize,
+    {
+        fn name() -> Cow<'static, str> {
+            Cow::Borrowed("Feature")
+        }
+
+        fn schemas(schemas: &mut Vec<(String, RefOr<Schema>)>) {
+            geometry_schemas::<T>(schemas);
+            P::schemas(schemas);
+        }
+    }
+
+    impl<T, P> ComposeSchema for FeatureCollection<T, P>
+    where
+        T: PositionSchema + ToSchema,
+        P: ToSchema + serde::Serialize,
+    {
+        fn compose(_: Vec<RefOr<Schema>>) -> RefOr<Schema> {
+            to_schema(feature_collection(to_value(Feature::<T, P>::schema())))
+        }
+    }
+
+    impl<T, P> ToSchema for FeatureCollection<T, P>
+    where
+        T: PositionSchema + ToSchema,
+        P: ToSchema + serde::Serialize,
+    {
+        fn name() -> Cow<'static, str> {
+            Cow::Borrowed("FeatureCollection")
+        }
+
+        fn schemas(schemas: &mut Vec<(String, RefOr<Schema>)>) {
+            Feature::<T, P>::schemas(schemas);
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use utoipa::OpenApi;
+
+        use super::*;
+
+        #[derive(ToSchema, serde::Serialize)]
+        #[allow(dead_code)]
+        struct Station {
+            name: String,
+            location: Point,
+            platforms: Option<MultiPoint<PointZ>>,
+            area: GeometryContainer<Point>,
+        }
+
+        #[derive(OpenApi)]
+        #[openapi(components(schemas(Station, FeatureCollection<PointZM, Station>)))]
+        struct StationApi;
+
+        fn components() -> serde_json::Map<String, Value> {
+            let openapi = serde_json::to_value(StationApi::openapi()).unwrap();
+            openapi["components"]["schemas"]
+                .as_object()
+                .unwrap()
+                .clone()
+        }
+
+        fn references(value: &Value, refs: &mut Vec<String>) {
+            match value {
+                Value::Object(map) => {
+                    if let Some(Value::String(r)) = map.get("$ref") {
+                        refs.push(r.replace("#/components/schemas/", ""));
+                    }
+                    map.values().for_each(|v| references(v, refs));
+                }
+                Value::Array(values) => values.iter().for_each(|v| references(v, refs)),
+                _ => {}
+            }
+        }
+
+        #[test]
+        fn test_openapi_components() {
+            let components = components();
+            let mut refs = Vec::new();
+            references(&Value::Object(components.clone()), &mut refs);
+            for r in refs {
+                assert!(components.contains_key(&r), "missing component {}", r);
+            }
+            assert_eq!(
+                vec![
+                    "FeatureCollection_PointZM_Station",
+                    "GeometryCollection_Point",
+                    "GeometryCollection_PointZM",
+                    "GeometryContainer_Point",
+                    "GeometryContainer_PointZM",
+                    "MultiPoint_PointZ",
+                    "Point",
+                    "Station",
+                ],
+                components.keys().collect::<Vec<_>>()
+            );
+            assert_eq!(geometry::<Point>("Point"), components["Point"],);
+            assert_eq!(
+                json!(3),
+                components["MultiPoint_PointZ"]["properties"]["coordinates"]["items"]["maxItems"]
+            );
+            let station = &components["Station"]["properties"];
+            assert_eq!(
+                json!({"$ref": "#/components/schemas/GeometryContainer_Point"}),
+                station["area"]
+            );
+            assert_eq!(
+                json!({"$ref": "#/components/schemas/GeometryContainer_Point"}),
+                components["GeometryCollection_Point"]["properties"]["geometries"]["items"]
+            );
+            let geometries = components["GeometryContainer_Point"]["oneOf"]
+                .as_array()
+                .unwrap();
+            assert_eq!(7, geometries.len());
+            assert_eq!(
+                json!({"$ref": "#/components/schemas/GeometryCollection_Point"}),
+                geometries[6]
+            );
+        }
+
+        #[test]
+        fn test_openapi_feature_collection() {
+            let collection = &components()["FeatureCollection_PointZM_Station"];
+            assert_eq!(
+                json!(["FeatureCollection"]),
+                collection["properties"]["type"]["enum"]
+            );
+            let feature = &collection["properties"]["features"]["items"];
+            assert_eq!(
+                json!({"type": ["string", "number"]}),
+                feature["properties"]["id"]
+            );
+            let geometry = &feature["properties"]["geometry"]["anyOf"];
+            assert_eq!(json!({"type": "null"}), geometry[1]);
+            assert_eq!(
+                json!(4),
+                geometry[0]["oneOf"][0]["properties"]["coordinates"]["maxItems"]
+            );
+            assert_eq!(
+                json!(["name", "location", "area"]),
+                feature["properties"]["properties"]["anyOf"][0]["required"]
+            );
+        }
+    }
+}
@@ -0,0 +1,754 @@
+//! Streaming GeoJSON, one feature at a time.
+//!
+//! [`FeatureCollectionWriter`] writes a FeatureCollection to an `io::Write` while the features
+//! are produced, e.g. by `load_iter` of a Diesel query, and [`FeatureCollectionReader`] reads the
+//! features of a FeatureCollection from an `io::Read` without collecting them. The options set
+//! with `with_geojson_options` apply to every feature.
+//!
+//! [`GeoJsonSeqWriter`] and [`GeoJsonSeqReader`] write and read GeoJSON text sequences
+//! ([RFC 8142](https://www.rfc-editor.org/rfc/rfc8142)), where every text starts with the record
+//! separator. The reader accepts newline-delimited GeoJSON, without record separators, as well.
+//! ```
+//! use postgis_diesel::geojson_stream::{FeatureCollectionReader, FeatureCollectionWriter};
+//! use postgis_diesel::types::{Feature, FeatureId, GeometryContainer, Point};
+//! let features = (0..3).map(|i| Feature {
+//!     id: Some(i.into()),
+//!     bbox: None,
+//!     geometry: Some(GeometryContainer::Point(Point::new(i as f64, 0.0, Some(4326)))),
+//!     properties: None::<()>,
+//!     foreign_members: Default::default(),
+//! });
+//! let mut writer = FeatureCollectionWriter::new(Vec::new()).unwrap();
+//! writer.write_all(features).unwrap();
+//! let json = writer.finish().unwrap();
+//!
+//! let reader = FeatureCollectionReader::<_, Point, ()>::new(json.as_slice());
+//! let ids = reader
+//!     .map(|feature| feature.unwrap().id.unwrap())
+//!     .collect::<Vec<_>>();
+//! assert_eq!(vec![FeatureId::from(0), 1.into(), 2.into()], ids);
+//! ```
+//! The rows of a Diesel query are written as they are loaded:
+//! ```no_run
+//! # #[cfg(feature = "postgres")]
+//! # mod example {
+//! use diesel::connection::DefaultLoadingMode;
+//! use diesel::pg::PgConnection;
+//! use diesel::prelude::*;
+//! use postgis_diesel::geojson_stream::FeatureCollectionWriter;
+//! use postgis_diesel::types::{Feature, GeometryContainer, Point};
+//! diesel::table! {
+//!     use postgis_diesel::sql_types::*;
+//!     use diesel::sql_types::*;
+//!     stations (id) {
+//!         id -> Int4,
+//!         location -> Geometry,
+//!     }
+//! }
+//! fn export(
+//!     conn: &mut PgConnection,
+//!     out: impl std::io::Write,
+//! ) -> Result<(), Box<dyn std::error::Error>> {
+//!     let mut writer = FeatureCollectionWriter::new(out)?;
+//!     for row in stations::table.load_iter::<(i32, Point), DefaultLoadingMode>(conn)? {
+//!         let (id, location) = row?;
+//!         writer.write(&Feature {
+//!             id: Some(id.into()),
+//!             bbox: None,
+//!             geometry: Some(GeometryContainer::Point(location)),
+//!             properties: None::<()>,
+//!             foreign_members: Default::default(),
+//!         })?;
+//!     }
+//!     writer.finish()?;
+//!     Ok(())
+//! }
+//! # }
+//! # fn main() {}
+//! ```
+#![cfg(feature = "serde")]
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::marker::PhantomData;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::geojson::collection_options;
+use crate::serde::geojson::{GeoJson, GeoJsonDeserialize, GeoJsonSerialize};
+use crate::types::{with_geojson_options, Feature, GeoJsonOptions};
+
+const RECORD_SEPARATOR: u8 = 0x1e;
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Runs `f` with the options of a reader or a writer, if it has some.
+fn with_options<F, R>(options: Option<GeoJsonOptions>, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    match options {
+        Some(options) => with_geojson_options(options, f),
+        None => f(),
+    }
+}
+
+/// Writes a FeatureCollection one feature at a time.
+///
+/// The collection has no `bbox`, which would have to be written before the features.
+#[derive(Debug)]
+pub struct FeatureCollectionWriter<W: Write> {
+    writer: W,
+    empty: bool,
+    options: Option<GeoJsonOptions>,
+}
+
+impl<W: Write> FeatureCollectionWriter<W> {
+    /// Writes the start of the FeatureCollection to `writer`.
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writer.write_all(br#"{"type":"FeatureCollection","features":["#)?;
+        Ok(FeatureCollectionWriter {
+            writer,
+            empty: true,
+            options: None,
+        })
+    }
+
+    /// Writes the features with `options` instead of those of the current thread.
+    pub fn with_options(mut self, options: GeoJsonOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    /// Writes a feature of the collection.
+    pub fn write<T, P>(&mut self, feature: &Feature<T, P>) -> io::Result<()>
+    where
+        P: Serialize,
+        Feature<T, P>: Serialize,
+    {
+        if !self.empty {
+            self.writer.write_all(b",")?;
+        }
+        let writer = &mut self.writer;
+        with_options(self.options, || serde_json::to_writer(writer, feature))?;
+        self.empty = false;
+        Ok(())
+    }
+
+    /// Writes every feature of `features`.
+    pub fn write_all<T, P, I>(&mut self, features: I) -> io::Result<()>
+    where
+        P: Serialize,
+        Feature<T, P>: Serialize,
+        I: IntoIterator<Item = Feature<T, P>>,
+    {
+        for feature in features {
+            self.write(&feature)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the end of the FeatureCollection, flushes and returns the writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.write_all(b"]}")?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Reads JSON values as raw bytes, so they may be decoded one at a time.
+#[derive(Debug)]
+struct Scanner<R> {
+    reader: BufReader<R>,
+}
+
+impl<R: Read> Scanner<R> {
+    fn peek(&mut self) -> io::Result<Option<u8>> {
+        Ok(self.reader.fill_buf()?.first().copied())
+    }
+
+    fn next_byte(&mut self) -> io::Result<Option<u8>> {
+        let byte = self.peek()?;
+        if byte.is_some() {
+            self.reader.consume(1);
+        }
+        Ok(byte)
+    }
+
+    /// Skips whitespace and returns the next byte without consuming it.
+    fn skip_whitespace(&mut self) -> io::Result<Option<u8>> {
+        loop {
+            match self.peek()? {
+                Some(b' ' | b'\t' | b'\r' | b'\n') => self.reader.consume(1),
+                byte => return Ok(byte),
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: u8) -> io::Result<()> {
+        match self.skip_whitespace()? {
+            Some(byte) if byte == expected => {
+                self.reader.consume(1);
+                Ok(())
+            }
+            Some(byte) => Err(invalid_data(format!(
+                "expected `{}`, found `{}`",
+                expected as char, byte as char
+            ))),
+            None => Err(io::ErrorKind::UnexpectedEof.into()),
+        }
+    }
+
+    /// Appends the bytes of the next value to `out`, its syntax is checked when it is decoded.
+    fn read_value(&mut self, out: &mut Vec<u8>) -> io::Result<()> {
+        let first = self
+            .skip_whitespace()?
+            .ok_or(io::ErrorKind::UnexpectedEof)?;
+        match first {
+            b'{' | b'[' => {
+                let mut depth = 0usize;
+                let mut in_string = false;
+                let mut escaped = false;
+                loop {
+                    let byte = self.next_byte()?.ok_or(io::ErrorKind::UnexpectedEof)?;
+                    out.push(byte);
+                    if in_string {
+                        match byte {
+                            _ if escaped => escaped = false,
+                            b'\\' => escaped = true,
+                            b'"' => in_string = false,
+                            _ => {}
+                        }
+                        continue;
+                    }
+                    match byte {
+                        b'"' => in_string = true,
+                        b'{' | b'[' => depth += 1,
+                        b'}' | b']' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                return Ok(());
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            b'"' => {
+                self.reader.consume(1);
+                out.push(first);
+                let mut escaped = false;
+                loop {
+                    let byte = self.next_byte()?.ok_or(io::ErrorKind::UnexpectedEof)?;
+                    out.push(byte);
+                    match byte {
+                        _ if escaped => escaped = false,
+                        b'\\' => escaped = true,
+                        b'"' => return Ok(()),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {
+                while let Some(byte) = self.peek()? {
+                    if matches!(byte, b',' | b'}' | b']' | b' ' | b'\t' | b'\r' | b'\n') {
+                        break;
+                    }
+                    out.push(byte);
+                    self.reader.consume(1);
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ReaderState {
+    Start,
+    Features { first: bool },
+    Members,
+    Done,
+}
+
+/// Reads the features of a FeatureCollection one at a time.
+///
+/// The members of the collection before `features` are read with the first feature, and those
+/// after it when the last feature has been read. Reading stops at the first error.
+#[derive(Debug)]
+pub struct FeatureCollectionReader<R, T, P> {
+    scanner: Scanner<R>,
+    state: ReaderState,
+    buffer: Vec<u8>,
+    has_type: bool,
+    bbox: Option<Vec<f64>>,
+    foreign_members: serde_json::Map<String, serde_json::Value>,
+    options: Option<GeoJsonOptions>,
+    marker: PhantomData<fn() -> (T, P)>,
+}
+
+impl<R, T, P> FeatureCollectionReader<R, T, P>
+where
+    R: Read,
+    P: Serialize,
+    Feature<T, P>: DeserializeOwned,
+{
+    pub fn new(reader: R) -> Self {
+        FeatureCollectionReader {
+            scanner: Scanner {
+                reader: BufReader::new(reader),
+            },
+            state: ReaderState::Start,
+            buffer: Vec::new(),
+            has_type: false,
+            bbox: None,
+            foreign_members: serde_json::Map::new(),
+            options: None,
+            marker: PhantomData,
+        }
+    }
+
+    /// Reads the features with `options` instead of those of the current thread.
+    pub fn with_options(mut self, options: GeoJsonOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    /// `bbox` of the collection, if it has been read.
+    pub fn bbox(&self) -> Option<&[f64]> {
+        self.bbox.as_deref()
+    }
+
+    /// Members of the collection which are not defined by GeoJSON, read so far.
+    pub fn foreign_members(&self) -> &serde_json::Map<String, serde_json::Value> {
+        &self.foreign_members
+    }
+
+    /// Reads members of the collection until `features`, returns false at the end of the
+    /// collection instead.
+    fn read_members(&mut self, mut first: bool) -> io::Result<bool> {
+        loop {
+            if self.scanner.skip_whitespace()? == Some(b'}') {
+                self.scanner.next_byte()?;
+                return Ok(false);
+            }
+            if !first {
+                self.scanner.expect(b',')?;
+            }
+            first = false;
+            self.buffer.clear();
+            self.scanner.read_value(&mut self.buffer)?;
+            let key: String = serde_json::from_slice(&self.buffer)?;
+            self.scanner.expect(b':')?;
+            if key == "features" {
+                self.scanner.expect(b'[')?;
+                return Ok(true);
+            }
+            self.buffer.clear();
+            self.scanner.read_value(&mut self.buffer)?;
+            match key.as_str() {
+                "type" => {
+                    let geojson_type: String = serde_json::from_slice(&self.buffer)?;
+                    if geojson_type != "FeatureCollection" {
+                        return Err(invalid_data(format!(
+                            "unknown type `{}`, expected FeatureCollection",
+                            geojson_type
+                        )));
+                    }
+                    self.has_type = true;
+                }
+                "bbox" => self.bbox = Some(serde_json::from_slice(&self.buffer)?),
+                _ => {
+                    let value = serde_json::from_slice(&self.buffer)?;
+                    self.foreign_members.insert(key, value);
+                }
+            }
+        }
+    }
+
+    fn read_next(&mut self) -> io::Result<Option<Feature<T, P>>> {
+        loop {
+            match self.state {
+                ReaderState::Start => {
+                    self.scanner.expect(b'{')?;
+                    if !self.read_members(true)? {
+                        return Err(invalid_data("missing field `features`".to_string()));
+                    }
+                    self.state = ReaderState::Features { first: true };
+                }
+                ReaderState::Features { first } => {
+                    if self.scanner.skip_whitespace()? == Some(b']') {
+                        self.scanner.next_byte()?;
+                        self.state = ReaderState::Members;
+                        continue;
+                    }
+                    if !first {
+                        self.scanner.expect(b',')?;
+                    }
+                    self.buffer.clear();
+                    self.scanner.read_value(&mut self.buffer)?;
+                    self.state = ReaderState::Features { first: false };
+                    // the crs of the collection applies if it comes before the features
+                    let (buffer, foreign_members) = (&self.buffer, &self.foreign_members);
+                    let feature = with_options(self.options, || {
+                        let options = collection_options::<serde_json::Error>(foreign_members)?;
+                        with_geojson_options(options, || serde_json::from_slice(buffer))
+                    })?;
+                    return Ok(Some(feature));
+                }
+                ReaderState::Members => {
+                    if self.read_members(false)? {
+                        return Err(invalid_data("duplicate field `features`".to_string()));
+                    }
+                    if !self.has_type {
+                        return Err(invalid_data("missing field `type`".to_string()));
+                    }
+                    if let Some(byte) = self.scanner.skip_whitespace()? {
+                        return Err(invalid_data(format!(
+                            "unexpected `{}` after the FeatureCollection",
+                            byte as char
+                        )));
+                    }
+                    self.state = ReaderState::Done;
+                }
+                ReaderState::Done => return Ok(None),
+            }
+        }
+    }
+}
+
+impl<R, T, P> Iterator for FeatureCollectionReader<R, T, P>
+where
+    R: Read,
+    P: Serialize,
+    Feature<T, P>: DeserializeOwned,
+{
+    type Item = io::Result<Feature<T, P>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.read_next() {
+            Ok(feature) => feature.map(Ok),
+            Err(err) => {
+                self.state = ReaderState::Done;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+/// Writes a GeoJSON text sequence, every value is a record separator, its JSON text and a line
+/// feed.
+#[derive(Debug)]
+pub struct GeoJsonSeqWriter<W: Write> {
+    writer: W,
+    options: Option<GeoJsonOptions>,
+}
+
+impl<W: Write> GeoJsonSeqWriter<W> {
+    pub fn new(writer: W) -> Self {
+        GeoJsonSeqWriter {
+            writer,
+            options: None,
+        }
+    }
+
+    /// Writes the values with `options` instead of those of the current thread.
+    pub fn with_options(mut self, options: GeoJsonOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    /// Writes a feature, a feature collection or a geometry of the sequence, as GeoJSON whatever
+    /// the serde impls of its type.
+    pub fn write<V: GeoJsonSerialize>(&mut self, value: &V) -> io::Result<()> {
+        self.writer.write_all(&[RECORD_SEPARATOR])?;
+        let writer = &mut self.writer;
+        with_options(self.options, || {
+            serde_json::to_writer(writer, &GeoJson(value))
+        })?;
+        self.writer.write_all(b"\n")
+    }
+
+    /// Writes every value of `values`.
+    pub fn write_all<V, I>(&mut self, values: I) -> io::Result<()>
+    where
+        V: GeoJsonSerialize,
+        I: IntoIterator<Item = V>,
+    {
+        for value in values {
+            self.write(&value)?;
+        }
+        Ok(())
+    }
+
+    /// Flushes and returns the writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Reads the values of a GeoJSON text sequence, or of newline-delimited GeoJSON when the input
+/// does not start with a record separator.
+///
+/// Every record is decoded on its own, so reading goes on after a record which can't be
+/// decoded, e.g. a truncated one.
+#[derive(Debug)]
+pub struct GeoJsonSeqReader<R, V> {
+    reader: BufReader<R>,
+    delimiter: Option<u8>,
+    buffer: Vec<u8>,
+    options: Option<GeoJsonOptions>,
+    marker: PhantomData<fn() -> V>,
+}
+
+impl<R: Read, V: GeoJsonDeserialize> GeoJsonSeqReader<R, V> {
+    pub fn new(reader: R) -> Self {
+        GeoJsonSeqReader {
+            reader: BufReader::new(reader),
+            delimiter: None,
+            buffer: Vec::new(),
+            options: None,
+            marker: PhantomData,
+        }
+    }
+
+    /// Reads the values with `options` instead of those of the current thread.
+    pub fn with_options(mut self, options: GeoJsonOptions) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    fn delimiter(&mut self) -> io::Result<u8> {
+        if let Some(delimiter) = self.delimiter {
+            return Ok(delimiter);
+        }
+        let delimiter = loop {
+            match self.reader.fill_buf()?.first() {
+                Some(b' ' | b'\t' | b'\r' | b'\n') => self.reader.consume(1),
+                Some(&RECORD_SEPARATOR) => break RECORD_SEPARATOR,
+                _ => break b'\n',
+            }
+        };
+        self.delimiter = Some(delimiter);
+        Ok(delimiter)
+    }
+
+    fn read_next(&mut self) -> io::Result<Option<V>> {
+        let delimiter = self.delimiter()?;
+        loop {
+            self.buffer.clear();
+            if self.reader.read_until(delimiter, &mut self.buffer)? == 0 {
+                return Ok(None);
+            }
+            let record = self
+                .buffer
+                .split(|byte| *byte == delimiter)
+                .next()
+                .unwrap_or_default();
+            if record.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            let value = with_options(self.options, || {
+                serde_json::from_slice::<GeoJson<V>>(record)
+            })?;
+            return Ok(Some(value.0));
+        }
+    }
+}
+
+impl<R: Read, V: GeoJsonDeserialize> Iterator for GeoJsonSeqReader<R, V> {
+    type Item = io::Result<V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_next().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::*;
+
+    fn feature(id: i64, x: f64, y: f64) -> Feature<Point, serde_json::Value> {
+        Feature {
+            id: Some(id.into()),
+            bbox: None,
+            geometry: Some(GeometryContainer::Point(Point::new(x, y, Some(4326)))),
+            properties: Some(serde_json::json!({ "name": format!("station {}", id) })),
+            foreign_members: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_feature_collection_round_trip() {
+        let features = (0..100).map(|i| feature(i, i as f64, -(i as f64)));
+        let mut writer = FeatureCollectionWriter::new(Vec::new()).unwrap();
+        writer.write_all(features.clone()).unwrap();
+        let json = writer.finish().unwrap();
+
+        let feature_collection: FeatureCollection<Point, serde_json::Value> =
+            serde_json::from_slice(&json).unwrap();
+        assert_eq!(
+            features.clone().collect::<Vec<_>>(),
+            feature_collection.features
+        );
+        let read = FeatureCollectionReader::new(json.as_slice())
+            .collect::<io::Result<Vec<Feature<Point, serde_json::Value>>>>()
+            .unwrap();
+        assert_eq!(features.collect::<Vec<_>>(), read);
+    }
+
+    #[test]
+    fn test_empty_feature_collection() {
+        let json = FeatureCollectionWriter::new(Vec::new())
+            .unwrap()
+            .finish()
+            .unwrap();
+        assert_eq!(
+            br#"{"type":"FeatureCollection","features":[]}"#,
+            json.as_slice()
+        );
+        let mut reader = FeatureCollectionReader::<_, Point, ()>::new(json.as_slice());
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_read_feature_collection_members() {
+        let json = r#"
+            {
+                "bbox": [0, 0, 2, 2],
+                "name": "stations [north]",
+                "features": [
+                    {"type": "Feature", "id": "a\"]}", "geometry": {"type": "Point", "coordinates": [1, 2]}, "properties": null},
+                    {"type": "Feature", "geometry": null, "properties": {"tags": ["{", "}"]}}
+                ],
+                "type": "FeatureCollection",
+                "count": 2
+            }
+        "#;
+        let mut reader =
+            FeatureCollectionReader::<_, Point, serde_json::Value>::new(json.as_bytes());
+        let first = reader.next().unwrap().unwrap();
+        assert_eq!(Some(FeatureId::from("a\"]}")), first.id);
+        assert_eq!(
+            Some(GeometryContainer::Point(Point::new(1.0, 2.0, Some(4326)))),
+            first.geometry
+        );
+        assert_eq!(Some(&[0.0, 0.0, 2.0, 2.0][..]), reader.bbox());
+        assert_eq!(1, reader.foreign_members().len());
+        let second = reader.next().unwrap().unwrap();
+        assert_eq!(
+            Some(serde_json::json!({"tags": ["{", "}"]})),
+            second.properties
+        );
+        assert!(reader.next().is_none());
+        assert_eq!(
+            Some(&serde_json::json!(2)),
+            reader.foreign_members().get("count")
+        );
+    }
+
+    #[test]
+    fn test_read_invalid_feature_collection() {
+        for json in [
+            r#"{"type":"Feature","features":[]}"#,
+            r#"{"type":"FeatureCollection"}"#,
+            r#"{"features":[]}"#,
+            r#"{"type":"FeatureCollection","features":[{"type":"Feature","geometry":null,"properties":null}"#,
+            r#"{"type":"FeatureCollection","features":[{"type":"Point","coordinates":[1,2]}]}"#,
+            r#"{"type":"FeatureCollection","features":[]} []"#,
+        ] {
+            let result = FeatureCollectionReader::<_, Point, ()>::new(json.as_bytes())
+                .collect::<io::Result<Vec<_>>>();
+            assert!(result.is_err(), "{}", json);
+        }
+        // reading stops at the first error
+        let json = r#"{"type":"FeatureCollection","features":[1,{"type":"Feature","geometry":null,"properties":null}]}"#;
+        let mut reader = FeatureCollectionReader::<_, Point, ()>::new(json.as_bytes());
+        assert!(reader.next().unwrap().is_err());
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn test_geojson_seq_round_trip() {
+        let features = (0..3).map(|i| feature(i, 1.0, 2.0)).collect::<Vec<_>>();
+        let mut writer = GeoJsonSeqWriter::new(Vec::new());
+        writer.write_all(&features).unwrap();
+        let seq = writer.finish().unwrap();
+        assert_eq!(
+            3,
+            seq.iter().filter(|byte| **byte == RECORD_SEPARATOR).count()
+        );
+        assert!(seq.starts_with(b"\x1e{\"type\":\"Feature\",\"id\":0,"));
+
+        let read = GeoJsonSeqReader::new(seq.as_slice())
+            .collect::<io::Result<Vec<Feature<Point, serde_json::Value>>>>()
+            .unwrap();
+        assert_eq!(features, read);
+    }
+
+    #[test]
+    fn test_read_geojson_seq() {
+        // a pretty-printed text and a truncated one
+        let seq = "\x1e{\"type\":\"Point\",\n \"coordinates\":[1,2]}\n\x1e{\"type\":\"Point\",\"coord\n\x1e{\"type\":\"Point\",\"coordinates\":[3,4]}\n";
+        let read = GeoJsonSeqReader::<_, Point>::new(seq.as_bytes()).collect::<Vec<_>>();
+        assert_eq!(3, read.len());
+        assert_eq!(Point::new(1.0, 2.0, Some(4326)), *read[0].as_ref().unwrap());
+        assert!(read[1].is_err());
+        assert_eq!(Point::new(3.0, 4.0, Some(4326)), *read[2].as_ref().unwrap());
+    }
+
+    #[test]
+    fn test_read_newline_delimited_geojson() {
+        let lines = "\n{\"type\":\"Point\",\"coordinates\":[1,2]}\r\n\n{\"type\":\"Point\",\"coordinates\":[3,4]}";
+        let read = GeoJsonSeqReader::<_, Point>::new(lines.as_bytes())
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            vec![
+                Point::new(1.0, 2.0, Some(4326)),
+                Point::new(3.0, 4.0, Some(4326))
+            ],
+            read
+        );
+    }
+
+    #[test]
+    fn test_stream_options() {
+        let json = r#"{"type":"FeatureCollection","crs":{"type":"name","properties":{"name":"EPSG:2056"}},"features":[{"type":"Feature","geometry":{"type":"Point","coordinates":[2600000,1200000]},"properties":null}]}"#;
+        let options = GeoJsonOptions {
+            srid: SridPolicy::AllowAny { crs: false },
+            ..Default::default()
+        };
+        assert!(
+            FeatureCollectionReader::<_, Point, ()>::new(json.as_bytes())
+                .next()
+                .unwrap()
+                .is_err()
+        );
+        let feature = FeatureCollectionReader::<_, Point, ()>::new(json.as_bytes())
+            .with_options(options)
+            .next()
+            .unwrap()
+            .unwrap();
+        let point = Point::new(2600000.0, 1200000.0, Some(2056));
+        assert_eq!(Some(GeometryContainer::Point(point)), feature.geometry);
+
+        assert!(GeoJsonSeqWriter::new(Vec::new()).write(&point).is_err());
+        let mut writer = GeoJsonSeqWriter::new(Vec::new()).with_options(options);
+        writer.write(&point).unwrap();
+        let seq = writer.finish().unwrap();
+        let read = GeoJsonSeqReader::<_, Point>::new(seq.as_slice())
+            .with_options(GeoJsonOptions {
+                srid: SridPolicy::DefaultSrid(Some(2056)),
+                ..Default::default()
+            })
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(point, read);
+    }
+}
@@ -1,10 +1,11 @@
 use crate::{
-    ewkb::{EwkbSerializable, GeometryType},
+    ewkb::{EwkbHeader, EwkbSerializable, GeometryType},
     points::Dimension,
     types::*,
 };
 
-use crate::write_to_read_from_sql::{ReadFromSql, WriteToSql};
+use crate::errors::DecodeErrorReason;
+use crate::write_to_read_from_sql::{read_part, ReadFromSql, WriteToSql, MAX_NESTING_DEPTH};
 
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
@@ -76,48 +77,20 @@ where
         Reader: std::io::Read,
         Endianness: byteorder::ByteOrder,
     {
+        if header.depth >= MAX_NESTING_DEPTH {
+            return Err(DecodeErrorReason::TooDeeplyNested.into());
+        }
         let geometries_n = reader.read_u32::<Endianness>()?;
         let mut g_collection = GeometryCollection::new(header.srid);
+        // the parts of the collection are one level deeper
+        let parts = EwkbHeader {
+            depth: header.depth + 1,
+            ..header
+        };
         for _i in 0..geometries_n {
-            // skip 1 byte for byte order and 4 bytes for point type
-            reader.read_u8()?;
-            let geom_type = GeometryType::from(reader.read_u32::<Endianness>()?);
-            let g_container = match geom_type {
-                GeometryType::Point => {
-                    GeometryContainer::Point(P::read_body::<Endianness, Reader>(header, reader)?)
-                }
-                GeometryType::LineString => {
-                    GeometryContainer::LineString(LineString::read_body::<Endianness, Reader>(
-                        header, reader,
-                    )?)
-                }
-                GeometryType::Polygon => {
-                    GeometryContainer::Polygon(Polygon::read_body::<Endianness, Reader>(
-                        header, reader,
-                    )?)
-                }
-                GeometryType::MultiPoint => {
-                    GeometryContainer::MultiPoint(MultiPoint::read_body::<Endianness, Reader>(
-                        header, reader,
-                    )?)
-                }
-                GeometryType::MultiLineString => {
-                    GeometryContainer::MultiLineString(MultiLineString::read_body::<
-                        Endianness,
-                        Reader,
-                    >(header, reader)?)
-                }
-                GeometryType::MultiPolygon => GeometryContainer::MultiPolygon(
-                    MultiPolygon::read_body::<Endianness, Reader>(header, reader)?,
-                ),
-                GeometryType::GeometryCollection => {
-                    GeometryContainer::GeometryCollection(GeometryCollection::read_body::<
-                        Endianness,
-                        Reader,
-                    >(header, reader)?)
-                }
-            };
-            g_collection.geometries.push(g_container);
+            g_collection
+                .geometries
+                .push(read_part::<GeometryContainer<P>, Reader>(parts, reader)?);
         }
         Ok(g_collection)
     }
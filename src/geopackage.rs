@@ -0,0 +1,424 @@
+//! GeoPackage support for the SQLite backend.
+//!
+//! Geometries of [`GeoPackageGeometry`](crate::sql_types::GeoPackageGeometry) columns are stored in
+//! the GeoPackage binary format: the `GP` magic, the version, the flags, the SRID and an optional
+//! envelope, followed by ISO WKB. The tables below describe the metadata tables which make a SQLite
+//! database a GeoPackage, [`create_metadata_tables`] creates them.
+//! ```
+//! use diesel::prelude::*;
+//! use postgis_diesel::geopackage::{create_metadata_tables, gpkg_contents, gpkg_geometry_columns};
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//! create_metadata_tables(&mut conn).unwrap();
+//! diesel::insert_into(gpkg_contents::table)
+//!     .values((
+//!         gpkg_contents::table_name.eq("places"),
+//!         gpkg_contents::data_type.eq("features"),
+//!         gpkg_contents::srs_id.eq(4326),
+//!     ))
+//!     .execute(&mut conn)
+//!     .unwrap();
+//! diesel::insert_into(gpkg_geometry_columns::table)
+//!     .values((
+//!         gpkg_geometry_columns::table_name.eq("places"),
+//!         gpkg_geometry_columns::column_name.eq("geom"),
+//!         gpkg_geometry_columns::geometry_type_name.eq("POINT"),
+//!         gpkg_geometry_columns::srs_id.eq(4326),
+//!         gpkg_geometry_columns::z.eq(0),
+//!         gpkg_geometry_columns::m.eq(0),
+//!     ))
+//!     .execute(&mut conn)
+//!     .unwrap();
+//! ```
+#![cfg(feature = "sqlite")]
+
+use byteorder::{BigEndian, ByteOrder, LittleEndian, WriteBytesExt};
+use diesel::RunQueryDsl;
+
+use crate::ewkb::{LITTLE_ENDIAN, SRID};
+use crate::types::EwkbRef;
+
+diesel::table! {
+    /// Spatial reference systems used by the GeoPackage, `-1`, `0` and `4326` are always defined.
+    gpkg_spatial_ref_sys (srs_id) {
+        srs_name -> Text,
+        srs_id -> Integer,
+        organization -> Text,
+        organization_coordsys_id -> Integer,
+        definition -> Text,
+        description -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    /// Tables holding the content of the GeoPackage, `data_type` is `features` for geometry tables.
+    gpkg_contents (table_name) {
+        table_name -> Text,
+        data_type -> Text,
+        identifier -> Nullable<Text>,
+        description -> Nullable<Text>,
+        last_change -> Text,
+        min_x -> Nullable<Double>,
+        min_y -> Nullable<Double>,
+        max_x -> Nullable<Double>,
+        max_y -> Nullable<Double>,
+        srs_id -> Nullable<Integer>,
+    }
+}
+
+diesel::table! {
+    /// Geometry columns of the feature tables, `z` and `m` are 0 when prohibited, 1 when mandatory
+    /// and 2 when optional.
+    gpkg_geometry_columns (table_name, column_name) {
+        table_name -> Text,
+        column_name -> Text,
+        geometry_type_name -> Text,
+        srs_id -> Integer,
+        z -> Integer,
+        m -> Integer,
+    }
+}
+
+diesel::allow_tables_to_appear_in_same_query!(
+    gpkg_spatial_ref_sys,
+    gpkg_contents,
+    gpkg_geometry_columns
+);
+
+const METADATA_TABLES: [&str; 8] = [
+    "PRAGMA application_id = 1196444487",
+    "PRAGMA user_version = 10400",
+    "CREATE TABLE IF NOT EXISTS gpkg_spatial_ref_sys (
+    srs_name TEXT NOT NULL,
+    srs_id INTEGER NOT NULL PRIMARY KEY,
+    organization TEXT NOT NULL,
+    organization_coordsys_id INTEGER NOT NULL,
+    definition TEXT NOT NULL,
+    description TEXT
+)",
+    "CREATE TABLE IF NOT EXISTS gpkg_contents (
+    table_name TEXT NOT NULL PRIMARY KEY,
+    data_type TEXT NOT NULL,
+    identifier TEXT UNIQUE,
+    description TEXT DEFAULT '',
+    last_change DATETIME NOT NULL DEFAULT (strftime('%Y-%m-%dT%H:%M:%fZ','now')),
+    min_x DOUBLE,
+    min_y DOUBLE,
+    max_x DOUBLE,
+    max_y DOUBLE,
+    srs_id INTEGER,
+    CONSTRAINT fk_gc_r_srs_id FOREIGN KEY (srs_id) REFERENCES gpkg_spatial_ref_sys(srs_id)
+)",
+    "CREATE TABLE IF NOT EXISTS gpkg_geometry_columns (
+    table_name TEXT NOT NULL,
+    column_name TEXT NOT NULL,
+    geometry_type_name TEXT NOT NULL,
+    srs_id INTEGER NOT NULL,
+    z TINYINT NOT NULL,
+    m TINYINT NOT NULL,
+    CONSTRAINT pk_geom_cols PRIMARY KEY (table_name, column_name),
+    CONSTRAINT fk_gc_tn FOREIGN KEY (table_name) REFERENCES gpkg_contents(table_name),
+    CONSTRAINT fk_gc_srs FOREIGN KEY (srs_id) REFERENCES gpkg_spatial_ref_sys(srs_id)
+)",
+    "INSERT OR IGNORE INTO gpkg_spatial_ref_sys VALUES (
+    'Undefined cartesian SRS', -1, 'NONE', -1, 'undefined',
+    'undefined cartesian coordinate reference system'
+)",
+    "INSERT OR IGNORE INTO gpkg_spatial_ref_sys VALUES (
+    'Undefined geographic SRS', 0, 'NONE', 0, 'undefined',
+    'undefined geographic coordinate reference system'
+)",
+    "INSERT OR IGNORE INTO gpkg_spatial_ref_sys VALUES (
+    'WGS 84 geodetic', 4326, 'EPSG', 4326,
+    'GEOGCS[\"WGS 84\",DATUM[\"WGS_1984\",SPHEROID[\"WGS 84\",6378137,298.257223563,AUTHORITY[\"EPSG\",\"7030\"]],AUTHORITY[\"EPSG\",\"6326\"]],PRIMEM[\"Greenwich\",0,AUTHORITY[\"EPSG\",\"8901\"]],UNIT[\"degree\",0.0174532925199433,AUTHORITY[\"EPSG\",\"9122\"]],AUTHORITY[\"EPSG\",\"4326\"]]',
+    'longitude/latitude coordinates in decimal degrees on the WGS 84 spheroid'
+)",
+];
+
+/// Marks the database as a GeoPackage and creates the `gpkg_spatial_ref_sys`, `gpkg_contents` and
+/// `gpkg_geometry_columns` tables if they do not exist yet.
+pub fn create_metadata_tables(conn: &mut diesel::SqliteConnection) -> diesel::QueryResult<()> {
+    for statement in METADATA_TABLES {
+        diesel::sql_query(statement).execute(conn)?;
+    }
+    Ok(())
+}
+
+const MAGIC: [u8; 2] = *b"GP";
+const VERSION: u8 = 0;
+const LITTLE_ENDIAN_FLAG: u8 = 0b0000_0001;
+const EMPTY_FLAG: u8 = 0b0001_0000;
+const EXTENDED_FLAG: u8 = 0b0010_0000;
+
+fn invalid_data(reason: String) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, reason)
+}
+
+/// Envelope of the ordinates of a geometry, in the GeoPackage order `[min_x, max_x, min_y, ...]`.
+struct Envelope {
+    bounds: [(f64, f64); 4],
+    has_z: bool,
+    has_m: bool,
+    empty: bool,
+}
+
+impl Envelope {
+    fn new() -> Self {
+        Envelope {
+            bounds: [(f64::INFINITY, f64::NEG_INFINITY); 4],
+            has_z: false,
+            has_m: false,
+            empty: true,
+        }
+    }
+
+    fn add(&mut self, ordinate: usize, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+        self.empty = false;
+        let (min, max) = &mut self.bounds[ordinate];
+        *min = min.min(value);
+        *max = max.max(value);
+    }
+
+    /// Envelope contents indicator of the flags, 0 for no envelope.
+    fn indicator(&self) -> u8 {
+        match (self.empty, self.has_z, self.has_m) {
+            (true, _, _) => 0,
+            (false, false, false) => 1,
+            (false, true, false) => 2,
+            (false, false, true) => 3,
+            (false, true, true) => 4,
+        }
+    }
+
+    fn write<W: std::io::Write>(&self, out: &mut W) -> Result<(), std::io::Error> {
+        let ordinates = match self.indicator() {
+            0 => return Ok(()),
+            1 => vec![0, 1],
+            2 => vec![0, 1, 2],
+            3 => vec![0, 1, 3],
+            _ => vec![0, 1, 2, 3],
+        };
+        for ordinate in ordinates {
+            let (min, max) = self.bounds[ordinate];
+            out.write_f64::<LittleEndian>(min)?;
+            out.write_f64::<LittleEndian>(max)?;
+        }
+        Ok(())
+    }
+}
+
+/// Size of the envelope in bytes for an envelope contents indicator.
+fn envelope_len(indicator: u8) -> Result<usize, std::io::Error> {
+    match indicator {
+        0 => Ok(0),
+        1 => Ok(32),
+        2 | 3 => Ok(48),
+        4 => Ok(64),
+        _ => Err(invalid_data(format!(
+            "Invalid GeoPackage envelope indicator {}",
+            indicator
+        ))),
+    }
+}
+
+/// Converts the EWKB of a geometry to a GeoPackage binary geometry with an envelope.
+pub(crate) fn ewkb_to_gpkg(ewkb: &[u8]) -> Result<Vec<u8>, std::io::Error> {
+    let ewkb = EwkbRef::new(ewkb);
+    let mut wkb = Vec::with_capacity(ewkb.as_bytes().len());
+    let mut envelope = Envelope::new();
+    ewkb.write_wkb(
+        &mut wkb,
+        &mut |out, header, _| {
+            out.write_u8(LITTLE_ENDIAN)?;
+            out.write_u32::<LittleEndian>(header.iso_type())
+        },
+        &mut |coordinate| {
+            envelope.has_z |= coordinate.z.is_some();
+            envelope.has_m |= coordinate.m.is_some();
+            envelope.add(0, coordinate.x);
+            envelope.add(1, coordinate.y);
+            envelope.add(2, coordinate.z.unwrap_or(f64::NAN));
+            envelope.add(3, coordinate.m.unwrap_or(f64::NAN));
+        },
+    )?;
+    let mut flags = LITTLE_ENDIAN_FLAG | envelope.indicator() << 1;
+    if envelope.empty {
+        flags |= EMPTY_FLAG;
+    }
+    let mut out = Vec::with_capacity(8 + 64 + wkb.len());
+    out.extend_from_slice(&MAGIC);
+    out.write_u8(VERSION)?;
+    out.write_u8(flags)?;
+    out.write_i32::<LittleEndian>(ewkb.srid()?.map_or(0, |srid| srid as i32))?;
+    envelope.write(&mut out)?;
+    out.extend_from_slice(&wkb);
+    Ok(out)
+}
+
+/// Converts a GeoPackage binary geometry to the little-endian EWKB of the geometry, SRID 0 and -1
+/// are read as no SRID.
+pub(crate) fn gpkg_to_ewkb(blob: &[u8]) -> Result<Vec<u8>, std::io::Error> {
+    if blob.len() < 8 || blob[..2] != MAGIC {
+        return Err(invalid_data("Not a GeoPackage geometry".to_string()));
+    }
+    if blob[2] != VERSION {
+        return Err(invalid_data(format!(
+            "Unsupported GeoPackage geometry version {}",
+            blob[2]
+        )));
+    }
+    let flags = blob[3];
+    if flags & EXTENDED_FLAG != 0 {
+        return Err(invalid_data(
+            "Extended GeoPackage geometries are not supported".to_string(),
+        ));
+    }
+    let srid = if flags & LITTLE_ENDIAN_FLAG != 0 {
+        LittleEndian::read_i32(&blob[4..8])
+    } else {
+        BigEndian::read_i32(&blob[4..8])
+    };
+    let wkb_start = 8 + envelope_len((flags >> 1) & 0b111)?;
+    if blob.len() < wkb_start {
+        return Err(invalid_data("Truncated GeoPackage envelope".to_string()));
+    }
+    let srid = Some(srid).filter(|srid| *srid > 0);
+    // EwkbRef reads ISO WKB, the dimensions given by EWKB flags are accepted as well
+    let mut out = Vec::with_capacity(blob.len() - wkb_start + 4);
+    EwkbRef::new(&blob[wkb_start..]).write_wkb(
+        &mut out,
+        &mut |out, header, depth| {
+            out.write_u8(LITTLE_ENDIAN)?;
+            match srid.filter(|_| depth == 0) {
+                Some(srid) => {
+                    out.write_u32::<LittleEndian>(header.ewkb_type() | SRID)?;
+                    out.write_u32::<LittleEndian>(srid as u32)
+                }
+                None => out.write_u32::<LittleEndian>(header.ewkb_type()),
+            }
+        },
+        &mut |_| {},
+    )?;
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::DecodeErrorReason;
+    use crate::types::{LineString, MultiPoint, Point, PointZM};
+    use crate::write_to_read_from_sql::{ReadFromSql, WriteToSql};
+
+    fn to_gpkg<T: WriteToSql>(geometry: &T) -> Vec<u8> {
+        let mut ewkb = Vec::new();
+        geometry.write_to_sql(true, &mut ewkb).unwrap();
+        ewkb_to_gpkg(&ewkb).unwrap()
+    }
+
+    #[test]
+    fn test_point_gpkg() {
+        let point = Point::new(1.0, 2.0, Some(4326));
+        let blob = to_gpkg(&point);
+        let mut expected = vec![b'G', b'P', 0x00, 0x03, 0xE6, 0x10, 0x00, 0x00];
+        for value in [1.0f64, 1.0, 2.0, 2.0] {
+            expected.extend_from_slice(&value.to_le_bytes());
+        }
+        expected.extend_from_slice(&[0x01, 0x01, 0x00, 0x00, 0x00]);
+        expected.extend_from_slice(&1.0f64.to_le_bytes());
+        expected.extend_from_slice(&2.0f64.to_le_bytes());
+        assert_eq!(expected, blob);
+        assert_eq!(
+            point,
+            Point::read_from_sql(&gpkg_to_ewkb(&blob).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_zm_gpkg() {
+        let mut line = LineString::new(None);
+        line.add_points([
+            PointZM::new(0.0, -1.0, 5.0, 7.0, None),
+            PointZM::new(3.0, 4.0, 6.0, 8.0, None),
+        ])
+        .unwrap();
+        let blob = to_gpkg(&line);
+        // little endian, XYZM envelope
+        assert_eq!(0b0000_1001, blob[3]);
+        assert_eq!(&0i32.to_le_bytes(), &blob[4..8]);
+        let envelope: Vec<f64> = blob[8..72].chunks(8).map(LittleEndian::read_f64).collect();
+        assert_eq!(vec![0.0, 3.0, -1.0, 4.0, 5.0, 6.0, 7.0, 8.0], envelope);
+        assert_eq!(&3002u32.to_le_bytes(), &blob[73..77]);
+        assert_eq!(
+            line,
+            LineString::read_from_sql(&gpkg_to_ewkb(&blob).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_empty_gpkg() {
+        let multipoint: MultiPoint<Point> = MultiPoint::new(Some(4326));
+        let blob = to_gpkg(&multipoint);
+        assert_eq!(EMPTY_FLAG | LITTLE_ENDIAN_FLAG, blob[3]);
+        assert_eq!(8 + 9, blob.len());
+        assert_eq!(
+            multipoint,
+            MultiPoint::read_from_sql(&gpkg_to_ewkb(&blob).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_big_endian_gpkg() {
+        let mut blob = vec![b'G', b'P', 0x00, 0b0000_0010, 0x00, 0x00, 0x0F, 0x11];
+        for value in [1.0f64, 1.0, 2.0, 2.0] {
+            blob.extend_from_slice(&value.to_be_bytes());
+        }
+        blob.extend_from_slice(&[0x00, 0x00, 0x00, 0x00, 0x01]);
+        blob.extend_from_slice(&1.0f64.to_be_bytes());
+        blob.extend_from_slice(&2.0f64.to_be_bytes());
+        assert_eq!(
+            Point::new(1.0, 2.0, Some(3857)),
+            Point::read_from_sql(&gpkg_to_ewkb(&blob).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_crafted_count_gpkg() {
+        // a ZM line string of 0x8000_0000 points with a single value
+        let mut blob = vec![b'G', b'P', 0x00, LITTLE_ENDIAN_FLAG, 0x00, 0x00, 0x00, 0x00];
+        blob.push(0x01);
+        blob.extend_from_slice(&3002u32.to_le_bytes());
+        blob.extend_from_slice(&0x8000_0000u32.to_le_bytes());
+        blob.extend_from_slice(&1.0f64.to_le_bytes());
+        let error = gpkg_to_ewkb(&blob).unwrap_err();
+        assert_eq!(std::io::ErrorKind::UnexpectedEof, error.kind());
+    }
+
+    #[test]
+    fn test_invalid_gpkg() {
+        let mut ewkb = Vec::new();
+        Point::new(1.0, 2.0, None)
+            .write_to_sql(true, &mut ewkb)
+            .unwrap();
+        assert!(gpkg_to_ewkb(&ewkb).is_err());
+        let mut blob = ewkb_to_gpkg(&ewkb).unwrap();
+        blob[3] |= EXTENDED_FLAG;
+        assert!(gpkg_to_ewkb(&blob).is_err());
+    }
+
+    #[test]
+    fn test_nested_gpkg() {
+        let mut blob = vec![b'G', b'P', 0x00, LITTLE_ENDIAN_FLAG, 0x00, 0x00, 0x00, 0x00];
+        for _ in 0..200_000 {
+            blob.extend_from_slice(&[0x01, 7, 0, 0, 0, 1, 0, 0, 0]);
+        }
+        let error = gpkg_to_ewkb(&blob).unwrap_err();
+        assert_eq!(
+            Some(&DecodeErrorReason::TooDeeplyNested),
+            error
+                .get_ref()
+                .and_then(|inner| inner.downcast_ref::<DecodeErrorReason>())
+        );
+    }
+}
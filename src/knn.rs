@@ -0,0 +1,133 @@
+//! K nearest neighbours queries assisted by the `<->` index operator.
+#![cfg(feature = "postgres")]
+
+use diesel::expression::AsExpression;
+use diesel::pg::Pg;
+use diesel::query_builder::{AstPass, Query, QueryFragment, QueryId};
+use diesel::sql_types::{BigInt, Double};
+use diesel::{Column, Expression, QueryResult, RunQueryDsl, Table};
+
+use crate::operators::{distance_2d, Distance2d};
+use crate::sql_types::GeoType;
+
+/// Query returning the rows of a table closest to a target geometry, along with their distance.
+///
+/// Rows are loaded as `(Row, f64)` where `Row` is the table's default selection.
+///
+/// `KnnQuery` is a complete query and not a Diesel query builder, so it can't be combined with
+/// `.filter()`, `.select()` or the other query DSL methods. To restrict the rows, write the query
+/// with Diesel, e.g. `table.filter(...).order_by(distance_2d(column, target)).limit(n)`, without the
+/// distance column and the re-ranking.
+/// ```
+/// #[macro_use] extern crate diesel;
+/// use postgis_diesel::knn::nearest;
+/// use postgis_diesel::types::Point;
+/// table! {
+///     use postgis_diesel::sql_types::*;
+///     use diesel::sql_types::*;
+///     places (id) {
+///         id -> Int4,
+///         location -> Geography,
+///     }
+/// }
+/// let target = Point::new(13.4, 52.5, Some(4326));
+/// // SELECT ..., location <-> $1 FROM places ORDER BY location <-> $1 LIMIT 5
+/// let _index_only = nearest(places::table, places::location, target, 5);
+/// // re-ranks the 50 index candidates by the exact ST_Distance
+/// let _exact = nearest(places::table, places::location, target, 5).recheck(50);
+/// ```
+#[derive(Debug, Clone)]
+pub struct KnnQuery<T: Table, D> {
+    table: T,
+    columns: T::AllColumns,
+    distance: D,
+    limit: i64,
+    candidates: Option<i64>,
+}
+
+/// Builds a [`KnnQuery`] ordered by `column <-> target` and limited to `limit` rows.
+///
+/// The distance column is the one computed by the `<->` operator, which is exact for geometry
+/// and uses a sphere for geography. Use [`KnnQuery::recheck`] to get exact `ST_Distance` order.
+pub fn nearest<T, C, V>(
+    table: T,
+    column: C,
+    target: V,
+    limit: i64,
+) -> KnnQuery<T, Distance2d<C, V::Expression>>
+where
+    T: Table,
+    C: Column<Table = T> + Expression,
+    C::SqlType: GeoType,
+    V: AsExpression<C::SqlType>,
+{
+    KnnQuery {
+        table,
+        columns: T::all_columns(),
+        distance: distance_2d(column, target),
+        limit,
+        candidates: None,
+    }
+}
+
+impl<T: Table, C, V> KnnQuery<T, Distance2d<C, V>> {
+    /// Takes `candidates` nearest rows from the index and re-ranks them by `ST_Distance`, which is
+    /// then returned as the distance column. `candidates` lower than the limit are raised to the
+    /// limit, so the query never returns fewer rows than without re-ranking.
+    pub fn recheck(mut self, candidates: i64) -> Self {
+        self.candidates = Some(candidates.max(self.limit));
+        self
+    }
+}
+
+impl<T: Table, D> QueryId for KnnQuery<T, D> {
+    type QueryId = ();
+
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<T, D> Query for KnnQuery<T, D>
+where
+    T: Table,
+{
+    type SqlType = (<T::AllColumns as Expression>::SqlType, Double);
+}
+
+impl<T: Table, D, Conn> RunQueryDsl<Conn> for KnnQuery<T, D> {}
+
+impl<T, C, V> QueryFragment<Pg> for KnnQuery<T, Distance2d<C, V>>
+where
+    T: Table + QueryFragment<Pg>,
+    T::AllColumns: QueryFragment<Pg>,
+    C: QueryFragment<Pg>,
+    V: QueryFragment<Pg>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, Pg>) -> QueryResult<()> {
+        if self.candidates.is_some() {
+            out.push_sql("SELECT * FROM (");
+        }
+        out.push_sql("SELECT ");
+        self.columns.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        if self.candidates.is_some() {
+            out.push_sql("ST_Distance(");
+            self.distance.left.walk_ast(out.reborrow())?;
+            out.push_sql(", ");
+            self.distance.right.walk_ast(out.reborrow())?;
+            out.push_sql(")");
+        } else {
+            self.distance.walk_ast(out.reborrow())?;
+        }
+        out.push_sql(" AS knn_distance FROM ");
+        self.table.walk_ast(out.reborrow())?;
+        out.push_sql(" ORDER BY ");
+        self.distance.walk_ast(out.reborrow())?;
+        out.push_sql(" LIMIT ");
+        if let Some(candidates) = &self.candidates {
+            out.push_bind_param::<BigInt, _>(candidates)?;
+            out.push_sql(") AS knn ORDER BY knn_distance LIMIT ");
+        }
+        out.push_bind_param::<BigInt, _>(&self.limit)?;
+        Ok(())
+    }
+}
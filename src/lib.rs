@@ -1,26 +1,49 @@
 #![doc = include_str!("../README.md")]
 
-#[cfg(feature = "schemars")]
+#[cfg(all(feature = "schemars", not(feature = "serde_geojson")))]
 #[macro_use]
 extern crate schemars;
 
+pub mod copy;
 pub mod errors;
 mod ewkb;
+mod ewkb_ref;
+mod flatgeometry;
 pub mod functions;
 pub mod functions_nullable;
-#[cfg(feature = "serde_geojson")]
+#[cfg(feature = "serde")]
 mod geojson;
+#[cfg(feature = "serde")]
+mod geojson_feature;
+#[cfg(all(
+    feature = "serde_geojson",
+    any(feature = "schemars", feature = "utoipa")
+))]
+mod geojson_schema;
+pub mod geojson_stream;
 mod geometrycollection;
 mod geometrycontainer;
+pub mod geopackage;
+pub mod knn;
 mod linestring;
 mod multiline;
 mod multipoint;
 mod multipolygon;
+#[cfg(all(feature = "utoipa", not(feature = "serde_geojson")))]
+mod openapi;
 pub mod operators;
 mod points;
 mod polygon;
+pub mod rtree;
+pub mod serde;
+mod spatialite;
 pub mod sql_types;
+pub mod sqlite_functions;
+mod srid;
+mod to_and_from_geo_types;
+mod to_and_from_postgres_types;
 mod to_and_from_sql_geography;
 mod to_and_from_sql_geometry;
+mod to_and_from_sqlx;
 pub mod types;
 mod write_to_read_from_sql;
@@ -1,7 +1,7 @@
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
 use crate::points::Dimension;
-use crate::write_to_read_from_sql::{ReadFromSql, WriteToSql};
+use crate::write_to_read_from_sql::{capacity, ReadFromSql, WriteToSql};
 use crate::{
     ewkb::{EwkbSerializable, GeometryType},
     types::{LineString, PointT},
@@ -95,10 +95,9 @@ where
         Endianness: byteorder::ByteOrder,
     {
         let len = reader.read_u32::<Endianness>()?;
-        let mut ls = LineString::with_capacity(header.srid, len as usize);
+        let mut ls = LineString::with_capacity(header.srid, capacity(len));
         for _i in 0..len {
-            ls.add_point(P::read_body::<Endianness, Reader>(header, reader)?)
-                .unwrap();
+            ls.add_point(P::read_body::<Endianness, Reader>(header, reader)?)?;
         }
         Ok(ls)
     }
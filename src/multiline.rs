@@ -1,6 +1,6 @@
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
-use crate::write_to_read_from_sql::{ReadFromSql, WriteToSql};
+use crate::write_to_read_from_sql::{capacity, read_part, ReadFromSql, WriteToSql};
 use crate::{
     ewkb::{EwkbSerializable, GeometryType},
     points::Dimension,
@@ -132,22 +132,12 @@ where
         Reader: std::io::Read,
         Endianness: byteorder::ByteOrder,
     {
-        let lines_n = reader.read_u32::<Endianness>().unwrap();
-        println!("read lines_n: {:?}", lines_n);
-        let mut multiline = MultiLineString::with_capacity(header.srid, lines_n as usize);
+        let lines_n = reader.read_u32::<Endianness>()?;
+        let mut multiline = MultiLineString::with_capacity(header.srid, capacity(lines_n));
         for _i in 0..lines_n {
-            println!("read line");
-            // skip 1 byte for byte order and 4 bytes for point type
-            reader.read_u8().unwrap();
-            reader.read_u32::<Endianness>().unwrap();
-            let points_n = reader.read_u32::<Endianness>().unwrap();
-            println!("read points_n: {:?}", points_n);
-            multiline.add_line_with_cap(points_n as usize);
-            for _p in 0..points_n {
-                let point = P::read_body::<Endianness, Reader>(header, reader)?;
-                println!("read point {:?}", point);
-                multiline.add_point(point).unwrap();
-            }
+            multiline
+                .lines
+                .push(read_part::<LineString<P>, Reader>(header, reader)?);
         }
         Ok(multiline)
     }
@@ -1,4 +1,4 @@
-use crate::write_to_read_from_sql::{ReadFromSql, WriteToSql};
+use crate::write_to_read_from_sql::{capacity, read_part, ReadFromSql, WriteToSql};
 use crate::{
     ewkb::{EwkbSerializable, GeometryType},
     points::Dimension,
@@ -76,12 +76,9 @@ where
         Endianness: byteorder::ByteOrder,
     {
         let len = reader.read_u32::<Endianness>()?;
-        let mut mp = MultiPoint::with_capacity(header.srid, len as usize);
+        let mut mp = MultiPoint::with_capacity(header.srid, capacity(len));
         for _i in 0..len {
-            // skip 1 byte for byte order and 4 bytes for point type
-            reader.read_u8()?;
-            reader.read_u32::<Endianness>()?;
-            mp.add_point(P::read_body::<Endianness, Reader>(header, reader)?);
+            mp.add_point(read_part::<P, Reader>(header, reader)?);
         }
         Ok(mp)
     }
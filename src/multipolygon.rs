@@ -1,4 +1,4 @@
-use crate::write_to_read_from_sql::{ReadFromSql, WriteToSql};
+use crate::write_to_read_from_sql::{capacity, read_part, ReadFromSql, WriteToSql};
 use crate::{
     ewkb::{EwkbSerializable, GeometryType},
     points::Dimension,
@@ -118,17 +118,12 @@ where
         Endianness: byteorder::ByteOrder,
     {
         let polygons_n = reader.read_u32::<Endianness>()?;
-        let mut polygon = MultiPolygon::with_capacity(header.srid, polygons_n as usize);
+        let mut polygon = MultiPolygon::with_capacity(header.srid, capacity(polygons_n));
 
         for _i in 0..polygons_n {
-            // skip 1 byte for byte order and 4 bytes for point type
-            reader.read_u8()?;
-            reader.read_u32::<Endianness>()?;
             polygon
                 .polygons
-                .push(Polygon::<P>::read_body::<Endianness, Reader>(
-                    header, reader,
-                )?);
+                .push(read_part::<Polygon<P>, Reader>(header, reader)?);
         }
         Ok(polygon)
     }
@@ -0,0 +1,98 @@
+//! OpenAPI schemas of `GeometryContainer` and `GeometryCollection` with plain serde. They refer to
+//! each other, so unlike the schemas derived for the other types, each of them adds both component
+//! schemas, whichever is used first.
+use std::borrow::Cow;
+
+use utoipa::__dev::ComposeSchema;
+use utoipa::openapi::schema::{
+    ArrayBuilder, KnownFormat, Object, ObjectBuilder, OneOfBuilder, Schema, SchemaFormat,
+    SchemaType, Type,
+};
+use utoipa::openapi::{Ref, RefOr};
+use utoipa::{PartialSchema, ToSchema};
+
+use crate::types::*;
+
+/// Externally tagged variant of a geometry container.
+fn variant(tag: &str, schema: impl Into<RefOr<Schema>>) -> Object {
+    ObjectBuilder::new()
+        .property(tag, schema)
+        .required(tag)
+        .build()
+}
+
+fn reference<T: ToSchema>(name: &str) -> Ref {
+    Ref::from_schema_name(format!("{}_{}", name, T::name()))
+}
+
+fn geometry_schemas<T: ToSchema>(schemas: &mut Vec<(String, RefOr<Schema>)>) {
+    schemas.push((
+        format!("GeometryContainer_{}", T::name()),
+        GeometryContainer::<T>::schema(),
+    ));
+    schemas.push((
+        format!("GeometryCollection_{}", T::name()),
+        GeometryCollection::<T>::schema(),
+    ));
+    schemas.push((T::name().into_owned(), T::schema()));
+    T::schemas(schemas);
+    LineString::<T>::schemas(schemas);
+    Polygon::<T>::schemas(schemas);
+    MultiPoint::<T>::schemas(schemas);
+    MultiLineString::<T>::schemas(schemas);
+    MultiPolygon::<T>::schemas(schemas);
+}
+
+impl<T: ToSchema> ComposeSchema for GeometryContainer<T> {
+    fn compose(_: Vec<RefOr<Schema>>) -> RefOr<Schema> {
+        OneOfBuilder::new()
+            .item(variant("Point", T::schema()))
+            .item(variant("LineString", LineString::<T>::schema()))
+            .item(variant("Polygon", Polygon::<T>::schema()))
+            .item(variant("MultiPoint", MultiPoint::<T>::schema()))
+            .item(variant("MultiLineString", MultiLineString::<T>::schema()))
+            .item(variant("MultiPolygon", MultiPolygon::<T>::schema()))
+            .item(variant(
+                "GeometryCollection",
+                reference::<T>("GeometryCollection"),
+            ))
+            .into()
+    }
+}
+
+impl<T: ToSchema> ToSchema for GeometryContainer<T> {
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("GeometryContainer")
+    }
+
+    fn schemas(schemas: &mut Vec<(String, RefOr<Schema>)>) {
+        geometry_schemas::<T>(schemas);
+    }
+}
+
+impl<T: ToSchema> ComposeSchema for GeometryCollection<T> {
+    fn compose(_: Vec<RefOr<Schema>>) -> RefOr<Schema> {
+        let srid = ObjectBuilder::new()
+            .schema_type(SchemaType::Array(vec![Type::Integer, Type::Null]))
+            .format(Some(SchemaFormat::KnownFormat(KnownFormat::Int32)))
+            .minimum(Some(0));
+        ObjectBuilder::new()
+            .property(
+                "geometries",
+                ArrayBuilder::new().items(reference::<T>("GeometryContainer")),
+            )
+            .required("geometries")
+            .property("srid", srid)
+            .into()
+    }
+}
+
+impl<T: ToSchema> ToSchema for GeometryCollection<T> {
+    fn name() -> Cow<'static, str> {
+        Cow::Borrowed("GeometryCollection")
+    }
+
+    fn schemas(schemas: &mut Vec<(String, RefOr<Schema>)>) {
+        geometry_schemas::<T>(schemas);
+    }
+}
@@ -1,9 +1,9 @@
 #![cfg(feature = "diesel")]
 use diesel::expression::AsExpression;
-use diesel::sql_types::SqlType;
+use diesel::sql_types::is_nullable::{IsOneNullable, IsSqlTypeNullable, MaybeNullable};
+use diesel::sql_types::{Bool, MaybeNullableType, OneIsNullable, SqlType};
 use diesel::Expression;
 
-diesel::infix_operator!(BBIntersects2D, " && ");
 diesel::infix_operator!(BBOverlapsOrLeft, " &< ");
 diesel::infix_operator!(BBOverlapsOrBelow, " &<| ");
 diesel::infix_operator!(BBOverlapsOrRight, " &> ");
@@ -23,6 +23,94 @@ diesel::infix_operator!(Distance2BBs, " <#> ", diesel::sql_types::Double);
 diesel::infix_operator!(DistanceNdCentroidsBBs, " <<->> ", diesel::sql_types::Double);
 diesel::infix_operator!(DistanceNdBBs, " <<#>> ", diesel::sql_types::Double);
 
+/// The `&&` operator, created by [`intersects_2d`].
+///
+/// Unlike the other operators it isn't defined with `infix_operator!`, because SQLite has no such
+/// operator: there it renders as `MbrIntersects(a, b)`, which SQLite gets from
+/// `sqlite_functions::register_functions` with the `sqlite` feature.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    diesel::query_builder::QueryId,
+    diesel::sql_types::DieselNumericOps,
+    diesel::expression::ValidGrouping,
+)]
+#[cfg_attr(not(any(feature = "postgres", feature = "sqlite")), allow(dead_code))]
+pub struct BBIntersects2D<T, U> {
+    pub(crate) left: T,
+    pub(crate) right: U,
+}
+
+impl<T, U> BBIntersects2D<T, U> {
+    pub(crate) fn new(left: T, right: U) -> Self {
+        BBIntersects2D { left, right }
+    }
+}
+
+impl<T, U> Expression for BBIntersects2D<T, U>
+where
+    T: Expression,
+    U: Expression,
+    T::SqlType: SqlType,
+    U::SqlType: SqlType,
+    IsSqlTypeNullable<T::SqlType>: OneIsNullable<IsSqlTypeNullable<U::SqlType>>,
+    IsOneNullable<T::SqlType, U::SqlType>: MaybeNullableType<Bool>,
+{
+    type SqlType = MaybeNullable<IsOneNullable<T::SqlType, U::SqlType>, Bool>;
+}
+
+impl<T, U, QS> diesel::AppearsOnTable<QS> for BBIntersects2D<T, U>
+where
+    T: diesel::AppearsOnTable<QS>,
+    U: diesel::AppearsOnTable<QS>,
+    Self: Expression,
+{
+}
+
+impl<T, U, QS> diesel::SelectableExpression<QS> for BBIntersects2D<T, U>
+where
+    T: diesel::SelectableExpression<QS>,
+    U: diesel::SelectableExpression<QS>,
+    Self: diesel::AppearsOnTable<QS>,
+{
+}
+
+#[cfg(feature = "postgres")]
+impl<T, U> diesel::query_builder::QueryFragment<diesel::pg::Pg> for BBIntersects2D<T, U>
+where
+    T: diesel::query_builder::QueryFragment<diesel::pg::Pg>,
+    U: diesel::query_builder::QueryFragment<diesel::pg::Pg>,
+{
+    fn walk_ast<'b>(
+        &'b self,
+        mut out: diesel::query_builder::AstPass<'_, 'b, diesel::pg::Pg>,
+    ) -> diesel::QueryResult<()> {
+        self.left.walk_ast(out.reborrow())?;
+        out.push_sql(" && ");
+        self.right.walk_ast(out.reborrow())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<T, U> diesel::query_builder::QueryFragment<diesel::sqlite::Sqlite> for BBIntersects2D<T, U>
+where
+    T: diesel::query_builder::QueryFragment<diesel::sqlite::Sqlite>,
+    U: diesel::query_builder::QueryFragment<diesel::sqlite::Sqlite>,
+{
+    fn walk_ast<'b>(
+        &'b self,
+        mut out: diesel::query_builder::AstPass<'_, 'b, diesel::sqlite::Sqlite>,
+    ) -> diesel::QueryResult<()> {
+        out.push_sql("MbrIntersects(");
+        self.left.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        self.right.walk_ast(out.reborrow())?;
+        out.push_sql(")");
+        Ok(())
+    }
+}
+
 /// The @ operator returns TRUE if the bounding box of geometry A is completely contained by the bounding box of geometry B.
 pub fn contained_by<T, U>(left: T, right: U) -> BBContainedBy<T, U::Expression>
 where
@@ -11,8 +11,8 @@ pub struct Dimension;
 
 impl Dimension {
     pub const NONE: u32 = 0;
-    pub const Z: u32 = 0x80000000;
-    pub const M: u32 = 0x40000000;
+    pub const Z: u32 = crate::ewkb::EWKB_Z;
+    pub const M: u32 = crate::ewkb::EWKB_M;
     pub const ZM: u32 = Self::Z | Self::M;
 }
 
@@ -1,6 +1,6 @@
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 
-use crate::write_to_read_from_sql::{ReadFromSql, WriteToSql};
+use crate::write_to_read_from_sql::{capacity, ReadFromSql, WriteToSql};
 use crate::{
     ewkb::{EwkbSerializable, GeometryType},
     types::{PointT, Polygon},
@@ -140,14 +140,12 @@ where
         Endianness: byteorder::ByteOrder,
     {
         let rings_n = reader.read_u32::<Endianness>()?;
-        let mut polygon = Polygon::with_capacity(header.srid, rings_n as usize);
+        let mut polygon = Polygon::with_capacity(header.srid, capacity(rings_n));
         for _i in 0..rings_n {
             let points_n = reader.read_u32::<Endianness>()?;
-            polygon.add_ring_with_capacity(points_n as usize);
+            polygon.add_ring_with_capacity(capacity(points_n));
             for _p in 0..points_n {
-                polygon
-                    .add_point(P::read_body::<Endianness, Reader>(header, reader)?)
-                    .unwrap();
+                polygon.add_point(P::read_body::<Endianness, Reader>(header, reader)?)?;
             }
         }
         Ok(polygon)
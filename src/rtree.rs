@@ -0,0 +1,252 @@
+//! R*Tree spatial index for geometry columns with the SQLite backend.
+//!
+//! [`RTreeIndex::create`] creates the `rtree_<table>_<column>` R*Tree virtual table, which holds
+//! the bounding box of the geometry of every row keyed by its `rowid`, and the triggers which keep
+//! it in sync. The triggers compute the bounding box with `ST_XMin` and co, so
+//! [`register_functions`](crate::sqlite_functions::register_functions) has to be called on every
+//! connection writing to the table. [`RTreeIndex::intersects_2d`] filters through the index.
+//! ```
+//! #[macro_use] extern crate diesel;
+//! use diesel::prelude::*;
+//! use postgis_diesel::rtree::RTreeIndex;
+//! use postgis_diesel::sqlite_functions::register_functions;
+//! use postgis_diesel::types::{Point, Polygon};
+//! table! {
+//!     use postgis_diesel::sql_types::*;
+//!     use diesel::sql_types::*;
+//!     places (id) {
+//!         id -> Integer,
+//!         location -> Geometry,
+//!     }
+//! }
+//! const PLACES_LOCATION: RTreeIndex = RTreeIndex::new("places", "location");
+//! # fn main() {
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//! register_functions(&mut conn).unwrap();
+//! diesel::sql_query("CREATE TABLE places (id INTEGER PRIMARY KEY, location BLOB NOT NULL)")
+//!     .execute(&mut conn)
+//!     .unwrap();
+//! PLACES_LOCATION.create(&mut conn).unwrap();
+//! diesel::insert_into(places::table)
+//!     .values(places::location.eq(Point::new(1.0, 1.0, None)))
+//!     .execute(&mut conn)
+//!     .unwrap();
+//! let mut area = Polygon::new(None);
+//! area.add_points([
+//!     Point::new(0.0, 0.0, None),
+//!     Point::new(2.0, 0.0, None),
+//!     Point::new(2.0, 2.0, None),
+//!     Point::new(0.0, 0.0, None),
+//! ])
+//! .unwrap();
+//! let found: Vec<i32> = places::table
+//!     .select(places::id)
+//!     .filter(PLACES_LOCATION.intersects_2d(places::location, area))
+//!     .load(&mut conn)
+//!     .unwrap();
+//! assert_eq!(vec![1], found);
+//! # }
+//! ```
+#![cfg(feature = "sqlite")]
+
+use diesel::expression::AsExpression;
+use diesel::query_builder::{AstPass, QueryFragment};
+use diesel::sql_types::is_nullable::{IsOneNullable, IsSqlTypeNullable, MaybeNullable};
+use diesel::sql_types::{Bool, MaybeNullableType, OneIsNullable, SqlType};
+use diesel::sqlite::{Sqlite, SqliteConnection};
+use diesel::{Connection, Expression, QueryResult, RunQueryDsl};
+
+/// R*Tree index of the geometry column `column` of the table `table`, which has to have a `rowid`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RTreeIndex {
+    table: &'static str,
+    column: &'static str,
+}
+
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+impl RTreeIndex {
+    pub const fn new(table: &'static str, column: &'static str) -> Self {
+        RTreeIndex { table, column }
+    }
+
+    /// Name of the R*Tree virtual table, `rtree_<table>_<column>`.
+    pub fn name(&self) -> String {
+        format!("rtree_{}_{}", self.table, self.column)
+    }
+
+    /// Creates the R*Tree virtual table, fills it from the rows already in the table and creates the
+    /// triggers which update it on insert, update and delete.
+    pub fn create(&self, conn: &mut SqliteConnection) -> QueryResult<()> {
+        let rtree = quote(&self.name());
+        let table = quote(self.table);
+        let bbox = |row: &str| {
+            let column = format!("{}{}", row, quote(self.column));
+            (
+                format!(
+                    "ST_XMin({c}), ST_XMax({c}), ST_YMin({c}), ST_YMax({c})",
+                    c = column
+                ),
+                format!("{c} IS NOT NULL AND ST_XMin({c}) IS NOT NULL", c = column),
+            )
+        };
+        let (values, condition) = bbox("");
+        let (new_values, new_condition) = bbox("NEW.");
+        let statements = [
+            format!(
+                "CREATE VIRTUAL TABLE {} USING rtree(id, minx, maxx, miny, maxy)",
+                rtree
+            ),
+            format!(
+                "INSERT INTO {} SELECT rowid, {} FROM {} WHERE {}",
+                rtree, values, table, condition
+            ),
+            format!(
+                "CREATE TRIGGER {} AFTER INSERT ON {} WHEN {} BEGIN \
+                 INSERT OR REPLACE INTO {} VALUES (NEW.rowid, {}); END",
+                quote(&format!("{}_insert", self.name())),
+                table,
+                new_condition,
+                rtree,
+                new_values
+            ),
+            format!(
+                "CREATE TRIGGER {} AFTER UPDATE ON {} BEGIN \
+                 DELETE FROM {} WHERE id = OLD.rowid; \
+                 INSERT OR REPLACE INTO {} SELECT NEW.rowid, {} WHERE {}; END",
+                quote(&format!("{}_update", self.name())),
+                table,
+                rtree,
+                rtree,
+                new_values,
+                new_condition
+            ),
+            format!(
+                "CREATE TRIGGER {} AFTER DELETE ON {} BEGIN \
+                 DELETE FROM {} WHERE id = OLD.rowid; END",
+                quote(&format!("{}_delete", self.name())),
+                table,
+                rtree
+            ),
+        ];
+        conn.transaction(|conn| {
+            for statement in statements {
+                diesel::sql_query(statement).execute(conn)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Drops the triggers and the R*Tree virtual table.
+    pub fn drop(&self, conn: &mut SqliteConnection) -> QueryResult<()> {
+        conn.transaction(|conn| {
+            for trigger in ["insert", "update", "delete"] {
+                diesel::sql_query(format!(
+                    "DROP TRIGGER IF EXISTS {}",
+                    quote(&format!("{}_{}", self.name(), trigger))
+                ))
+                .execute(conn)?;
+            }
+            diesel::sql_query(format!("DROP TABLE IF EXISTS {}", quote(&self.name())))
+                .execute(conn)?;
+            Ok(())
+        })
+    }
+
+    /// Same as [`intersects_2d`](crate::operators::intersects_2d), but only checks the rows whose
+    /// bounding box is found in the R*Tree. `left` has to be the indexed column.
+    pub fn intersects_2d<T, U>(&self, left: T, right: U) -> RTreeIntersects2D<T, U::Expression>
+    where
+        T: Expression,
+        <T as diesel::Expression>::SqlType: SqlType,
+        U: AsExpression<T::SqlType>,
+    {
+        RTreeIntersects2D {
+            index: *self,
+            left,
+            right: right.as_expression(),
+        }
+    }
+}
+
+/// Expression created by [`RTreeIndex::intersects_2d`].
+#[derive(Debug, Clone, Copy)]
+pub struct RTreeIntersects2D<T, U> {
+    index: RTreeIndex,
+    left: T,
+    right: U,
+}
+
+impl<T, U> diesel::query_builder::QueryId for RTreeIntersects2D<T, U> {
+    type QueryId = ();
+
+    // The names of the index are not part of the type.
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<T, U, GB> diesel::expression::ValidGrouping<GB> for RTreeIntersects2D<T, U>
+where
+    (T, U): diesel::expression::ValidGrouping<GB>,
+{
+    type IsAggregate = <(T, U) as diesel::expression::ValidGrouping<GB>>::IsAggregate;
+}
+
+impl<T, U> Expression for RTreeIntersects2D<T, U>
+where
+    T: Expression,
+    U: Expression,
+    T::SqlType: SqlType,
+    U::SqlType: SqlType,
+    IsSqlTypeNullable<T::SqlType>: OneIsNullable<IsSqlTypeNullable<U::SqlType>>,
+    IsOneNullable<T::SqlType, U::SqlType>: MaybeNullableType<Bool>,
+{
+    type SqlType = MaybeNullable<IsOneNullable<T::SqlType, U::SqlType>, Bool>;
+}
+
+impl<T, U, QS> diesel::AppearsOnTable<QS> for RTreeIntersects2D<T, U>
+where
+    T: diesel::AppearsOnTable<QS>,
+    U: diesel::AppearsOnTable<QS>,
+    Self: Expression,
+{
+}
+
+impl<T, U, QS> diesel::SelectableExpression<QS> for RTreeIntersects2D<T, U>
+where
+    T: diesel::SelectableExpression<QS>,
+    U: diesel::SelectableExpression<QS>,
+    Self: diesel::AppearsOnTable<QS>,
+{
+}
+
+impl<T, U> QueryFragment<Sqlite> for RTreeIntersects2D<T, U>
+where
+    T: QueryFragment<Sqlite>,
+    U: QueryFragment<Sqlite>,
+{
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, Sqlite>) -> QueryResult<()> {
+        out.push_sql("(");
+        out.push_identifier(self.index.table)?;
+        out.push_sql(".rowid IN (SELECT id FROM ");
+        out.push_identifier(&self.index.name())?;
+        for (bound, function) in [
+            (" WHERE minx <= ", "ST_XMax("),
+            (" AND maxx >= ", "ST_XMin("),
+            (" AND miny <= ", "ST_YMax("),
+            (" AND maxy >= ", "ST_YMin("),
+        ] {
+            out.push_sql(bound);
+            out.push_sql(function);
+            self.right.walk_ast(out.reborrow())?;
+            out.push_sql(")");
+        }
+        out.push_sql(") AND MbrIntersects(");
+        self.left.walk_ast(out.reborrow())?;
+        out.push_sql(", ");
+        self.right.walk_ast(out.reborrow())?;
+        out.push_sql("))");
+        Ok(())
+    }
+}
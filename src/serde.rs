@@ -0,0 +1,15 @@
+//! Plain and GeoJSON serde representations of the geometry types, side by side.
+//!
+//! The `Serialize` and `Deserialize` impls of the geometry types write GeoJSON with the
+//! `serde_geojson` feature, and plain structs like `{"x":1.0,"y":2.0,"srid":4326}` with the
+//! `serde` feature only. [`plain`] and [`geojson`] choose one of them whatever the features, for
+//! a value with the [`Plain`](plain::Plain) and [`GeoJson`](geojson::GeoJson) wrappers, or for a
+//! field with `#[serde(with = "postgis_diesel::serde::plain")]` and
+//! `#[serde(with = "postgis_diesel::serde::geojson")]`.
+//!
+//! Both only need the `serde` feature, `serde_geojson` only changes the own impls, which every
+//! crate of a build shares: libraries should use these adapters instead of enabling it.
+#![cfg(feature = "serde")]
+
+pub mod geojson;
+pub mod plain;
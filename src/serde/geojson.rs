@@ -0,0 +1,293 @@
+//! GeoJSON representation of the geometry types, the one of their own impls with the
+//! `serde_geojson` feature, next to the [`plain`](super::plain) one. It is available with the
+//! `serde` feature only, without changing the own impls.
+//! ```
+//! use postgis_diesel::serde::geojson::GeoJson;
+//! use postgis_diesel::types::Point;
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Station {
+//!     #[serde(with = "postgis_diesel::serde::geojson")]
+//!     location: Point,
+//!     #[serde(with = "postgis_diesel::serde::plain")]
+//!     entrance: Point,
+//! }
+//! let station = Station {
+//!     location: Point::new(7.44, 46.95, Some(4326)),
+//!     entrance: Point::new(7.45, 46.95, Some(4326)),
+//! };
+//! let json = serde_json::to_string(&station).unwrap();
+//! assert_eq!(
+//!     r#"{"location":{"type":"Point","coordinates":[7.44,46.95]},"entrance":{"x":7.45,"y":46.95,"srid":4326}}"#,
+//!     json
+//! );
+//! let point: GeoJson<Point> =
+//!     serde_json::from_str(r#"{"type":"Point","coordinates":[7.44,46.95]}"#).unwrap();
+//! assert_eq!(Point::new(7.44, 46.95, Some(4326)), point.0);
+//! ```
+
+use std::marker::PhantomData;
+
+use serde::de::DeserializeSeed;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::types::*;
+
+/// Value written as GeoJSON.
+pub trait GeoJsonSerialize {
+    fn serialize_geojson<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+}
+
+/// Value read from GeoJSON.
+pub trait GeoJsonDeserialize: Sized {
+    fn deserialize_geojson<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+}
+
+/// Wrapper serializing and deserializing a geometry, a feature or a feature collection as
+/// GeoJSON.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GeoJson<T>(pub T);
+
+impl<T> GeoJson<T> {
+    /// Wraps `value` to serialize it as GeoJSON with `options`, whatever the options of the
+    /// thread, e.g. when it is a field of a struct serialized by a framework. With a
+    /// `PhantomData<T>` value, it deserializes a `T` with `options` as a
+    /// [`DeserializeSeed`].
+    /// ```
+    /// use std::marker::PhantomData;
+    /// use serde::de::DeserializeSeed;
+    /// use postgis_diesel::serde::geojson::GeoJson;
+    /// use postgis_diesel::types::{GeoJsonOptions, MeasurePolicy, PointM};
+    /// let options = GeoJsonOptions {
+    ///     measures: MeasurePolicy::Properties,
+    ///     ..Default::default()
+    /// };
+    /// let point = PointM::new(7.4, 46.9, 1700000000.0, Some(4326));
+    /// let json = serde_json::to_string(&GeoJson::with_options(&point, options)).unwrap();
+    /// assert_eq!(
+    ///     r#"{"type":"Point","coordinates":[7.4,46.9],"properties":{"m":[1700000000.0]}}"#,
+    ///     json
+    /// );
+    /// let mut deserializer = serde_json::Deserializer::from_str(&json);
+    /// let from_json = GeoJson::with_options(PhantomData::<PointM>, options)
+    ///     .deserialize(&mut deserializer)
+    ///     .unwrap();
+    /// assert_eq!(point, from_json);
+    /// ```
+    pub fn with_options(value: T, options: GeoJsonOptions) -> WithOptions<T> {
+        WithOptions { value, options }
+    }
+}
+
+/// Value serialized or deserialized as GeoJSON with its own options, created by
+/// [`GeoJson::with_options`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct WithOptions<T> {
+    pub value: T,
+    pub options: GeoJsonOptions,
+}
+
+impl<T: GeoJsonSerialize> Serialize for WithOptions<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        with_geojson_options(self.options, || self.value.serialize_geojson(serializer))
+    }
+}
+
+impl<'de, T: GeoJsonDeserialize> DeserializeSeed<'de> for WithOptions<PhantomData<T>> {
+    type Value = T;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<T, D::Error> {
+        with_geojson_options(self.options, || T::deserialize_geojson(deserializer))
+    }
+}
+
+impl<T: GeoJsonSerialize> Serialize for GeoJson<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_geojson(serializer)
+    }
+}
+
+impl<'de, T: GeoJsonDeserialize> Deserialize<'de> for GeoJson<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize_geojson(deserializer).map(GeoJson)
+    }
+}
+
+/// Serializes `value` as GeoJSON, for `#[serde(with = "...")]`.
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: GeoJsonSerialize + ?Sized,
+    S: Serializer,
+{
+    value.serialize_geojson(serializer)
+}
+
+/// Deserializes a value from GeoJSON, for `#[serde(with = "...")]`.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: GeoJsonDeserialize,
+    D: Deserializer<'de>,
+{
+    T::deserialize_geojson(deserializer)
+}
+
+impl<T: GeoJsonSerialize + ?Sized> GeoJsonSerialize for &T {
+    fn serialize_geojson<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize_geojson(serializer)
+    }
+}
+
+impl<T: GeoJsonSerialize> GeoJsonSerialize for [T] {
+    fn serialize_geojson<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(GeoJson))
+    }
+}
+
+impl<T: GeoJsonSerialize> GeoJsonSerialize for Vec<T> {
+    fn serialize_geojson<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_slice().serialize_geojson(serializer)
+    }
+}
+
+impl<T: GeoJsonDeserialize> GeoJsonDeserialize for Vec<T> {
+    fn deserialize_geojson<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = Vec::<GeoJson<T>>::deserialize(deserializer)?;
+        Ok(values.into_iter().map(|value| value.0).collect())
+    }
+}
+
+impl<T: GeoJsonSerialize> GeoJsonSerialize for Option<T> {
+    fn serialize_geojson<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().map(GeoJson).serialize(serializer)
+    }
+}
+
+impl<T: GeoJsonDeserialize> GeoJsonDeserialize for Option<T> {
+    fn deserialize_geojson<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Option::<GeoJson<T>>::deserialize(deserializer)?;
+        Ok(value.map(|value| value.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serde::plain::Plain;
+
+    #[test]
+    fn test_geojson_and_plain() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Track {
+            #[serde(with = "crate::serde::geojson")]
+            geojson: LineString<PointZ>,
+            #[serde(with = "crate::serde::plain")]
+            plain: LineString<PointZ>,
+            #[serde(with = "crate::serde::geojson")]
+            start: Option<PointZ>,
+        }
+
+        let mut line = LineString::new(Some(4326));
+        line.add_point(PointZ::new(1.0, 2.0, 3.0, Some(4326)))
+            .unwrap();
+        line.add_point(PointZ::new(4.0, 5.0, 6.0, Some(4326)))
+            .unwrap();
+        let track = Track {
+            geojson: line.clone(),
+            plain: line,
+            start: None,
+        };
+        let json = serde_json::to_value(&track).unwrap();
+        assert_eq!(
+            serde_json::json!({"type": "LineString", "coordinates": [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]}),
+            json["geojson"]
+        );
+        assert_eq!(
+            serde_json::json!({"points": [
+                {"x": 1.0, "y": 2.0, "z": 3.0, "srid": 4326},
+                {"x": 4.0, "y": 5.0, "z": 6.0, "srid": 4326}
+            ], "srid": 4326}),
+            json["plain"]
+        );
+        assert_eq!(serde_json::Value::Null, json["start"]);
+        assert_eq!(track, serde_json::from_value(json).unwrap());
+    }
+
+    #[test]
+    fn test_geojson_wrapper() {
+        let points = vec![Point::new(1.0, 2.0, Some(4326))];
+        let json = serde_json::to_string(&GeoJson(&points)).unwrap();
+        assert_eq!(r#"[{"type":"Point","coordinates":[1.0,2.0]}]"#, json);
+        assert_eq!(
+            r#"[{"x":1.0,"y":2.0,"srid":4326}]"#,
+            serde_json::to_string(&Plain(&points)).unwrap()
+        );
+        let from_json: GeoJson<Vec<Point>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(points, from_json.0);
+    }
+
+    /// GeoJSON doesn't need the `serde_geojson` feature, which only changes the own impls.
+    #[test]
+    fn test_geojson_whatever_the_own_impls() {
+        let mut line = LineString::new(Some(4326));
+        line.add_point(PointM::new(1.0, 2.0, 3.0, Some(4326)))
+            .unwrap();
+        let feature = Feature {
+            id: Some(FeatureId::from(1)),
+            bbox: None,
+            geometry: Some(GeometryContainer::GeometryCollection(GeometryCollection {
+                geometries: vec![GeometryContainer::LineString(line)],
+                srid: Some(4326),
+            })),
+            properties: None::<()>,
+            foreign_members: Default::default(),
+        };
+        let expected_json = r#"{"type":"Feature","id":1,"geometry":{"type":"GeometryCollection","geometries":[{"type":"LineString","coordinates":[[1.0,2.0]],"properties":{"m":[3.0]}}]},"properties":null}"#;
+        // Feature has no plain representation, its own impls are GeoJSON with the `serde` feature
+        assert_eq!(expected_json, serde_json::to_string(&feature).unwrap());
+        assert_eq!(
+            expected_json,
+            serde_json::to_string(&GeoJson(&feature)).unwrap()
+        );
+        let from_json: Feature<PointM, ()> = serde_json::from_str(expected_json).unwrap();
+        assert_eq!(feature, from_json);
+
+        let geometry = feature.geometry.unwrap();
+        let json = serde_json::to_string(&GeoJson(&geometry)).unwrap();
+        #[cfg(feature = "serde_geojson")]
+        assert_eq!(json, serde_json::to_string(&geometry).unwrap());
+        #[cfg(not(feature = "serde_geojson"))]
+        assert_eq!(
+            serde_json::to_string(&Plain(&geometry)).unwrap(),
+            serde_json::to_string(&geometry).unwrap()
+        );
+        let from_json: GeoJson<GeometryContainer<PointM>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(geometry, from_json.0);
+        assert!(serde_json::from_str::<GeoJson<GeometryContainer<PointM>>>(
+            r#"{"type":"Circle","coordinates":[1.0,2.0]}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_geojson_with_options() {
+        let options = GeoJsonOptions {
+            srid: SridPolicy::AllowAny { crs: true },
+            ..Default::default()
+        };
+        let point = Point::new(2600000.0, 1200000.0, Some(2056));
+        // the options of the thread only apply to values without their own
+        assert!(serde_json::to_string(&GeoJson(&point)).is_err());
+        let json = serde_json::to_string(&vec![GeoJson::with_options(&point, options)]).unwrap();
+        assert_eq!(
+            r#"[{"type":"Point","coordinates":[2600000.0,1200000.0],"crs":{"type":"name","properties":{"name":"urn:ogc:def:crs:EPSG::2056"}}}]"#,
+            json
+        );
+        let json = &json[1..json.len() - 1];
+        assert!(serde_json::from_str::<GeoJson<Point>>(json).is_err());
+        let mut deserializer = serde_json::Deserializer::from_str(json);
+        let from_json = GeoJson::with_options(PhantomData::<Point>, options)
+            .deserialize(&mut deserializer)
+            .unwrap();
+        assert_eq!(point, from_json);
+        assert_eq!(GeoJsonOptions::default(), crate::geojson::options());
+    }
+}
@@ -0,0 +1,351 @@
+//! Plain serde representation of the geometry types, the structs with their fields, which is the
+//! one of their own impls without the `serde_geojson` feature.
+//! ```
+//! use postgis_diesel::serde::plain::Plain;
+//! use postgis_diesel::types::Point;
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Station {
+//!     name: String,
+//!     #[serde(with = "postgis_diesel::serde::plain")]
+//!     location: Point,
+//! }
+//! let station = Station {
+//!     name: "Bern".to_string(),
+//!     location: Point::new(7.44, 46.95, Some(4326)),
+//! };
+//! let json = serde_json::to_string(&station).unwrap();
+//! assert_eq!(
+//!     r#"{"name":"Bern","location":{"x":7.44,"y":46.95,"srid":4326}}"#,
+//!     json
+//! );
+//! let point: Plain<Point> = serde_json::from_str(r#"{"x":7.44,"y":46.95}"#).unwrap();
+//! assert_eq!(Point::new(7.44, 46.95, None), point.0);
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::types::*;
+
+/// Value written in the plain representation.
+pub trait PlainSerialize {
+    fn serialize_plain<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+}
+
+/// Value read from the plain representation.
+pub trait PlainDeserialize: Sized {
+    fn deserialize_plain<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+}
+
+/// Wrapper serializing and deserializing a geometry in the plain representation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Plain<T>(pub T);
+
+impl<T: PlainSerialize> Serialize for Plain<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize_plain(serializer)
+    }
+}
+
+impl<'de, T: PlainDeserialize> Deserialize<'de> for Plain<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize_plain(deserializer).map(Plain)
+    }
+}
+
+/// Serializes `value` in the plain representation, for `#[serde(with = "...")]`.
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: PlainSerialize + ?Sized,
+    S: Serializer,
+{
+    value.serialize_plain(serializer)
+}
+
+/// Deserializes a value from the plain representation, for `#[serde(with = "...")]`.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: PlainDeserialize,
+    D: Deserializer<'de>,
+{
+    T::deserialize_plain(deserializer)
+}
+
+impl<T: PlainSerialize + ?Sized> PlainSerialize for &T {
+    fn serialize_plain<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (**self).serialize_plain(serializer)
+    }
+}
+
+impl<T: PlainSerialize> PlainSerialize for [T] {
+    fn serialize_plain<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(Plain))
+    }
+}
+
+impl<T: PlainSerialize> PlainSerialize for Vec<T> {
+    fn serialize_plain<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_slice().serialize_plain(serializer)
+    }
+}
+
+impl<T: PlainDeserialize> PlainDeserialize for Vec<T> {
+    fn deserialize_plain<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let values = Vec::<Plain<T>>::deserialize(deserializer)?;
+        Ok(values.into_iter().map(|value| value.0).collect())
+    }
+}
+
+impl<T: PlainSerialize> PlainSerialize for Option<T> {
+    fn serialize_plain<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.as_ref().map(Plain).serialize(serializer)
+    }
+}
+
+impl<T: PlainDeserialize> PlainDeserialize for Option<T> {
+    fn deserialize_plain<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Option::<Plain<T>>::deserialize(deserializer)?;
+        Ok(value.map(|value| value.0))
+    }
+}
+
+macro_rules! impl_plain_point {
+    ($point:ident, $name:literal, $($field:ident),+) => {
+        impl PlainSerialize for $point {
+            fn serialize_plain<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                #[derive(Serialize)]
+                #[serde(rename = $name)]
+                struct Repr {
+                    $($field: f64,)+
+                    #[serde(skip_serializing_if = "Option::is_none")]
+                    srid: Option<u32>,
+                }
+
+                Repr {
+                    $($field: self.$field,)+
+                    srid: self.srid,
+                }
+                .serialize(serializer)
+            }
+        }
+
+        impl PlainDeserialize for $point {
+            fn deserialize_plain<'de, D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Self, D::Error> {
+                #[derive(Deserialize)]
+                #[serde(rename = $name)]
+                struct Repr {
+                    $($field: f64,)+
+                    srid: Option<u32>,
+                }
+
+                let repr = Repr::deserialize(deserializer)?;
+                Ok($point {
+                    $($field: repr.$field,)+
+                    srid: repr.srid,
+                })
+            }
+        }
+    };
+}
+
+impl_plain_point!(Point, "Point", x, y);
+impl_plain_point!(PointZ, "PointZ", x, y, z);
+impl_plain_point!(PointM, "PointM", x, y, m);
+impl_plain_point!(PointZM, "PointZM", x, y, z, m);
+
+macro_rules! impl_plain_geometry {
+    ($geometry:ident, $name:literal, $field:ident: $field_type:ty) => {
+        impl<T: PlainSerialize> PlainSerialize for $geometry<T> {
+            fn serialize_plain<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                #[derive(Serialize)]
+                #[serde(rename = $name, bound(serialize = "T: PlainSerialize"))]
+                struct Repr<'a, T> {
+                    $field: Plain<&'a $field_type>,
+                    #[serde(skip_serializing_if = "Option::is_none")]
+                    srid: Option<u32>,
+                }
+
+                Repr {
+                    $field: Plain(&self.$field),
+                    srid: self.srid,
+                }
+                .serialize(serializer)
+            }
+        }
+
+        impl<T: PlainDeserialize> PlainDeserialize for $geometry<T> {
+            fn deserialize_plain<'de, D: Deserializer<'de>>(
+                deserializer: D,
+            ) -> Result<Self, D::Error> {
+                #[derive(Deserialize)]
+                #[serde(rename = $name, bound(deserialize = "T: PlainDeserialize"))]
+                struct Repr<T> {
+                    $field: Plain<$field_type>,
+                    srid: Option<u32>,
+                }
+
+                let repr = Repr::<T>::deserialize(deserializer)?;
+                Ok($geometry {
+                    $field: repr.$field.0,
+                    srid: repr.srid,
+                })
+            }
+        }
+    };
+}
+
+impl_plain_geometry!(MultiPoint, "MultiPoint", points: Vec<T>);
+impl_plain_geometry!(LineString, "LineString", points: Vec<T>);
+impl_plain_geometry!(MultiLineString, "MultiLineString", lines: Vec<LineString<T>>);
+impl_plain_geometry!(Polygon, "Polygon", rings: Vec<Vec<T>>);
+impl_plain_geometry!(MultiPolygon, "MultiPolygon", polygons: Vec<Polygon<T>>);
+impl_plain_geometry!(
+    GeometryCollection,
+    "GeometryCollection",
+    geometries: Vec<GeometryContainer<T>>
+);
+
+impl<T: PlainSerialize> PlainSerialize for GeometryContainer<T> {
+    fn serialize_plain<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        #[serde(rename = "GeometryContainer", bound(serialize = "T: PlainSerialize"))]
+        enum Repr<'a, T> {
+            Point(Plain<&'a T>),
+            LineString(Plain<&'a LineString<T>>),
+            Polygon(Plain<&'a Polygon<T>>),
+            MultiPoint(Plain<&'a MultiPoint<T>>),
+            MultiLineString(Plain<&'a MultiLineString<T>>),
+            MultiPolygon(Plain<&'a MultiPolygon<T>>),
+            GeometryCollection(Plain<&'a GeometryCollection<T>>),
+        }
+
+        match self {
+            GeometryContainer::Point(g) => Repr::Point(Plain(g)),
+            GeometryContainer::LineString(g) => Repr::LineString(Plain(g)),
+            GeometryContainer::Polygon(g) => Repr::Polygon(Plain(g)),
+            GeometryContainer::MultiPoint(g) => Repr::MultiPoint(Plain(g)),
+            GeometryContainer::MultiLineString(g) => Repr::MultiLineString(Plain(g)),
+            GeometryContainer::MultiPolygon(g) => Repr::MultiPolygon(Plain(g)),
+            GeometryContainer::GeometryCollection(g) => Repr::GeometryCollection(Plain(g)),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<T: PlainDeserialize> PlainDeserialize for GeometryContainer<T> {
+    fn deserialize_plain<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(
+            rename = "GeometryContainer",
+            bound(deserialize = "T: PlainDeserialize")
+        )]
+        enum Repr<T> {
+            Point(Plain<T>),
+            LineString(Plain<LineString<T>>),
+            Polygon(Plain<Polygon<T>>),
+            MultiPoint(Plain<MultiPoint<T>>),
+            MultiLineString(Plain<MultiLineString<T>>),
+            MultiPolygon(Plain<MultiPolygon<T>>),
+            GeometryCollection(Plain<GeometryCollection<T>>),
+        }
+
+        Ok(match Repr::<T>::deserialize(deserializer)? {
+            Repr::Point(g) => GeometryContainer::Point(g.0),
+            Repr::LineString(g) => GeometryContainer::LineString(g.0),
+            Repr::Polygon(g) => GeometryContainer::Polygon(g.0),
+            Repr::MultiPoint(g) => GeometryContainer::MultiPoint(g.0),
+            Repr::MultiLineString(g) => GeometryContainer::MultiLineString(g.0),
+            Repr::MultiPolygon(g) => GeometryContainer::MultiPolygon(g.0),
+            Repr::GeometryCollection(g) => GeometryContainer::GeometryCollection(g.0),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon() -> Polygon<PointZ> {
+        let mut polygon = Polygon::new(Some(2056));
+        polygon
+            .add_points([
+                PointZ::new(0.0, 0.0, 1.0, Some(2056)),
+                PointZ::new(1.0, 0.0, 2.0, Some(2056)),
+                PointZ::new(0.0, 1.0, 3.0, Some(2056)),
+                PointZ::new(0.0, 0.0, 1.0, Some(2056)),
+            ])
+            .unwrap();
+        polygon
+    }
+
+    #[test]
+    fn test_plain_serde() {
+        let collection = GeometryCollection {
+            geometries: vec![
+                GeometryContainer::Point(PointZ::new(1.0, 2.0, 3.0, None)),
+                GeometryContainer::Polygon(polygon()),
+            ],
+            srid: Some(2056),
+        };
+        let expected_json = r#"{"geometries":[{"Point":{"x":1.0,"y":2.0,"z":3.0}},{"Polygon":{"rings":[[{"x":0.0,"y":0.0,"z":1.0,"srid":2056},{"x":1.0,"y":0.0,"z":2.0,"srid":2056},{"x":0.0,"y":1.0,"z":3.0,"srid":2056},{"x":0.0,"y":0.0,"z":1.0,"srid":2056}]],"srid":2056}}],"srid":2056}"#;
+        assert_eq!(
+            expected_json,
+            serde_json::to_string(&Plain(&collection)).unwrap()
+        );
+        let from_json: Plain<GeometryCollection<PointZ>> =
+            serde_json::from_str(expected_json).unwrap();
+        assert_eq!(collection, from_json.0);
+    }
+
+    #[test]
+    fn test_plain_with() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Parcel {
+            #[serde(with = "crate::serde::plain")]
+            area: Polygon<PointZ>,
+            #[serde(with = "crate::serde::plain")]
+            entrance: Option<PointM>,
+            #[serde(with = "crate::serde::plain")]
+            trees: Vec<Point>,
+        }
+
+        let parcel = Parcel {
+            area: polygon(),
+            entrance: Some(PointM::new(1.0, 2.0, 3.0, None)),
+            trees: vec![Point::new(0.5, 0.5, None)],
+        };
+        let json = serde_json::to_value(&parcel).unwrap();
+        assert_eq!(
+            serde_json::json!({"x": 1.0, "y": 2.0, "m": 3.0}),
+            json["entrance"]
+        );
+        assert_eq!(serde_json::json!([{"x": 0.5, "y": 0.5}]), json["trees"]);
+        assert_eq!(parcel, serde_json::from_value(json).unwrap());
+    }
+
+    /// The plain representation is the one of the derived impls.
+    #[cfg(not(feature = "serde_geojson"))]
+    #[test]
+    fn test_plain_is_derived_serde() {
+        let mut multi_line_string = MultiLineString::new(Some(4326));
+        multi_line_string
+            .add_points([
+                PointZM::new(1.0, 2.0, 3.0, 4.0, Some(4326)),
+                PointZM::new(5.0, 6.0, 7.0, 8.0, Some(4326)),
+            ])
+            .unwrap();
+        let geometries = vec![
+            GeometryContainer::MultiLineString(multi_line_string),
+            GeometryContainer::MultiPoint(MultiPoint {
+                points: vec![PointZM::new(1.0, 2.0, 3.0, 4.0, None)],
+                srid: None,
+            }),
+        ];
+        let derived = serde_json::to_string(&geometries).unwrap();
+        assert_eq!(derived, serde_json::to_string(&Plain(&geometries)).unwrap());
+        let from_json: Plain<Vec<GeometryContainer<PointZM>>> =
+            serde_json::from_str(&derived).unwrap();
+        assert_eq!(geometries, from_json.0);
+    }
+}
@@ -0,0 +1,311 @@
+//! Submodule converting between EWKB and the SpatiaLite BLOB geometry format.
+//!
+//! A SpatiaLite BLOB starts with `0x00`, the byte order, the SRID and the MBR followed by `0x7C`,
+//! then the class code and the WKB-like body, where the header of every nested geometry is the
+//! `0x69` marker and its class code, and ends with `0xFE`.
+#![cfg(feature = "sqlite")]
+
+use byteorder::{BigEndian, ByteOrder, LittleEndian, ReadBytesExt, WriteBytesExt};
+
+use crate::errors::DecodeErrorReason;
+use crate::ewkb::{BIG_ENDIAN, EWKB_M, EWKB_Z, LITTLE_ENDIAN, SRID};
+use crate::types::EwkbRef;
+use crate::write_to_read_from_sql::MAX_NESTING_DEPTH;
+
+const START: u8 = 0x00;
+const MBR_END: u8 = 0x7C;
+const ENTITY: u8 = 0x69;
+const END: u8 = 0xFE;
+const HEADER_LEN: usize = 39;
+
+fn invalid_data(reason: String) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, reason)
+}
+
+struct Mbr {
+    min_x: f64,
+    min_y: f64,
+    max_x: f64,
+    max_y: f64,
+}
+
+impl Mbr {
+    fn new() -> Self {
+        Mbr {
+            min_x: f64::INFINITY,
+            min_y: f64::INFINITY,
+            max_x: f64::NEG_INFINITY,
+            max_y: f64::NEG_INFINITY,
+        }
+    }
+
+    fn add(&mut self, x: f64, y: f64) {
+        self.min_x = self.min_x.min(x);
+        self.min_y = self.min_y.min(y);
+        self.max_x = self.max_x.max(x);
+        self.max_y = self.max_y.max(y);
+    }
+
+    fn write<W: std::io::Write>(&self, out: &mut W) -> Result<(), std::io::Error> {
+        let (min_x, min_y, max_x, max_y) = if self.min_x > self.max_x {
+            (0.0, 0.0, 0.0, 0.0)
+        } else {
+            (self.min_x, self.min_y, self.max_x, self.max_y)
+        };
+        for value in [min_x, min_y, max_x, max_y] {
+            out.write_f64::<LittleEndian>(value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Base type, EWKB geometry type and number of ordinates of each point for a SpatiaLite class code.
+fn split_class(class: u32) -> Result<(u32, u32, usize), std::io::Error> {
+    let base = class % 1000;
+    if !(1..=7).contains(&base) || class >= 4000 {
+        return Err(invalid_data(format!(
+            "Unsupported SpatiaLite geometry class {}",
+            class
+        )));
+    }
+    let (g_type, ordinates) = match class / 1000 {
+        0 => (base, 2),
+        1 => (base | EWKB_Z, 3),
+        2 => (base | EWKB_M, 3),
+        _ => (base | EWKB_Z | EWKB_M, 4),
+    };
+    Ok((base, g_type, ordinates))
+}
+
+/// Converts the EWKB of a geometry to the SpatiaLite BLOB format.
+pub fn ewkb_to_spatialite(ewkb: &[u8]) -> Result<Vec<u8>, std::io::Error> {
+    let ewkb = EwkbRef::new(ewkb);
+    let mut body = Vec::with_capacity(ewkb.as_bytes().len());
+    let mut mbr = Mbr::new();
+    // SpatiaLite class codes are the ISO WKB type codes, the geometry starts with its class code
+    // and its parts with the entity marker
+    ewkb.write_wkb(
+        &mut body,
+        &mut |out, header, depth| {
+            if depth > 0 {
+                out.write_u8(ENTITY)?;
+            }
+            out.write_u32::<LittleEndian>(header.iso_type())
+        },
+        &mut |coordinate| mbr.add(coordinate.x, coordinate.y),
+    )?;
+    let mut out = Vec::with_capacity(HEADER_LEN + body.len() + 1);
+    out.write_u8(START)?;
+    out.write_u8(LITTLE_ENDIAN)?;
+    out.write_u32::<LittleEndian>(ewkb.srid()?.unwrap_or(0))?;
+    mbr.write(&mut out)?;
+    out.write_u8(MBR_END)?;
+    out.extend_from_slice(&body);
+    out.write_u8(END)?;
+    Ok(out)
+}
+
+/// Converts a SpatiaLite BLOB to the little-endian EWKB of the geometry, SRID 0 is read as no SRID.
+pub fn spatialite_to_ewkb(blob: &[u8]) -> Result<Vec<u8>, std::io::Error> {
+    if blob.len() < HEADER_LEN + 1
+        || blob[0] != START
+        || (blob[1] != BIG_ENDIAN && blob[1] != LITTLE_ENDIAN)
+        || blob[HEADER_LEN - 1] != MBR_END
+        || blob[blob.len() - 1] != END
+    {
+        return Err(invalid_data("Not a SpatiaLite BLOB geometry".to_string()));
+    }
+    let mut out = Vec::with_capacity(blob.len());
+    let mut cursor = std::io::Cursor::new(&blob[2..blob.len() - 1]);
+    if blob[1] == BIG_ENDIAN {
+        read_spatialite_geometry::<BigEndian>(&mut cursor, &mut out)?;
+    } else {
+        read_spatialite_geometry::<LittleEndian>(&mut cursor, &mut out)?;
+    }
+    if cursor.position() as usize != cursor.get_ref().len() {
+        return Err(invalid_data(
+            "Unexpected bytes after SpatiaLite geometry".to_string(),
+        ));
+    }
+    Ok(out)
+}
+
+fn read_spatialite_geometry<T>(
+    cursor: &mut std::io::Cursor<&[u8]>,
+    out: &mut Vec<u8>,
+) -> Result<(), std::io::Error>
+where
+    T: ByteOrder,
+{
+    let srid = cursor.read_i32::<T>()?;
+    // skip the MBR and its end marker
+    cursor.set_position(cursor.position() + 33);
+    let class = cursor.read_u32::<T>()?;
+    let (_, g_type, _) = split_class(class)?;
+    out.write_u8(LITTLE_ENDIAN)?;
+    if srid > 0 {
+        out.write_u32::<LittleEndian>(g_type | SRID)?;
+        out.write_u32::<LittleEndian>(srid as u32)?;
+    } else {
+        out.write_u32::<LittleEndian>(g_type)?;
+    }
+    read_spatialite_body::<T>(cursor, out, class, 0)
+}
+
+/// Reads the body of a geometry of the class, nested `depth` geometries deep.
+fn read_spatialite_body<T>(
+    cursor: &mut std::io::Cursor<&[u8]>,
+    out: &mut Vec<u8>,
+    class: u32,
+    depth: usize,
+) -> Result<(), std::io::Error>
+where
+    T: ByteOrder,
+{
+    let (base, _, ordinates) = split_class(class)?;
+    let copy_points = |cursor: &mut std::io::Cursor<&[u8]>, out: &mut Vec<u8>, len: u32| {
+        for _ in 0..len as usize * ordinates {
+            out.write_f64::<LittleEndian>(cursor.read_f64::<T>()?)?;
+        }
+        Ok::<(), std::io::Error>(())
+    };
+    match base {
+        1 => copy_points(cursor, out, 1)?,
+        2 => {
+            let len = cursor.read_u32::<T>()?;
+            out.write_u32::<LittleEndian>(len)?;
+            copy_points(cursor, out, len)?;
+        }
+        3 => {
+            let rings = cursor.read_u32::<T>()?;
+            out.write_u32::<LittleEndian>(rings)?;
+            for _ in 0..rings {
+                let len = cursor.read_u32::<T>()?;
+                out.write_u32::<LittleEndian>(len)?;
+                copy_points(cursor, out, len)?;
+            }
+        }
+        _ => {
+            if depth >= MAX_NESTING_DEPTH {
+                return Err(DecodeErrorReason::TooDeeplyNested.into());
+            }
+            let geometries = cursor.read_u32::<T>()?;
+            out.write_u32::<LittleEndian>(geometries)?;
+            for _ in 0..geometries {
+                if cursor.read_u8()? != ENTITY {
+                    return Err(invalid_data(
+                        "Expected SpatiaLite entity marker".to_string(),
+                    ));
+                }
+                let class = cursor.read_u32::<T>()?;
+                let (_, g_type, _) = split_class(class)?;
+                out.write_u8(LITTLE_ENDIAN)?;
+                out.write_u32::<LittleEndian>(g_type)?;
+                read_spatialite_body::<T>(cursor, out, class, depth + 1)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{GeometryCollection, GeometryContainer, LineString, Point, PointZ, Polygon};
+    use crate::write_to_read_from_sql::{ReadFromSql, WriteToSql};
+
+    #[test]
+    fn test_point_spatialite_blob() {
+        let point = Point::new(1.0, 2.0, Some(4326));
+        let mut ewkb = Vec::new();
+        point.write_to_sql(true, &mut ewkb).unwrap();
+        let blob = ewkb_to_spatialite(&ewkb).unwrap();
+        let mut expected = vec![0x00, 0x01, 0xE6, 0x10, 0x00, 0x00];
+        for value in [1.0f64, 2.0, 1.0, 2.0] {
+            expected.extend_from_slice(&value.to_le_bytes());
+        }
+        expected.extend_from_slice(&[0x7C, 0x01, 0x00, 0x00, 0x00]);
+        expected.extend_from_slice(&1.0f64.to_le_bytes());
+        expected.extend_from_slice(&2.0f64.to_le_bytes());
+        expected.push(0xFE);
+        assert_eq!(expected, blob);
+        let decoded = Point::read_from_sql(&spatialite_to_ewkb(&blob).unwrap()).unwrap();
+        assert_eq!(point, decoded);
+    }
+
+    #[test]
+    fn test_geometry_collection_spatialite_blob() {
+        let mut line = LineString::new(None);
+        line.add_points([
+            PointZ::new(0.0, -1.0, 5.0, None),
+            PointZ::new(3.0, 4.0, 6.0, None),
+        ])
+        .unwrap();
+        let mut polygon = Polygon::new(None);
+        polygon
+            .add_points([
+                PointZ::new(0.0, 0.0, 1.0, None),
+                PointZ::new(2.0, 0.0, 1.0, None),
+                PointZ::new(2.0, 2.0, 1.0, None),
+                PointZ::new(0.0, 0.0, 1.0, None),
+            ])
+            .unwrap();
+        let mut collection = GeometryCollection::new(None);
+        collection
+            .geometries
+            .push(GeometryContainer::LineString(line));
+        collection
+            .geometries
+            .push(GeometryContainer::Polygon(polygon));
+        let mut ewkb = Vec::new();
+        collection.write_to_sql(true, &mut ewkb).unwrap();
+        let blob = ewkb_to_spatialite(&ewkb).unwrap();
+        assert_eq!(&0u32.to_le_bytes(), &blob[2..6]);
+        assert_eq!(&0.0f64.to_le_bytes(), &blob[6..14]);
+        assert_eq!(&(-1.0f64).to_le_bytes(), &blob[14..22]);
+        assert_eq!(&3.0f64.to_le_bytes(), &blob[22..30]);
+        assert_eq!(&4.0f64.to_le_bytes(), &blob[30..38]);
+        assert_eq!(&1007u32.to_le_bytes(), &blob[39..43]);
+        assert_eq!(ENTITY, blob[47]);
+        assert_eq!(&1002u32.to_le_bytes(), &blob[48..52]);
+        let decoded =
+            GeometryCollection::<PointZ>::read_from_sql(&spatialite_to_ewkb(&blob).unwrap())
+                .unwrap();
+        assert_eq!(collection, decoded);
+    }
+
+    #[test]
+    fn test_invalid_spatialite_blob() {
+        let point = Point::new(1.0, 2.0, None);
+        let mut ewkb = Vec::new();
+        point.write_to_sql(true, &mut ewkb).unwrap();
+        assert!(spatialite_to_ewkb(&ewkb).is_err());
+        let mut blob = ewkb_to_spatialite(&ewkb).unwrap();
+        let mut byte_order = blob.clone();
+        byte_order[1] = 0x02;
+        assert_eq!(
+            "Not a SpatiaLite BLOB geometry",
+            spatialite_to_ewkb(&byte_order).unwrap_err().to_string()
+        );
+        blob.pop();
+        assert!(spatialite_to_ewkb(&blob).is_err());
+    }
+
+    #[test]
+    fn test_nested_spatialite_blob() {
+        let mut blob = vec![START, LITTLE_ENDIAN, 0, 0, 0, 0];
+        blob.extend_from_slice(&[0; 32]);
+        blob.extend_from_slice(&[MBR_END, 7, 0, 0, 0, 1, 0, 0, 0]);
+        for _ in 0..200_000 {
+            blob.extend_from_slice(&[ENTITY, 7, 0, 0, 0, 1, 0, 0, 0]);
+        }
+        blob.push(END);
+        let error = spatialite_to_ewkb(&blob).unwrap_err();
+        assert_eq!(
+            Some(&DecodeErrorReason::TooDeeplyNested),
+            error
+                .get_ref()
+                .and_then(|inner| inner.downcast_ref::<DecodeErrorReason>())
+        );
+    }
+}
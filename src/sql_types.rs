@@ -18,6 +18,7 @@
 )]
 #[cfg_attr(feature = "postgres", diesel(postgres_type(name = "geometry")))]
 #[cfg_attr(feature = "sqlite", diesel(sqlite_type(name = "Binary")))]
+#[cfg_attr(feature = "mysql", diesel(mysql_type(name = "Blob")))]
 pub struct Geometry;
 
 /// SQL type which may be used in table definition.
@@ -41,6 +42,43 @@ pub struct Geometry;
 #[cfg_attr(feature = "sqlite", diesel(sqlite_type(name = "Binary")))]
 pub struct Geography;
 
+/// SQLite SQL type of geometries stored in the SpatiaLite BLOB format, as written by SpatiaLite,
+/// QGIS or ogr2ogr. Use [`Geometry`] for columns holding EWKB.
+/// ```
+///#[macro_use] extern crate diesel;
+///table! {
+///    use postgis_diesel::sql_types::*;
+///    use diesel::sql_types::*;
+///    spatialite_example (id) {
+///        id -> Int4,
+///        point -> Nullable<SpatiaLiteGeometry>,
+///        linestring -> SpatiaLiteGeometry,
+///    }
+///}
+/// ```
+#[cfg(feature = "sqlite")]
+#[derive(Clone, Copy, diesel::sql_types::SqlType, diesel::query_builder::QueryId)]
+#[diesel(sqlite_type(name = "Binary"))]
+pub struct SpatiaLiteGeometry;
+
+/// SQLite SQL type of geometries stored in the GeoPackage binary format, see
+/// [`geopackage`](crate::geopackage) for the metadata tables of a GeoPackage.
+/// ```
+///#[macro_use] extern crate diesel;
+///table! {
+///    use postgis_diesel::sql_types::*;
+///    use diesel::sql_types::*;
+///    geopackage_example (fid) {
+///        fid -> Int4,
+///        geom -> Nullable<GeoPackageGeometry>,
+///    }
+///}
+/// ```
+#[cfg(feature = "sqlite")]
+#[derive(Clone, Copy, diesel::sql_types::SqlType, diesel::query_builder::QueryId)]
+#[diesel(sqlite_type(name = "Binary"))]
+pub struct GeoPackageGeometry;
+
 #[cfg(feature = "diesel")]
 pub trait GeoType: diesel::sql_types::SingleValue {}
 
@@ -49,3 +87,203 @@ impl GeoType for Geometry {}
 
 #[cfg(feature = "diesel")]
 impl GeoType for Geography {}
+
+/// Geometry SQL type constrained to a subtype and a SRID, like `geometry(Point,4326)`.
+///
+/// Only the Rust type matching the kind may be written or read, and its SRID has to be `SRID`.
+/// Use [`untyped`](crate::functions::untyped) to pass such a column where `Geometry` is expected.
+/// ```
+///#[macro_use] extern crate diesel;
+///table! {
+///    use postgis_diesel::sql_types::*;
+///    use diesel::sql_types::*;
+///    typed_geometry_example (id) {
+///        id -> Int4,
+///        point -> TypedGeometry<PointKind, 4326>,
+///        linestring -> Nullable<TypedGeometry<LineStringKind<postgis_diesel::types::PointZ>, 4326>>,
+///    }
+///}
+/// ```
+#[derive(Clone, Copy)]
+pub struct TypedGeometry<K, const SRID: u32>(std::marker::PhantomData<K>);
+
+/// Kind of `TypedGeometry` holding [`Point`](crate::types::Point) or the point type `P`.
+#[derive(Clone, Copy)]
+pub struct PointKind<P = crate::types::Point>(std::marker::PhantomData<P>);
+
+/// Kind of `TypedGeometry` holding [`LineString<P>`](crate::types::LineString).
+#[derive(Clone, Copy)]
+pub struct LineStringKind<P = crate::types::Point>(std::marker::PhantomData<P>);
+
+/// Kind of `TypedGeometry` holding [`Polygon<P>`](crate::types::Polygon).
+#[derive(Clone, Copy)]
+pub struct PolygonKind<P = crate::types::Point>(std::marker::PhantomData<P>);
+
+/// Kind of `TypedGeometry` holding [`MultiPoint<P>`](crate::types::MultiPoint).
+#[derive(Clone, Copy)]
+pub struct MultiPointKind<P = crate::types::Point>(std::marker::PhantomData<P>);
+
+/// Kind of `TypedGeometry` holding [`MultiLineString<P>`](crate::types::MultiLineString).
+#[derive(Clone, Copy)]
+pub struct MultiLineStringKind<P = crate::types::Point>(std::marker::PhantomData<P>);
+
+/// Kind of `TypedGeometry` holding [`MultiPolygon<P>`](crate::types::MultiPolygon).
+#[derive(Clone, Copy)]
+pub struct MultiPolygonKind<P = crate::types::Point>(std::marker::PhantomData<P>);
+
+/// Kind of `TypedGeometry` holding [`GeometryCollection<P>`](crate::types::GeometryCollection).
+#[derive(Clone, Copy)]
+pub struct GeometryCollectionKind<P = crate::types::Point>(std::marker::PhantomData<P>);
+
+/// Kind of `TypedGeometry` holding any subtype as [`GeometryContainer<P>`](crate::types::GeometryContainer).
+#[derive(Clone, Copy)]
+pub struct GeometryKind<P = crate::types::Point>(std::marker::PhantomData<P>);
+
+/// Implemented by a `TypedGeometry` kind for the Rust type it accepts.
+pub trait TypedGeometryKind<T> {}
+
+impl<P: crate::types::PointT> TypedGeometryKind<P> for PointKind<P> {}
+impl<P: crate::types::PointT> TypedGeometryKind<crate::types::LineString<P>> for LineStringKind<P> {}
+impl<P: crate::types::PointT> TypedGeometryKind<crate::types::Polygon<P>> for PolygonKind<P> {}
+impl<P: crate::types::PointT> TypedGeometryKind<crate::types::MultiPoint<P>> for MultiPointKind<P> {}
+impl<P: crate::types::PointT> TypedGeometryKind<crate::types::MultiLineString<P>>
+    for MultiLineStringKind<P>
+{
+}
+impl<P: crate::types::PointT> TypedGeometryKind<crate::types::MultiPolygon<P>>
+    for MultiPolygonKind<P>
+{
+}
+impl<P: crate::types::PointT> TypedGeometryKind<crate::types::GeometryCollection<P>>
+    for GeometryCollectionKind<P>
+{
+}
+impl<P: crate::types::PointT> TypedGeometryKind<crate::types::GeometryContainer<P>>
+    for GeometryKind<P>
+{
+}
+
+#[cfg(feature = "diesel")]
+impl<K: 'static, const SRID: u32> diesel::sql_types::SqlType for TypedGeometry<K, SRID> {
+    type IsNull = diesel::sql_types::is_nullable::NotNull;
+}
+
+#[cfg(feature = "diesel")]
+impl<K: 'static, const SRID: u32> diesel::sql_types::SingleValue for TypedGeometry<K, SRID> {}
+
+#[cfg(feature = "diesel")]
+impl<K: 'static, const SRID: u32> diesel::query_builder::QueryId for TypedGeometry<K, SRID> {
+    type QueryId = Self;
+
+    const HAS_STATIC_QUERY_ID: bool = true;
+}
+
+#[cfg(feature = "postgres")]
+impl<K: 'static, const SRID: u32> diesel::sql_types::HasSqlType<TypedGeometry<K, SRID>>
+    for diesel::pg::Pg
+{
+    fn metadata(lookup: &mut Self::MetadataLookup) -> Self::TypeMetadata {
+        <Self as diesel::sql_types::HasSqlType<Geometry>>::metadata(lookup)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<K: 'static, const SRID: u32> diesel::sql_types::HasSqlType<TypedGeometry<K, SRID>>
+    for diesel::sqlite::Sqlite
+{
+    fn metadata(lookup: &mut Self::MetadataLookup) -> Self::TypeMetadata {
+        <Self as diesel::sql_types::HasSqlType<Geometry>>::metadata(lookup)
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl<K: 'static, const SRID: u32> diesel::sql_types::HasSqlType<TypedGeometry<K, SRID>>
+    for diesel::mysql::Mysql
+{
+    fn metadata(lookup: &mut Self::MetadataLookup) -> Self::TypeMetadata {
+        <Self as diesel::sql_types::HasSqlType<Geometry>>::metadata(lookup)
+    }
+}
+
+/// SQL types stored like `Geometry`, which may be used as `Geometry` without a cast.
+#[cfg(feature = "diesel")]
+pub trait UntypedGeometry {
+    /// `Geometry`, keeping the nullability of the source type.
+    type Untyped: diesel::expression::TypedExpressionType;
+}
+
+#[cfg(feature = "diesel")]
+impl<K: 'static, const SRID: u32> UntypedGeometry for TypedGeometry<K, SRID> {
+    type Untyped = Geometry;
+}
+
+#[cfg(feature = "diesel")]
+impl<K: 'static, const SRID: u32> UntypedGeometry
+    for diesel::sql_types::Nullable<TypedGeometry<K, SRID>>
+{
+    type Untyped = diesel::sql_types::Nullable<Geometry>;
+}
+
+/// Bind parameter of a geometry value with a `TypedGeometry` SQL type.
+#[cfg(feature = "diesel")]
+#[derive(Debug, Clone, Copy)]
+pub struct TypedBind<ST, T> {
+    value: T,
+    marker: std::marker::PhantomData<ST>,
+}
+
+#[cfg(feature = "diesel")]
+impl<ST, T> TypedBind<ST, T> {
+    pub(crate) fn new(value: T) -> Self {
+        TypedBind {
+            value,
+            marker: std::marker::PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl<ST, T> diesel::Expression for TypedBind<ST, T>
+where
+    ST: diesel::expression::TypedExpressionType,
+{
+    type SqlType = ST;
+}
+
+#[cfg(feature = "diesel")]
+impl<ST, T, DB> diesel::query_builder::QueryFragment<DB> for TypedBind<ST, T>
+where
+    DB: diesel::backend::Backend + diesel::sql_types::HasSqlType<ST>,
+    T: diesel::serialize::ToSql<ST, DB>,
+{
+    fn walk_ast<'b>(
+        &'b self,
+        mut out: diesel::query_builder::AstPass<'_, 'b, DB>,
+    ) -> diesel::QueryResult<()> {
+        out.push_bind_param(&self.value)
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl<ST, T> diesel::query_builder::QueryId for TypedBind<ST, T>
+where
+    ST: diesel::query_builder::QueryId,
+{
+    type QueryId = TypedBind<ST::QueryId, ()>;
+
+    const HAS_STATIC_QUERY_ID: bool = ST::HAS_STATIC_QUERY_ID;
+}
+
+#[cfg(feature = "diesel")]
+impl<ST, T, QS> diesel::AppearsOnTable<QS> for TypedBind<ST, T> where Self: diesel::Expression {}
+
+#[cfg(feature = "diesel")]
+impl<ST, T, QS> diesel::SelectableExpression<QS> for TypedBind<ST, T> where
+    Self: diesel::AppearsOnTable<QS>
+{
+}
+
+#[cfg(feature = "diesel")]
+impl<ST, T, GB> diesel::expression::ValidGrouping<GB> for TypedBind<ST, T> {
+    type IsAggregate = diesel::expression::is_aggregate::Never;
+}
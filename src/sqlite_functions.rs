@@ -0,0 +1,654 @@
+//! Rust implementations of a subset of the PostGIS functions for the SQLite backend.
+//!
+//! SQLite has no spatial functions, so queries built with [`functions`](crate::functions),
+//! [`functions_nullable`](crate::functions_nullable) and [`intersects_2d`](crate::operators::intersects_2d)
+//! fail at runtime unless [`register_functions`] was called on the connection first. The functions
+//! work on the EWKB stored in [`Geometry`](crate::sql_types::Geometry) columns, in 2D and in the
+//! plane: Z and M are ignored and geography values are measured in their coordinate units, not in
+//! meters. Operations on geometries with different SRIDs fail, like in PostGIS.
+//! ```
+//! use diesel::prelude::*;
+//! use diesel::select;
+//! use postgis_diesel::functions::{st_distance, st_x};
+//! use postgis_diesel::sql_types::Geometry;
+//! use postgis_diesel::sqlite_functions::register_functions;
+//! use postgis_diesel::types::Point;
+//! let mut conn = SqliteConnection::establish(":memory:").unwrap();
+//! register_functions(&mut conn).unwrap();
+//! let point = Point::new(3.0, 4.0, Some(4326)).into_sql::<Geometry>();
+//! let origin = Point::new(0.0, 0.0, Some(4326)).into_sql::<Geometry>();
+//! assert_eq!(Ok(3.0), select(st_x(point)).get_result::<f64>(&mut conn));
+//! assert_eq!(Ok(5.0), select(st_distance(point, origin)).get_result::<f64>(&mut conn));
+//! ```
+#![cfg(feature = "sqlite")]
+
+use diesel::deserialize::FromSql;
+use diesel::serialize::{Output, ToSql};
+use diesel::sql_types::Binary;
+use diesel::sqlite::{Sqlite, SqliteConnection, SqliteValue};
+use diesel::QueryResult;
+
+use crate::types::{Coordinates, EwkbRef, GeometryType};
+
+#[allow(dead_code)]
+mod definitions {
+    use diesel::sql_types::*;
+
+    diesel::define_sql_function! {
+        #[sql_name="ST_X"]
+        fn st_x(geometry: Nullable<Binary>) -> Nullable<Double>;
+    }
+    diesel::define_sql_function! {
+        #[sql_name="ST_Y"]
+        fn st_y(geometry: Nullable<Binary>) -> Nullable<Double>;
+    }
+    diesel::define_sql_function! {
+        #[sql_name="ST_SRID"]
+        fn st_srid(geometry: Nullable<Binary>) -> Nullable<Integer>;
+    }
+    diesel::define_sql_function! {
+        #[sql_name="ST_XMin"]
+        fn st_x_min(geometry: Nullable<Binary>) -> Nullable<Double>;
+    }
+    diesel::define_sql_function! {
+        #[sql_name="ST_XMax"]
+        fn st_x_max(geometry: Nullable<Binary>) -> Nullable<Double>;
+    }
+    diesel::define_sql_function! {
+        #[sql_name="ST_YMin"]
+        fn st_y_min(geometry: Nullable<Binary>) -> Nullable<Double>;
+    }
+    diesel::define_sql_function! {
+        #[sql_name="ST_YMax"]
+        fn st_y_max(geometry: Nullable<Binary>) -> Nullable<Double>;
+    }
+    diesel::define_sql_function! {
+        #[sql_name="ST_Area"]
+        fn st_area(geometry: Nullable<Binary>) -> Nullable<Double>;
+    }
+    diesel::define_sql_function! {
+        #[sql_name="ST_Length"]
+        fn st_length(geometry: Nullable<Binary>) -> Nullable<Double>;
+    }
+    diesel::define_sql_function! {
+        #[sql_name="MbrIntersects"]
+        fn mbr_intersects(left: Nullable<Binary>, right: Nullable<Binary>) -> Nullable<Bool>;
+    }
+    diesel::define_sql_function! {
+        #[sql_name="ST_Intersects"]
+        fn st_intersects(left: Nullable<Binary>, right: Nullable<Binary>) -> Nullable<Bool>;
+    }
+    diesel::define_sql_function! {
+        #[sql_name="ST_Contains"]
+        fn st_contains(left: Nullable<Binary>, right: Nullable<Binary>) -> Nullable<Bool>;
+    }
+    diesel::define_sql_function! {
+        #[sql_name="ST_Distance"]
+        fn st_distance(left: Nullable<Binary>, right: Nullable<Binary>) -> Nullable<Double>;
+    }
+    diesel::define_sql_function! {
+        #[sql_name="ST_DWithin"]
+        fn st_d_within(left: Nullable<Binary>, right: Nullable<Binary>, distance: Nullable<Double>) -> Nullable<Bool>;
+    }
+}
+
+/// Registers `ST_X`, `ST_Y`, `ST_SRID`, `ST_XMin`, `ST_XMax`, `ST_YMin`, `ST_YMax`, `ST_Area`,
+/// `ST_Length`, `ST_Intersects`, `ST_Contains`, `ST_Distance`, `ST_DWithin` and `MbrIntersects`, which
+/// backs the `&&` operator, on the connection.
+///
+/// `ST_Contains` is exact for points and polygons, a line is checked at its vertices and at the
+/// middle of its segments.
+pub fn register_functions(conn: &mut SqliteConnection) -> QueryResult<()> {
+    use definitions::*;
+
+    st_x_utils::register_impl(conn, |g: Option<Shape>| {
+        unary(g, |g| g.point("ST_X", |c| c.x))
+    })?;
+    st_y_utils::register_impl(conn, |g: Option<Shape>| {
+        unary(g, |g| g.point("ST_Y", |c| c.y))
+    })?;
+    st_srid_utils::register_impl(conn, |g: Option<Shape>| {
+        unary(g, |g| Ok(Some(g.srid.unwrap_or(0) as i32)))
+    })?;
+    st_x_min_utils::register_impl(conn, |g: Option<Shape>| {
+        unary(g, |g| Ok(g.mbr().map(|mbr| mbr.0.x)))
+    })?;
+    st_x_max_utils::register_impl(conn, |g: Option<Shape>| {
+        unary(g, |g| Ok(g.mbr().map(|mbr| mbr.1.x)))
+    })?;
+    st_y_min_utils::register_impl(conn, |g: Option<Shape>| {
+        unary(g, |g| Ok(g.mbr().map(|mbr| mbr.0.y)))
+    })?;
+    st_y_max_utils::register_impl(conn, |g: Option<Shape>| {
+        unary(g, |g| Ok(g.mbr().map(|mbr| mbr.1.y)))
+    })?;
+    st_area_utils::register_impl(conn, |g: Option<Shape>| unary(g, |g| Ok(Some(g.area()))))?;
+    st_length_utils::register_impl(conn, |g: Option<Shape>| unary(g, |g| Ok(Some(g.length()))))?;
+    mbr_intersects_utils::register_impl(conn, |a: Option<Shape>, b: Option<Shape>| {
+        binary(a, b, |a, b| Some(a.mbr_intersects(b)))
+    })?;
+    st_intersects_utils::register_impl(conn, |a: Option<Shape>, b: Option<Shape>| {
+        binary(a, b, |a, b| Some(a.intersects(b)))
+    })?;
+    st_contains_utils::register_impl(conn, |a: Option<Shape>, b: Option<Shape>| {
+        binary(a, b, |a, b| Some(a.contains(b)))
+    })?;
+    st_distance_utils::register_impl(conn, |a: Option<Shape>, b: Option<Shape>| {
+        binary(a, b, Shape::distance)
+    })?;
+    st_d_within_utils::register_impl(
+        conn,
+        |a: Option<Shape>, b: Option<Shape>, distance: Option<f64>| match distance {
+            Some(distance) => binary(a, b, |a, b| a.distance(b).map(|d| d <= distance)),
+            None => Outcome(Ok(None)),
+        },
+    )?;
+    Ok(())
+}
+
+/// Result of a function, an error is reported to SQLite when the value is bound.
+#[derive(Debug)]
+struct Outcome<T>(Result<Option<T>, String>);
+
+impl<T, ST> ToSql<ST, Sqlite> for Outcome<T>
+where
+    T: std::fmt::Debug,
+    Option<T>: ToSql<ST, Sqlite>,
+{
+    fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Sqlite>) -> diesel::serialize::Result {
+        match &self.0 {
+            Ok(value) => value.to_sql(out),
+            Err(message) => Err(message.clone().into()),
+        }
+    }
+}
+
+fn unary<T, F>(geometry: Option<Shape>, f: F) -> Outcome<T>
+where
+    F: Fn(&Shape) -> Result<Option<T>, String>,
+{
+    Outcome(geometry.map_or(Ok(None), |g| f(&g)))
+}
+
+fn binary<T, F>(left: Option<Shape>, right: Option<Shape>, f: F) -> Outcome<T>
+where
+    F: Fn(&Shape, &Shape) -> Option<T>,
+{
+    Outcome(match (left, right) {
+        (Some(left), Some(right)) if left.srid != right.srid => Err(format!(
+            "Operation on mixed SRID geometries ({} != {})",
+            left.srid.unwrap_or(0),
+            right.srid.unwrap_or(0)
+        )),
+        (Some(left), Some(right)) => Ok(f(&left, &right)),
+        _ => Ok(None),
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Coord {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Location {
+    Exterior,
+    Boundary,
+    Interior,
+}
+
+/// A decoded geometry, flattened to its points, lines and polygons.
+#[derive(Debug, diesel::deserialize::FromSqlRow)]
+struct Shape {
+    srid: Option<u32>,
+    geometry_type: GeometryType,
+    points: Vec<Coord>,
+    lines: Vec<Vec<Coord>>,
+    polygons: Vec<Vec<Vec<Coord>>>,
+}
+
+impl FromSql<Binary, Sqlite> for Shape {
+    fn from_sql(bytes: SqliteValue<'_, '_, '_>) -> diesel::deserialize::Result<Self> {
+        let bytes = <Vec<u8> as FromSql<Binary, Sqlite>>::from_sql(bytes)?;
+        Ok(Shape::from_ewkb(&bytes)?)
+    }
+}
+
+impl Shape {
+    fn from_ewkb(ewkb: &[u8]) -> Result<Self, std::io::Error> {
+        let ewkb = EwkbRef::new(ewkb);
+        let mut shape = Shape {
+            srid: ewkb.srid()?,
+            geometry_type: ewkb.geometry_type()?,
+            points: Vec::new(),
+            lines: Vec::new(),
+            polygons: Vec::new(),
+        };
+        shape.add(ewkb)?;
+        Ok(shape)
+    }
+
+    /// Adds the points, lines and polygons of the geometry, the parts of which `EwkbRef` reads no
+    /// deeper than `MAX_NESTING_DEPTH`.
+    fn add(&mut self, ewkb: EwkbRef<'_>) -> Result<(), std::io::Error> {
+        let coords = |coordinates: Coordinates| -> Vec<Coord> {
+            coordinates.map(|c| Coord { x: c.x, y: c.y }).collect()
+        };
+        match ewkb.geometry_type()? {
+            GeometryType::Point => self
+                .points
+                .extend(coords(ewkb.points()?).into_iter().filter(|c| !c.x.is_nan())),
+            GeometryType::LineString => {
+                let line = coords(ewkb.points()?);
+                if !line.is_empty() {
+                    self.lines.push(line);
+                }
+            }
+            GeometryType::Polygon => {
+                let rings = ewkb
+                    .rings()?
+                    .map(|ring| ring.map(coords))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if !rings.is_empty() {
+                    self.polygons.push(rings);
+                }
+            }
+            _ => {
+                for part in ewkb.parts()? {
+                    self.add(part?)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn is_empty(&self) -> bool {
+        self.points.is_empty() && self.lines.is_empty() && self.polygons.is_empty()
+    }
+
+    fn point<F>(&self, function: &str, f: F) -> Result<Option<f64>, String>
+    where
+        F: Fn(&Coord) -> f64,
+    {
+        if self.geometry_type != GeometryType::Point {
+            return Err(format!("Argument to {}() must have type POINT", function));
+        }
+        Ok(self.points.first().map(f))
+    }
+
+    fn coords(&self) -> impl Iterator<Item = &Coord> {
+        self.points
+            .iter()
+            .chain(self.lines.iter().flatten())
+            .chain(self.polygons.iter().flatten().flatten())
+    }
+
+    /// Segments of the points, lines and polygon rings, a point is a segment of zero length.
+    fn segments(&self) -> Vec<(Coord, Coord)> {
+        let mut segments: Vec<(Coord, Coord)> = self.points.iter().map(|p| (*p, *p)).collect();
+        for line in self.lines.iter().chain(self.polygons.iter().flatten()) {
+            if line.len() == 1 {
+                segments.push((line[0], line[0]));
+            }
+            segments.extend(line.windows(2).map(|s| (s[0], s[1])));
+        }
+        segments
+    }
+
+    fn area(&self) -> f64 {
+        self.polygons
+            .iter()
+            .map(|rings| {
+                let mut rings = rings.iter().map(|ring| ring_area(ring).abs());
+                let outer = rings.next().unwrap_or(0.0);
+                outer - rings.sum::<f64>()
+            })
+            .sum()
+    }
+
+    fn length(&self) -> f64 {
+        self.lines
+            .iter()
+            .flat_map(|line| line.windows(2))
+            .map(|s| distance(&s[0], &s[1]))
+            .sum()
+    }
+
+    fn mbr(&self) -> Option<(Coord, Coord)> {
+        self.coords().fold(None, |mbr, c| {
+            let (min, max) = mbr.unwrap_or((*c, *c));
+            Some((
+                Coord {
+                    x: min.x.min(c.x),
+                    y: min.y.min(c.y),
+                },
+                Coord {
+                    x: max.x.max(c.x),
+                    y: max.y.max(c.y),
+                },
+            ))
+        })
+    }
+
+    fn mbr_intersects(&self, other: &Shape) -> bool {
+        match (self.mbr(), other.mbr()) {
+            (Some((min, max)), Some((other_min, other_max))) => {
+                min.x <= other_max.x
+                    && other_min.x <= max.x
+                    && min.y <= other_max.y
+                    && other_min.y <= max.y
+            }
+            _ => false,
+        }
+    }
+
+    fn locate(&self, p: &Coord) -> Location {
+        let polygons = self
+            .polygons
+            .iter()
+            .map(|rings| locate_in_polygon(rings, p));
+        let lines = self.lines.iter().map(|line| {
+            if !line.windows(2).any(|s| on_segment(p, &s[0], &s[1])) && line != &[*p] {
+                Location::Exterior
+            } else if line.first() != line.last()
+                && (line.first() == Some(p) || line.last() == Some(p))
+            {
+                Location::Boundary
+            } else {
+                Location::Interior
+            }
+        });
+        let points = self.points.iter().map(|c| {
+            if c == p {
+                Location::Interior
+            } else {
+                Location::Exterior
+            }
+        });
+        polygons
+            .chain(lines)
+            .chain(points)
+            .max()
+            .unwrap_or(Location::Exterior)
+    }
+
+    fn intersects(&self, other: &Shape) -> bool {
+        let segments = self.segments();
+        let other_segments = other.segments();
+        segments.iter().any(|(a, b)| {
+            other_segments
+                .iter()
+                .any(|(c, d)| segments_intersect(a, b, c, d))
+        }) || self.coords().any(|c| other.locate(c) != Location::Exterior)
+            || other.coords().any(|c| self.locate(c) != Location::Exterior)
+    }
+
+    fn contains(&self, other: &Shape) -> bool {
+        if self.is_empty() || other.is_empty() {
+            return false;
+        }
+        let rings: Vec<(Coord, Coord)> = self
+            .polygons
+            .iter()
+            .flatten()
+            .flat_map(|ring| ring.windows(2).map(|s| (s[0], s[1])))
+            .collect();
+        let segments = other.segments();
+        let midpoints: Vec<Coord> = segments
+            .iter()
+            .map(|(a, b)| Coord {
+                x: (a.x + b.x) / 2.0,
+                y: (a.y + b.y) / 2.0,
+            })
+            .collect();
+        let covered = other
+            .coords()
+            .chain(midpoints.iter())
+            .all(|c| self.locate(c) != Location::Exterior);
+        let crosses_boundary = segments
+            .iter()
+            .any(|(a, b)| rings.iter().any(|(c, d)| properly_intersect(a, b, c, d)));
+        let encloses_boundary = self.polygons.iter().flatten().flatten().any(|c| {
+            other
+                .polygons
+                .iter()
+                .any(|rings| locate_in_polygon(rings, c) == Location::Interior)
+        });
+        let shares_interior = !other.polygons.is_empty()
+            || other
+                .coords()
+                .chain(midpoints.iter())
+                .any(|c| self.locate(c) == Location::Interior);
+        covered && !crosses_boundary && !encloses_boundary && shares_interior
+    }
+
+    fn distance(&self, other: &Shape) -> Option<f64> {
+        if self.is_empty() || other.is_empty() {
+            return None;
+        }
+        if self.intersects(other) {
+            return Some(0.0);
+        }
+        let other_segments = other.segments();
+        self.segments()
+            .iter()
+            .flat_map(|(a, b)| {
+                other_segments
+                    .iter()
+                    .map(move |(c, d)| segment_distance(a, b, c, d))
+            })
+            .reduce(f64::min)
+    }
+}
+
+fn ring_area(ring: &[Coord]) -> f64 {
+    ring.windows(2)
+        .map(|s| s[0].x * s[1].y - s[1].x * s[0].y)
+        .sum::<f64>()
+        / 2.0
+}
+
+fn distance(a: &Coord, b: &Coord) -> f64 {
+    (a.x - b.x).hypot(a.y - b.y)
+}
+
+fn orientation(a: &Coord, b: &Coord, c: &Coord) -> f64 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+fn on_segment(p: &Coord, a: &Coord, b: &Coord) -> bool {
+    orientation(a, b, p) == 0.0
+        && p.x >= a.x.min(b.x)
+        && p.x <= a.x.max(b.x)
+        && p.y >= a.y.min(b.y)
+        && p.y <= a.y.max(b.y)
+}
+
+fn properly_intersect(a: &Coord, b: &Coord, c: &Coord, d: &Coord) -> bool {
+    let (o1, o2) = (orientation(a, b, c), orientation(a, b, d));
+    let (o3, o4) = (orientation(c, d, a), orientation(c, d, b));
+    o1 * o2 < 0.0 && o3 * o4 < 0.0
+}
+
+fn segments_intersect(a: &Coord, b: &Coord, c: &Coord, d: &Coord) -> bool {
+    properly_intersect(a, b, c, d)
+        || on_segment(c, a, b)
+        || on_segment(d, a, b)
+        || on_segment(a, c, d)
+        || on_segment(b, c, d)
+}
+
+fn point_segment_distance(p: &Coord, a: &Coord, b: &Coord) -> f64 {
+    let length = (b.x - a.x).powi(2) + (b.y - a.y).powi(2);
+    if length == 0.0 {
+        return distance(p, a);
+    }
+    let t = (((p.x - a.x) * (b.x - a.x) + (p.y - a.y) * (b.y - a.y)) / length).clamp(0.0, 1.0);
+    distance(
+        p,
+        &Coord {
+            x: a.x + t * (b.x - a.x),
+            y: a.y + t * (b.y - a.y),
+        },
+    )
+}
+
+fn segment_distance(a: &Coord, b: &Coord, c: &Coord, d: &Coord) -> f64 {
+    if segments_intersect(a, b, c, d) {
+        return 0.0;
+    }
+    point_segment_distance(a, c, d)
+        .min(point_segment_distance(b, c, d))
+        .min(point_segment_distance(c, a, b))
+        .min(point_segment_distance(d, a, b))
+}
+
+fn locate_in_ring(ring: &[Coord], p: &Coord) -> Location {
+    if ring.windows(2).any(|s| on_segment(p, &s[0], &s[1])) {
+        return Location::Boundary;
+    }
+    let crossings = ring
+        .windows(2)
+        .filter(|s| {
+            let (a, b) = (&s[0], &s[1]);
+            (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) * (b.x - a.x) / (b.y - a.y)
+        })
+        .count();
+    if crossings % 2 == 1 {
+        Location::Interior
+    } else {
+        Location::Exterior
+    }
+}
+
+fn locate_in_polygon(rings: &[Vec<Coord>], p: &Coord) -> Location {
+    let mut rings = rings.iter();
+    let outer = match rings.next() {
+        Some(outer) => locate_in_ring(outer, p),
+        None => return Location::Exterior,
+    };
+    if outer != Location::Interior {
+        return outer;
+    }
+    for hole in rings {
+        match locate_in_ring(hole, p) {
+            Location::Interior => return Location::Exterior,
+            Location::Boundary => return Location::Boundary,
+            Location::Exterior => {}
+        }
+    }
+    Location::Interior
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::DecodeErrorReason;
+    use crate::types::{LineString, Point, Polygon};
+    use crate::write_to_read_from_sql::WriteToSql;
+
+    fn shape<G: WriteToSql>(geometry: &G) -> Shape {
+        let mut ewkb = Vec::new();
+        geometry.write_to_sql(true, &mut ewkb).unwrap();
+        Shape::from_ewkb(&ewkb).unwrap()
+    }
+
+    fn square(min: f64, max: f64) -> Polygon<Point> {
+        let mut polygon = Polygon::new(None);
+        polygon
+            .add_points([
+                Point::new(min, min, None),
+                Point::new(max, min, None),
+                Point::new(max, max, None),
+                Point::new(min, max, None),
+                Point::new(min, min, None),
+            ])
+            .unwrap();
+        polygon
+    }
+
+    fn line(points: &[(f64, f64)]) -> LineString<Point> {
+        let mut line = LineString::new(None);
+        for (x, y) in points {
+            line.add_point(Point::new(*x, *y, None)).unwrap();
+        }
+        line
+    }
+
+    #[test]
+    fn test_measures() {
+        let mut with_hole = square(0.0, 4.0);
+        with_hole.add_ring();
+        with_hole
+            .add_points([
+                Point::new(1.0, 1.0, None),
+                Point::new(1.0, 2.0, None),
+                Point::new(2.0, 2.0, None),
+                Point::new(2.0, 1.0, None),
+                Point::new(1.0, 1.0, None),
+            ])
+            .unwrap();
+        assert_eq!(15.0, shape(&with_hole).area());
+        assert_eq!(0.0, shape(&with_hole).length());
+        assert_eq!(
+            7.0,
+            shape(&line(&[(0.0, 0.0), (3.0, 4.0), (3.0, 6.0)])).length()
+        );
+    }
+
+    #[test]
+    fn test_predicates() {
+        let outer = shape(&square(0.0, 4.0));
+        let inner = shape(&square(1.0, 2.0));
+        let far = shape(&square(10.0, 12.0));
+        assert!(outer.contains(&inner));
+        assert!(!inner.contains(&outer));
+        assert!(outer.contains(&outer));
+        assert!(outer.intersects(&inner));
+        assert!(!outer.intersects(&far));
+        assert_eq!(Some(72f64.sqrt()), outer.distance(&far));
+        let crossing = shape(&line(&[(-1.0, 1.0), (5.0, 1.0)]));
+        assert!(outer.intersects(&crossing));
+        assert!(!outer.contains(&crossing));
+        let on_boundary = shape(&Point::new(4.0, 2.0, None));
+        assert!(outer.intersects(&on_boundary));
+        assert!(!outer.contains(&on_boundary));
+        assert!(outer.contains(&shape(&Point::new(3.0, 3.0, None))));
+    }
+
+    #[test]
+    fn test_hole() {
+        let mut with_hole = square(0.0, 6.0);
+        with_hole.add_ring();
+        with_hole
+            .add_points([
+                Point::new(2.0, 2.0, None),
+                Point::new(2.0, 4.0, None),
+                Point::new(4.0, 4.0, None),
+                Point::new(4.0, 2.0, None),
+                Point::new(2.0, 2.0, None),
+            ])
+            .unwrap();
+        let with_hole = shape(&with_hole);
+        let in_hole = shape(&Point::new(3.0, 3.0, None));
+        assert!(!with_hole.intersects(&in_hole));
+        assert_eq!(Some(1.0), with_hole.distance(&in_hole));
+        assert!(!with_hole.contains(&shape(&square(1.0, 5.0))));
+    }
+
+    #[test]
+    fn test_nested_collections() {
+        let mut ewkb = Vec::new();
+        for _ in 0..200_000 {
+            ewkb.extend_from_slice(&[1, 7, 0, 0, 0, 1, 0, 0, 0]);
+        }
+        let error = Shape::from_ewkb(&ewkb).unwrap_err();
+        assert_eq!(
+            Some(&DecodeErrorReason::TooDeeplyNested),
+            error
+                .get_ref()
+                .and_then(|inner| inner.downcast_ref::<DecodeErrorReason>())
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use crate::errors::Error;
+use crate::ewkb::EwkbSerializable;
+use crate::types::Srid;
+
+fn check_srid<const SRID: u32>(actual: Option<u32>, accept_none: bool) -> Result<(), Error> {
+    match actual {
+        Some(srid) if srid == SRID => Ok(()),
+        None if accept_none => Ok(()),
+        _ => Err(Error::IncompatibleSpatialReferenceSystemIdentifier {
+            expected: Some(SRID),
+            actual,
+        }),
+    }
+}
+
+impl<T, const SRID: u32> Srid<T, SRID>
+where
+    T: EwkbSerializable,
+{
+    /// Wraps `value`, fails if it has a SRID other than `SRID`.
+    pub fn new(value: T) -> Result<Self, Error> {
+        check_srid::<SRID>(value.srid(), true)?;
+        Ok(Self(value))
+    }
+}
+
+impl<T, const SRID: u32> Srid<T, SRID> {
+    /// Returns the wrapped geometry.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, const SRID: u32> std::ops::Deref for Srid<T, SRID> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+#[cfg(any(
+    feature = "postgres",
+    feature = "sqlite",
+    feature = "sqlx-postgres",
+    feature = "sqlx-sqlite"
+))]
+impl<T, const SRID: u32> Srid<T, SRID>
+where
+    T: crate::write_to_read_from_sql::WriteToSql,
+{
+    fn write_to_sql<W>(&self, out: &mut W) -> Result<(), std::io::Error>
+    where
+        W: std::io::Write,
+    {
+        use byteorder::{LittleEndian, WriteBytesExt};
+        out.write_u8(crate::ewkb::LITTLE_ENDIAN)?;
+        out.write_u32::<LittleEndian>(self.0.geometry_type() | crate::ewkb::SRID)?;
+        out.write_u32::<LittleEndian>(SRID)?;
+        self.0.write_body(out)
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl<T, ST, DB, const SRID: u32> diesel::deserialize::FromSql<ST, DB> for Srid<T, SRID>
+where
+    T: diesel::deserialize::FromSql<ST, DB> + EwkbSerializable,
+    DB: diesel::backend::Backend,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+        let value = T::from_sql(bytes)?;
+        check_srid::<SRID>(value.srid(), false)?;
+        Ok(Self(value))
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<T, const SRID: u32> diesel::serialize::ToSql<crate::sql_types::Geometry, diesel::pg::Pg>
+    for Srid<T, SRID>
+where
+    T: crate::write_to_read_from_sql::WriteToSql + std::fmt::Debug,
+{
+    fn to_sql(
+        &self,
+        out: &mut diesel::serialize::Output<diesel::pg::Pg>,
+    ) -> diesel::serialize::Result {
+        check_srid::<SRID>(self.0.srid(), true)?;
+        self.write_to_sql(out)?;
+        Ok(diesel::serialize::IsNull::No)
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<T, const SRID: u32> diesel::serialize::ToSql<crate::sql_types::Geography, diesel::pg::Pg>
+    for Srid<T, SRID>
+where
+    T: crate::write_to_read_from_sql::WriteToSql + std::fmt::Debug,
+{
+    fn to_sql<'b>(
+        &'b self,
+        out: &mut diesel::serialize::Output<'b, '_, diesel::pg::Pg>,
+    ) -> diesel::serialize::Result {
+        diesel::serialize::ToSql::<crate::sql_types::Geometry, diesel::pg::Pg>::to_sql(self, out)
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<T, const SRID: u32>
+    diesel::serialize::ToSql<crate::sql_types::Geometry, diesel::sqlite::Sqlite> for Srid<T, SRID>
+where
+    T: crate::write_to_read_from_sql::WriteToSql + std::fmt::Debug,
+{
+    fn to_sql(
+        &self,
+        out: &mut diesel::serialize::Output<diesel::sqlite::Sqlite>,
+    ) -> diesel::serialize::Result {
+        check_srid::<SRID>(self.0.srid(), true)?;
+        let mut buffer = Vec::new();
+        self.write_to_sql(&mut buffer)?;
+        out.set_value(buffer);
+        Ok(diesel::serialize::IsNull::No)
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl<T, const SRID: u32> diesel::serialize::ToSql<crate::sql_types::Geometry, diesel::mysql::Mysql>
+    for Srid<T, SRID>
+where
+    T: crate::write_to_read_from_sql::WriteToSql + std::fmt::Debug,
+{
+    fn to_sql(
+        &self,
+        out: &mut diesel::serialize::Output<diesel::mysql::Mysql>,
+    ) -> diesel::serialize::Result {
+        check_srid::<SRID>(self.0.srid(), true)?;
+        self.0.write_to_mysql_with_srid(SRID, out)?;
+        Ok(diesel::serialize::IsNull::No)
+    }
+}
+
+#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-sqlite"))]
+impl<T, DB, const SRID: u32> sqlx::Type<DB> for Srid<T, SRID>
+where
+    T: sqlx::Type<DB>,
+    DB: sqlx::Database,
+{
+    fn type_info() -> DB::TypeInfo {
+        T::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        T::compatible(ty)
+    }
+}
+
+#[cfg(any(feature = "sqlx-postgres", feature = "sqlx-sqlite"))]
+impl<'r, T, DB, const SRID: u32> sqlx::Decode<'r, DB> for Srid<T, SRID>
+where
+    T: sqlx::Decode<'r, DB> + EwkbSerializable,
+    DB: sqlx::Database,
+{
+    fn decode(value: DB::ValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+        let value = T::decode(value)?;
+        check_srid::<SRID>(value.srid(), false)?;
+        Ok(Self(value))
+    }
+}
+
+#[cfg(feature = "sqlx-postgres")]
+impl<'q, T, const SRID: u32> sqlx::Encode<'q, sqlx::Postgres> for Srid<T, SRID>
+where
+    T: crate::write_to_read_from_sql::WriteToSql,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut sqlx::postgres::PgArgumentBuffer,
+    ) -> Result<sqlx::encode::IsNull, sqlx::error::BoxDynError> {
+        check_srid::<SRID>(self.0.srid(), true)?;
+        self.write_to_sql(&mut **buf)?;
+        Ok(sqlx::encode::IsNull::No)
+    }
+}
+
+#[cfg(feature = "sqlx-sqlite")]
+impl<'q, T, const SRID: u32> sqlx::Encode<'q, sqlx::Sqlite> for Srid<T, SRID>
+where
+    T: crate::write_to_read_from_sql::WriteToSql,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut Vec<sqlx::sqlite::SqliteArgumentValue<'q>>,
+    ) -> Result<sqlx::encode::IsNull, sqlx::error::BoxDynError> {
+        check_srid::<SRID>(self.0.srid(), true)?;
+        let mut buffer = Vec::new();
+        self.write_to_sql(&mut buffer)?;
+        buf.push(sqlx::sqlite::SqliteArgumentValue::Blob(buffer.into()));
+        Ok(sqlx::encode::IsNull::No)
+    }
+}
@@ -0,0 +1,665 @@
+//! Submodule implementing conversions between the geometries of this crate and the ones of the
+//! `geo-types` crate, used by the `geo` ecosystem.
+//!
+//! `geo-types` geometries are two-dimensional and have no SRID, so converting to them drops the
+//! SRID. Geometries of `Point` convert with `From`, the ones of `PointZ`, `PointM` and `PointZM`
+//! with `TryFrom`, which fails on a Z or M value which isn't NaN instead of dropping it, and
+//! `to_2d` drops those values explicitly. Converting from them gives geometries of `Point` without
+//! SRID, unless they are converted to a [`Srid`] wrapper, which sets its SRID on the geometry and
+//! all its points.
+#![cfg(feature = "geo-types")]
+
+use std::convert::TryFrom;
+
+use crate::errors::GeoTypesError;
+use crate::types::*;
+
+fn coord<T: PointT>(point: &T) -> geo_types::Coord<f64> {
+    geo_types::coord! { x: point.get_x(), y: point.get_y() }
+}
+
+fn point(coord: geo_types::Coord<f64>, srid: Option<u32>) -> Point {
+    Point::new(coord.x, coord.y, srid)
+}
+
+fn points(
+    coords: impl IntoIterator<Item = geo_types::Coord<f64>>,
+    srid: Option<u32>,
+) -> Vec<Point> {
+    coords.into_iter().map(|c| point(c, srid)).collect()
+}
+
+fn ring<T: PointT>(points: &[T]) -> geo_types::LineString<f64> {
+    points.iter().map(coord).collect()
+}
+
+fn point_2d<T: PointT>(point: &T) -> Point {
+    Point::new(point.get_x(), point.get_y(), point.get_srid())
+}
+
+fn points_2d<T: PointT>(points: &[T]) -> Vec<Point> {
+    points.iter().map(point_2d).collect()
+}
+
+/// Fails on the first Z or M value which isn't NaN, NaN being a missing value.
+fn check_2d<'a, T: PointT + 'a>(
+    points: impl IntoIterator<Item = &'a T>,
+) -> Result<(), GeoTypesError> {
+    for point in points {
+        let z = point.get_z().filter(|z| !z.is_nan());
+        let m = point.get_m().filter(|m| !m.is_nan());
+        if z.is_some() || m.is_some() {
+            return Err(GeoTypesError::ZOrM { z, m });
+        }
+    }
+    Ok(())
+}
+
+macro_rules! impl_point_conversions {
+    ($($point:ident),*) => {
+        $(
+            impl $point {
+                /// Drops the Z and M values, the SRID is kept.
+                pub fn to_2d(&self) -> Point {
+                    point_2d(self)
+                }
+            }
+
+            impl TryFrom<$point> for geo_types::Coord<f64> {
+                type Error = GeoTypesError;
+
+                fn try_from(point: $point) -> Result<Self, Self::Error> {
+                    check_2d(&[point])?;
+                    Ok(coord(&point))
+                }
+            }
+
+            impl TryFrom<$point> for geo_types::Point<f64> {
+                type Error = GeoTypesError;
+
+                fn try_from(point: $point) -> Result<Self, Self::Error> {
+                    Ok(geo_types::Point(geo_types::Coord::try_from(point)?))
+                }
+            }
+        )*
+    };
+}
+
+impl_point_conversions!(PointZ, PointM, PointZM);
+
+impl From<Point> for geo_types::Coord<f64> {
+    fn from(point: Point) -> Self {
+        coord(&point)
+    }
+}
+
+impl From<Point> for geo_types::Point<f64> {
+    fn from(point: Point) -> Self {
+        geo_types::Point(coord(&point))
+    }
+}
+
+impl From<geo_types::Coord<f64>> for Point {
+    fn from(coord: geo_types::Coord<f64>) -> Self {
+        point(coord, None)
+    }
+}
+
+impl From<geo_types::Point<f64>> for Point {
+    fn from(value: geo_types::Point<f64>) -> Self {
+        point(value.0, None)
+    }
+}
+
+impl<const SRID: u32> From<geo_types::Point<f64>> for Srid<Point, SRID> {
+    fn from(value: geo_types::Point<f64>) -> Self {
+        Srid(point(value.0, Some(SRID)))
+    }
+}
+
+/// Implements the conversions of a geometry of `PointZ`, `PointM` and `PointZM` to `geo-types`,
+/// which fail instead of dropping Z and M values.
+macro_rules! impl_try_into_geo_types {
+    ($geometry:ident, $geo:ident) => {
+        impl_try_into_geo_types!($geometry, $geo, PointZ, PointM, PointZM);
+    };
+    ($geometry:ident, $geo:ident, $($point:ident),*) => {
+        $(
+            impl TryFrom<$geometry<$point>> for geo_types::$geo<f64> {
+                type Error = GeoTypesError;
+
+                fn try_from(geometry: $geometry<$point>) -> Result<Self, Self::Error> {
+                    geometry.check_2d()?;
+                    Ok(geometry.to_2d().into())
+                }
+            }
+        )*
+    };
+}
+
+/// Implements the conversions of a geometry from `geo-types` with a function building it with a
+/// given SRID.
+macro_rules! impl_from_geo_types {
+    ($geometry:ident, $geo:ident, $from:ident) => {
+        impl From<geo_types::$geo<f64>> for $geometry<Point> {
+            fn from(geometry: geo_types::$geo<f64>) -> Self {
+                $from(geometry, None)
+            }
+        }
+
+        impl<const SRID: u32> From<geo_types::$geo<f64>> for Srid<$geometry<Point>, SRID> {
+            fn from(geometry: geo_types::$geo<f64>) -> Self {
+                Srid($from(geometry, Some(SRID)))
+            }
+        }
+    };
+}
+
+/// Implements the conversions of a geometry from `geo-types` with a function building it with a
+/// given SRID, which fails for polygons with interiors but no exterior.
+macro_rules! impl_try_from_geo_types {
+    ($geometry:ident, $geo:ident, $from:ident) => {
+        impl TryFrom<geo_types::$geo<f64>> for $geometry<Point> {
+            type Error = GeoTypesError;
+
+            fn try_from(geometry: geo_types::$geo<f64>) -> Result<Self, Self::Error> {
+                $from(geometry, None)
+            }
+        }
+
+        impl<const SRID: u32> TryFrom<geo_types::$geo<f64>> for Srid<$geometry<Point>, SRID> {
+            type Error = GeoTypesError;
+
+            fn try_from(geometry: geo_types::$geo<f64>) -> Result<Self, Self::Error> {
+                Ok(Srid($from(geometry, Some(SRID))?))
+            }
+        }
+    };
+}
+
+impl<T: PointT> LineString<T> {
+    /// Drops the Z and M values of the points, the SRIDs are kept.
+    pub fn to_2d(&self) -> LineString<Point> {
+        LineString {
+            points: points_2d(&self.points),
+            srid: self.srid,
+        }
+    }
+
+    fn check_2d(&self) -> Result<(), GeoTypesError> {
+        check_2d(&self.points)
+    }
+}
+
+impl From<LineString<Point>> for geo_types::LineString<f64> {
+    fn from(line: LineString<Point>) -> Self {
+        ring(&line.points)
+    }
+}
+
+impl_try_into_geo_types!(LineString, LineString);
+
+fn line_string(line: geo_types::LineString<f64>, srid: Option<u32>) -> LineString<Point> {
+    LineString {
+        points: points(line.0, srid),
+        srid,
+    }
+}
+
+impl_from_geo_types!(LineString, LineString, line_string);
+
+impl<T: PointT> Polygon<T> {
+    /// Drops the Z and M values of the points, the SRIDs are kept.
+    pub fn to_2d(&self) -> Polygon<Point> {
+        Polygon {
+            rings: self.rings.iter().map(|r| points_2d(r)).collect(),
+            srid: self.srid,
+        }
+    }
+
+    fn check_2d(&self) -> Result<(), GeoTypesError> {
+        check_2d(self.rings.iter().flatten())
+    }
+}
+
+/// The first ring is the exterior, the other ones are the interiors. `geo-types` closes the rings
+/// which aren't, a polygon without rings has an empty exterior.
+impl From<Polygon<Point>> for geo_types::Polygon<f64> {
+    fn from(polygon: Polygon<Point>) -> Self {
+        let mut rings = polygon.rings.iter().map(|r| ring(r));
+        let exterior = rings
+            .next()
+            .unwrap_or_else(|| geo_types::LineString(vec![]));
+        geo_types::Polygon::new(exterior, rings.collect())
+    }
+}
+
+impl_try_into_geo_types!(Polygon, Polygon);
+
+/// An empty exterior gives a polygon without rings, it can't have interiors.
+fn polygon(
+    polygon: geo_types::Polygon<f64>,
+    srid: Option<u32>,
+) -> Result<Polygon<Point>, GeoTypesError> {
+    let (exterior, interiors) = polygon.into_inner();
+    if exterior.0.is_empty() && !interiors.is_empty() {
+        return Err(GeoTypesError::InteriorsWithoutExterior);
+    }
+    let rings = if exterior.0.is_empty() {
+        vec![]
+    } else {
+        std::iter::once(exterior)
+            .chain(interiors)
+            .map(|r| points(r.0, srid))
+            .collect()
+    };
+    Ok(Polygon { rings, srid })
+}
+
+impl_try_from_geo_types!(Polygon, Polygon, polygon);
+
+impl<T: PointT> MultiPoint<T> {
+    /// Drops the Z and M values of the points, the SRIDs are kept.
+    pub fn to_2d(&self) -> MultiPoint<Point> {
+        MultiPoint {
+            points: points_2d(&self.points),
+            srid: self.srid,
+        }
+    }
+
+    fn check_2d(&self) -> Result<(), GeoTypesError> {
+        check_2d(&self.points)
+    }
+}
+
+impl From<MultiPoint<Point>> for geo_types::MultiPoint<f64> {
+    fn from(multi_point: MultiPoint<Point>) -> Self {
+        multi_point
+            .points
+            .iter()
+            .map(|p| geo_types::Point(coord(p)))
+            .collect()
+    }
+}
+
+impl_try_into_geo_types!(MultiPoint, MultiPoint);
+
+fn multi_point(multi_point: geo_types::MultiPoint<f64>, srid: Option<u32>) -> MultiPoint<Point> {
+    MultiPoint {
+        points: points(multi_point.0.into_iter().map(|p| p.0), srid),
+        srid,
+    }
+}
+
+impl_from_geo_types!(MultiPoint, MultiPoint, multi_point);
+
+impl<T: PointT> MultiLineString<T> {
+    /// Drops the Z and M values of the points, the SRIDs are kept.
+    pub fn to_2d(&self) -> MultiLineString<Point> {
+        MultiLineString {
+            lines: self.lines.iter().map(LineString::to_2d).collect(),
+            srid: self.srid,
+        }
+    }
+
+    fn check_2d(&self) -> Result<(), GeoTypesError> {
+        self.lines.iter().try_for_each(LineString::check_2d)
+    }
+}
+
+impl From<MultiLineString<Point>> for geo_types::MultiLineString<f64> {
+    fn from(multi_line: MultiLineString<Point>) -> Self {
+        multi_line
+            .lines
+            .into_iter()
+            .map(geo_types::LineString::from)
+            .collect()
+    }
+}
+
+impl_try_into_geo_types!(MultiLineString, MultiLineString);
+
+fn multi_line_string(
+    multi_line: geo_types::MultiLineString<f64>,
+    srid: Option<u32>,
+) -> MultiLineString<Point> {
+    MultiLineString {
+        lines: multi_line
+            .0
+            .into_iter()
+            .map(|l| line_string(l, srid))
+            .collect(),
+        srid,
+    }
+}
+
+impl_from_geo_types!(MultiLineString, MultiLineString, multi_line_string);
+
+impl<T: PointT> MultiPolygon<T> {
+    /// Drops the Z and M values of the points, the SRIDs are kept.
+    pub fn to_2d(&self) -> MultiPolygon<Point> {
+        MultiPolygon {
+            polygons: self.polygons.iter().map(Polygon::to_2d).collect(),
+            srid: self.srid,
+        }
+    }
+
+    fn check_2d(&self) -> Result<(), GeoTypesError> {
+        self.polygons.iter().try_for_each(Polygon::check_2d)
+    }
+}
+
+impl From<MultiPolygon<Point>> for geo_types::MultiPolygon<f64> {
+    fn from(multi_polygon: MultiPolygon<Point>) -> Self {
+        multi_polygon
+            .polygons
+            .into_iter()
+            .map(geo_types::Polygon::from)
+            .collect()
+    }
+}
+
+impl_try_into_geo_types!(MultiPolygon, MultiPolygon);
+
+fn multi_polygon(
+    multi_polygon: geo_types::MultiPolygon<f64>,
+    srid: Option<u32>,
+) -> Result<MultiPolygon<Point>, GeoTypesError> {
+    Ok(MultiPolygon {
+        polygons: multi_polygon
+            .0
+            .into_iter()
+            .map(|p| polygon(p, srid))
+            .collect::<Result<_, _>>()?,
+        srid,
+    })
+}
+
+impl_try_from_geo_types!(MultiPolygon, MultiPolygon, multi_polygon);
+
+impl<T: PointT> GeometryCollection<T> {
+    /// Drops the Z and M values of the points, the SRIDs are kept.
+    pub fn to_2d(&self) -> GeometryCollection<Point> {
+        GeometryCollection {
+            geometries: self
+                .geometries
+                .iter()
+                .map(GeometryContainer::to_2d)
+                .collect(),
+            srid: self.srid,
+        }
+    }
+
+    fn check_2d(&self) -> Result<(), GeoTypesError> {
+        self.geometries
+            .iter()
+            .try_for_each(GeometryContainer::check_2d)
+    }
+}
+
+impl From<GeometryCollection<Point>> for geo_types::GeometryCollection<f64> {
+    fn from(collection: GeometryCollection<Point>) -> Self {
+        geo_types::GeometryCollection(collection.geometries.into_iter().map(Into::into).collect())
+    }
+}
+
+impl_try_into_geo_types!(GeometryCollection, GeometryCollection);
+
+fn geometry_collection(
+    collection: geo_types::GeometryCollection<f64>,
+    srid: Option<u32>,
+) -> Result<GeometryCollection<Point>, GeoTypesError> {
+    Ok(GeometryCollection {
+        geometries: collection
+            .0
+            .into_iter()
+            .map(|g| geometry(g, srid))
+            .collect::<Result<_, _>>()?,
+        srid,
+    })
+}
+
+impl_try_from_geo_types!(GeometryCollection, GeometryCollection, geometry_collection);
+
+impl<T: PointT> GeometryContainer<T> {
+    /// Drops the Z and M values of the points, the SRIDs are kept.
+    pub fn to_2d(&self) -> GeometryContainer<Point> {
+        match self {
+            GeometryContainer::Point(p) => GeometryContainer::Point(point_2d(p)),
+            GeometryContainer::LineString(g) => GeometryContainer::LineString(g.to_2d()),
+            GeometryContainer::Polygon(g) => GeometryContainer::Polygon(g.to_2d()),
+            GeometryContainer::MultiPoint(g) => GeometryContainer::MultiPoint(g.to_2d()),
+            GeometryContainer::MultiLineString(g) => GeometryContainer::MultiLineString(g.to_2d()),
+            GeometryContainer::MultiPolygon(g) => GeometryContainer::MultiPolygon(g.to_2d()),
+            GeometryContainer::GeometryCollection(g) => {
+                GeometryContainer::GeometryCollection(g.to_2d())
+            }
+        }
+    }
+
+    fn check_2d(&self) -> Result<(), GeoTypesError> {
+        match self {
+            GeometryContainer::Point(p) => check_2d(&[*p]),
+            GeometryContainer::LineString(g) => g.check_2d(),
+            GeometryContainer::Polygon(g) => g.check_2d(),
+            GeometryContainer::MultiPoint(g) => g.check_2d(),
+            GeometryContainer::MultiLineString(g) => g.check_2d(),
+            GeometryContainer::MultiPolygon(g) => g.check_2d(),
+            GeometryContainer::GeometryCollection(g) => g.check_2d(),
+        }
+    }
+}
+
+impl From<GeometryContainer<Point>> for geo_types::Geometry<f64> {
+    fn from(geometry: GeometryContainer<Point>) -> Self {
+        match geometry {
+            GeometryContainer::Point(p) => geo_types::Geometry::Point(geo_types::Point(coord(&p))),
+            GeometryContainer::LineString(g) => geo_types::Geometry::LineString(g.into()),
+            GeometryContainer::Polygon(g) => geo_types::Geometry::Polygon(g.into()),
+            GeometryContainer::MultiPoint(g) => geo_types::Geometry::MultiPoint(g.into()),
+            GeometryContainer::MultiLineString(g) => geo_types::Geometry::MultiLineString(g.into()),
+            GeometryContainer::MultiPolygon(g) => geo_types::Geometry::MultiPolygon(g.into()),
+            GeometryContainer::GeometryCollection(g) => {
+                geo_types::Geometry::GeometryCollection(g.into())
+            }
+        }
+    }
+}
+
+impl_try_into_geo_types!(GeometryContainer, Geometry);
+
+/// `Line` becomes a `LineString`, `Rect` and `Triangle` become a `Polygon`.
+fn geometry(
+    geometry: geo_types::Geometry<f64>,
+    srid: Option<u32>,
+) -> Result<GeometryContainer<Point>, GeoTypesError> {
+    Ok(match geometry {
+        geo_types::Geometry::Point(p) => GeometryContainer::Point(point(p.0, srid)),
+        geo_types::Geometry::Line(l) => GeometryContainer::LineString(line_string(l.into(), srid)),
+        geo_types::Geometry::LineString(l) => GeometryContainer::LineString(line_string(l, srid)),
+        geo_types::Geometry::Polygon(p) => GeometryContainer::Polygon(polygon(p, srid)?),
+        geo_types::Geometry::MultiPoint(g) => GeometryContainer::MultiPoint(multi_point(g, srid)),
+        geo_types::Geometry::MultiLineString(g) => {
+            GeometryContainer::MultiLineString(multi_line_string(g, srid))
+        }
+        geo_types::Geometry::MultiPolygon(g) => {
+            GeometryContainer::MultiPolygon(multi_polygon(g, srid)?)
+        }
+        geo_types::Geometry::GeometryCollection(g) => {
+            GeometryContainer::GeometryCollection(geometry_collection(g, srid)?)
+        }
+        geo_types::Geometry::Rect(r) => GeometryContainer::Polygon(polygon(r.to_polygon(), srid)?),
+        geo_types::Geometry::Triangle(t) => {
+            GeometryContainer::Polygon(polygon(t.to_polygon(), srid)?)
+        }
+    })
+}
+
+impl_try_from_geo_types!(GeometryContainer, Geometry, geometry);
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use geo_types::{coord, line_string, point, polygon};
+
+    use super::*;
+
+    fn square<T: PointT>(srid: Option<u32>, z: Option<f64>, closed: bool) -> Polygon<T> {
+        let mut exterior = vec![(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)];
+        if closed {
+            exterior.push((0.0, 0.0));
+        }
+        let interior = vec![(1.0, 1.0), (2.0, 1.0), (1.0, 2.0), (1.0, 1.0)];
+        Polygon {
+            rings: vec![exterior, interior]
+                .into_iter()
+                .map(|ring| {
+                    ring.into_iter()
+                        .map(|(x, y)| T::new_point(x, y, srid, z, None).unwrap())
+                        .collect()
+                })
+                .collect(),
+            srid,
+        }
+    }
+
+    #[test]
+    fn test_polygon_rings() {
+        let expected = polygon!(
+            exterior: [(x: 0.0, y: 0.0), (x: 4.0, y: 0.0), (x: 4.0, y: 4.0), (x: 0.0, y: 4.0)],
+            interiors: [[(x: 1.0, y: 1.0), (x: 2.0, y: 1.0), (x: 1.0, y: 2.0)]],
+        );
+        let geo: geo_types::Polygon<f64> = square::<Point>(Some(4326), None, false).into();
+        assert_eq!(expected, geo);
+        let polygon: Polygon<Point> = geo.try_into().unwrap();
+        assert_eq!(square(None, None, true), polygon);
+
+        let empty: geo_types::Polygon<f64> = Polygon::<Point>::new(None).into();
+        assert_eq!(polygon!(), empty);
+        let empty: Polygon<Point> = empty.try_into().unwrap();
+        assert_eq!(Polygon::new(None), empty);
+    }
+
+    #[test]
+    fn test_interiors_without_exterior() {
+        let geo = geo_types::Polygon::new(
+            line_string![],
+            vec![line_string![(x: 1.0, y: 1.0), (x: 2.0, y: 1.0), (x: 1.0, y: 2.0)]],
+        );
+        assert_eq!(
+            Err(GeoTypesError::InteriorsWithoutExterior),
+            Polygon::<Point>::try_from(geo.clone())
+        );
+        let geo = geo_types::Geometry::MultiPolygon(geo_types::MultiPolygon(vec![geo]));
+        assert!(Srid::<GeometryContainer<Point>, 4326>::try_from(geo).is_err());
+    }
+
+    #[test]
+    fn test_dropped_dimensions() {
+        let geo = geo_types::Point::from(PointZM::new(1.0, 2.0, 3.0, 4.0, Some(4326)).to_2d());
+        assert_eq!(point!(x: 1.0, y: 2.0), geo);
+        let polygon = square::<PointZ>(Some(4326), Some(1.0), true).to_2d();
+        assert_eq!(square::<Point>(Some(4326), None, true), polygon);
+        assert_eq!(
+            geo_types::Polygon::from(square::<Point>(None, None, true)),
+            polygon.into()
+        );
+    }
+
+    #[test]
+    fn test_rejected_dimensions() {
+        assert_eq!(
+            Err(GeoTypesError::ZOrM {
+                z: Some(3.0),
+                m: None
+            }),
+            geo_types::Point::try_from(PointZ::new(1.0, 2.0, 3.0, None))
+        );
+        assert!(geo_types::Coord::try_from(PointM::new(1.0, 2.0, 3.0, None)).is_err());
+        // NaN is a missing value, which is not lost
+        assert_eq!(
+            Ok(point!(x: 1.0, y: 2.0)),
+            geo_types::Point::try_from(PointZM::new(1.0, 2.0, f64::NAN, f64::NAN, None))
+        );
+        assert!(geo_types::Polygon::try_from(square::<PointZ>(None, Some(1.0), true)).is_err());
+        assert_eq!(
+            Ok(geo_types::Polygon::from(square::<Point>(None, None, true))),
+            geo_types::Polygon::try_from(square::<PointZ>(None, Some(f64::NAN), true))
+        );
+        let mut collection = GeometryCollection::new(None);
+        collection.add_geometry(GeometryContainer::Point(PointM::new(1.0, 2.0, 3.0, None)));
+        assert!(
+            geo_types::Geometry::try_from(GeometryContainer::GeometryCollection(collection))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_srid_reattached() {
+        let line: Srid<LineString<Point>, 4326> =
+            line_string![(x: 1.0, y: 2.0), (x: 3.0, y: 4.0)].into();
+        assert_eq!(Some(4326), line.srid);
+        assert!(line.points.iter().all(|p| p.srid == Some(4326)));
+
+        let geometry =
+            geo_types::Geometry::GeometryCollection(geo_types::GeometryCollection(vec![
+                geo_types::Geometry::Point(point!(x: 1.0, y: 2.0)),
+                geo_types::Geometry::Rect(geo_types::Rect::new(
+                    coord! { x: 0.0, y: 0.0 },
+                    coord! { x: 1.0, y: 1.0 },
+                )),
+            ]));
+        let container: Srid<GeometryContainer<Point>, 3857> = geometry.clone().try_into().unwrap();
+        let collection = match container.into_inner() {
+            GeometryContainer::GeometryCollection(collection) => collection,
+            other => panic!("unexpected geometry {:?}", other),
+        };
+        assert_eq!(Some(3857), collection.srid);
+        assert_eq!(
+            GeometryContainer::Point(Point::new(1.0, 2.0, Some(3857))),
+            collection.geometries[0]
+        );
+        match &collection.geometries[1] {
+            GeometryContainer::Polygon(polygon) => {
+                assert_eq!(Some(3857), polygon.srid);
+                assert_eq!(5, polygon.rings[0].len());
+            }
+            other => panic!("unexpected geometry {:?}", other),
+        }
+
+        let round_trip: geo_types::Geometry<f64> =
+            GeometryContainer::GeometryCollection(collection).into();
+        let expected: GeometryContainer<Point> = geometry.try_into().unwrap();
+        assert_eq!(geo_types::Geometry::from(expected), round_trip);
+    }
+
+    #[test]
+    fn test_multi_geometries() {
+        let mut multi_polygon = MultiPolygon::new(Some(4326));
+        multi_polygon
+            .polygons
+            .push(square::<Point>(Some(4326), None, true));
+        multi_polygon
+            .polygons
+            .push(square::<Point>(Some(4326), None, true));
+        let geo: geo_types::MultiPolygon<f64> = multi_polygon.into();
+        assert_eq!(2, geo.0.len());
+        let multi_polygon: MultiPolygon<Point> = geo.try_into().unwrap();
+        assert_eq!(None, multi_polygon.srid);
+        assert_eq!(square(None, None, true), multi_polygon.polygons[1]);
+
+        let geo = geo_types::MultiLineString(vec![
+            line_string![(x: 1.0, y: 2.0), (x: 3.0, y: 4.0)],
+            line_string![(x: 5.0, y: 6.0), (x: 7.0, y: 8.0)],
+        ]);
+        let multi_line: MultiLineString<Point> = geo.clone().into();
+        assert_eq!(Point::new(7.0, 8.0, None), multi_line.lines[1].points[1]);
+        assert_eq!(geo, multi_line.into());
+
+        let geo = geo_types::MultiPoint(vec![point!(x: 1.0, y: 2.0), point!(x: 3.0, y: 4.0)]);
+        let multi_point: MultiPoint<Point> = geo.clone().into();
+        assert_eq!(2, multi_point.points.len());
+        assert_eq!(geo, multi_point.into());
+    }
+}
@@ -0,0 +1,207 @@
+//! Submodule implementing the `ToSql` and `FromSql` traits of the `postgres-types` crate, used by
+//! `postgres` and `tokio-postgres`, for the `geometry` and `geography` Postgres types.
+#![cfg(feature = "postgres-types")]
+
+use postgres_types::{IsNull, Type};
+
+use crate::write_to_read_from_sql::{ReadFromSql, WriteToSql};
+
+type BoxedError = Box<dyn std::error::Error + Sync + Send>;
+
+fn accepts(ty: &Type) -> bool {
+    matches!(ty.name(), "geometry" | "geography")
+}
+
+fn to_sql<T>(value: &T, out: &mut bytes::BytesMut) -> Result<IsNull, BoxedError>
+where
+    T: WriteToSql,
+{
+    use bytes::BufMut;
+    value.write_to_sql(true, &mut out.writer())?;
+    Ok(IsNull::No)
+}
+
+fn from_sql<T>(raw: &[u8]) -> Result<T, BoxedError>
+where
+    T: ReadFromSql,
+{
+    Ok(T::read_from_sql(raw)?)
+}
+
+/// Macro implementing the `postgres_types` traits for the geometry types generic over the point type.
+macro_rules! impl_postgres_types {
+	($($type:ty),+) => {
+		$(
+			impl<P> postgres_types::ToSql for $type
+			where
+				P: crate::types::PointT,
+			{
+				fn to_sql(&self, _ty: &Type, out: &mut bytes::BytesMut) -> Result<IsNull, BoxedError> {
+					to_sql(self, out)
+				}
+
+				fn accepts(ty: &Type) -> bool {
+					accepts(ty)
+				}
+
+				postgres_types::to_sql_checked!();
+			}
+
+			impl<'a, P> postgres_types::FromSql<'a> for $type
+			where
+				P: crate::types::PointT,
+			{
+				fn from_sql(_ty: &Type, raw: &'a [u8]) -> Result<Self, BoxedError> {
+					from_sql(raw)
+				}
+
+				fn accepts(ty: &Type) -> bool {
+					accepts(ty)
+				}
+			}
+		)*
+	};
+}
+
+/// Macro implementing the `postgres_types` traits for the point types.
+macro_rules! impl_point_postgres_types {
+	($($type:ty),+) => {
+		$(
+			impl postgres_types::ToSql for $type {
+				fn to_sql(&self, _ty: &Type, out: &mut bytes::BytesMut) -> Result<IsNull, BoxedError> {
+					to_sql(self, out)
+				}
+
+				fn accepts(ty: &Type) -> bool {
+					accepts(ty)
+				}
+
+				postgres_types::to_sql_checked!();
+			}
+
+			impl<'a> postgres_types::FromSql<'a> for $type {
+				fn from_sql(_ty: &Type, raw: &'a [u8]) -> Result<Self, BoxedError> {
+					from_sql(raw)
+				}
+
+				fn accepts(ty: &Type) -> bool {
+					accepts(ty)
+				}
+			}
+		)*
+	};
+}
+
+impl_postgres_types!(
+    crate::types::MultiPoint<P>,
+    crate::types::MultiLineString<P>,
+    crate::types::MultiPolygon<P>,
+    crate::types::GeometryCollection<P>,
+    crate::types::GeometryContainer<P>,
+    crate::types::LineString<P>,
+    crate::types::Polygon<P>
+);
+
+impl_point_postgres_types!(
+    crate::types::Point,
+    crate::types::PointZ,
+    crate::types::PointM,
+    crate::types::PointZM,
+    crate::types::FlatGeometry
+);
+
+impl postgres_types::ToSql for crate::types::EwkbRef<'_> {
+    fn to_sql(&self, _ty: &Type, out: &mut bytes::BytesMut) -> Result<IsNull, BoxedError> {
+        out.extend_from_slice(self.as_bytes());
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        accepts(ty)
+    }
+
+    postgres_types::to_sql_checked!();
+}
+
+/// Borrows the bytes of the row.
+impl<'a> postgres_types::FromSql<'a> for crate::types::EwkbRef<'a> {
+    fn from_sql(_ty: &Type, raw: &'a [u8]) -> Result<Self, BoxedError> {
+        Ok(Self::new(raw))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        accepts(ty)
+    }
+}
+
+impl postgres_types::ToSql for crate::types::EwkbBuf {
+    fn to_sql(&self, ty: &Type, out: &mut bytes::BytesMut) -> Result<IsNull, BoxedError> {
+        self.as_ewkb_ref().to_sql(ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        accepts(ty)
+    }
+
+    postgres_types::to_sql_checked!();
+}
+
+impl<'a> postgres_types::FromSql<'a> for crate::types::EwkbBuf {
+    fn from_sql(_ty: &Type, raw: &'a [u8]) -> Result<Self, BoxedError> {
+        Ok(Self::new(raw.to_vec()))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        accepts(ty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use postgres_types::{FromSql, ToSql, Type};
+
+    use crate::types::{EwkbRef, GeometryContainer, LineString, Point, PointZ};
+
+    fn geometry_type() -> Type {
+        Type::new(
+            "geometry".to_string(),
+            0,
+            postgres_types::Kind::Simple,
+            "public".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let ty = geometry_type();
+        let mut line = LineString::new(Some(4326));
+        line.add_point(Point::new(1.0, 2.0, Some(4326))).unwrap();
+        line.add_point(Point::new(3.0, 4.0, Some(4326))).unwrap();
+        let container = GeometryContainer::LineString(line);
+        let mut buffer = bytes::BytesMut::new();
+        container.to_sql_checked(&ty, &mut buffer).unwrap();
+        assert_eq!(
+            container,
+            GeometryContainer::<Point>::from_sql(&ty, &buffer).unwrap()
+        );
+        let point = PointZ::new(1.0, 2.0, 3.0, None);
+        let mut buffer = bytes::BytesMut::new();
+        point.to_sql_checked(&ty, &mut buffer).unwrap();
+        assert_eq!(point, PointZ::from_sql(&ty, &buffer).unwrap());
+        let ewkb = EwkbRef::from_sql(&ty, &buffer).unwrap();
+        assert_eq!(buffer.as_ptr(), ewkb.as_bytes().as_ptr());
+        assert_eq!(point, ewkb.decode::<PointZ>().unwrap());
+    }
+
+    #[test]
+    fn test_accepts() {
+        assert!(<Point as ToSql>::accepts(&geometry_type()));
+        assert!(<Point as FromSql>::accepts(&Type::new(
+            "geography".to_string(),
+            0,
+            postgres_types::Kind::Simple,
+            "public".to_string(),
+        )));
+        assert!(!<Point as ToSql>::accepts(&Type::BYTEA));
+    }
+}
@@ -80,5 +80,7 @@ impl_point_to_sql_geography!(
     crate::types::Point,
     crate::types::PointZ,
     crate::types::PointM,
-    crate::types::PointZM
+    crate::types::PointZM,
+    crate::types::EwkbBuf,
+    crate::types::FlatGeometry
 );
@@ -1,5 +1,19 @@
 //! Submodule implementing with a macro the `ToSql` of the Geometry variant for several types.
 
+/// Checks the SRID of a geometry read from or written to a `TypedGeometry` column.
+#[cfg(feature = "diesel")]
+fn check_srid(actual: Option<u32>, expected: u32) -> Result<(), crate::errors::Error> {
+    if actual != Some(expected) {
+        return Err(
+            crate::errors::Error::IncompatibleSpatialReferenceSystemIdentifier {
+                expected: Some(expected),
+                actual,
+            },
+        );
+    }
+    Ok(())
+}
+
 /// Macro implementing the `ToSql` and `FromSql` trait for the Geometry variant.
 macro_rules! impl_to_sql_geometry {
 	($($type:ty),+) => {
@@ -59,6 +73,189 @@ macro_rules! impl_to_sql_geometry {
 					Ok(diesel::serialize::IsNull::No)
 				}
 			}
+
+			#[cfg(feature = "sqlite")]
+			impl<P> diesel::deserialize::FromSql<crate::sql_types::SpatiaLiteGeometry, diesel::sqlite::Sqlite> for $type
+			where
+				P: crate::types::PointT,
+			{
+				fn from_sql(
+					mut bytes: diesel::sqlite::SqliteValue<'_, '_, '_>,
+				) -> diesel::deserialize::Result<Self> {
+					use crate::write_to_read_from_sql::ReadFromSql;
+					let ewkb = crate::spatialite::spatialite_to_ewkb(bytes.read_blob())?;
+					Ok(Self::read_from_sql(&ewkb)?)
+				}
+			}
+
+			#[cfg(feature = "sqlite")]
+			impl<P> diesel::serialize::ToSql<crate::sql_types::SpatiaLiteGeometry, diesel::sqlite::Sqlite> for $type
+			where
+				P: crate::types::PointT,
+			{
+				fn to_sql(
+					&self,
+					out: &mut diesel::serialize::Output<diesel::sqlite::Sqlite>,
+				) -> diesel::serialize::Result {
+					use crate::write_to_read_from_sql::WriteToSql;
+					let mut buffer = Vec::new();
+					self.write_to_sql(true, &mut buffer)?;
+					out.set_value(crate::spatialite::ewkb_to_spatialite(&buffer)?);
+					Ok(diesel::serialize::IsNull::No)
+				}
+			}
+
+			#[cfg(feature = "sqlite")]
+			impl<P> diesel::deserialize::FromSql<crate::sql_types::GeoPackageGeometry, diesel::sqlite::Sqlite> for $type
+			where
+				P: crate::types::PointT,
+			{
+				fn from_sql(
+					mut bytes: diesel::sqlite::SqliteValue<'_, '_, '_>,
+				) -> diesel::deserialize::Result<Self> {
+					use crate::write_to_read_from_sql::ReadFromSql;
+					let ewkb = crate::geopackage::gpkg_to_ewkb(bytes.read_blob())?;
+					Ok(Self::read_from_sql(&ewkb)?)
+				}
+			}
+
+			#[cfg(feature = "sqlite")]
+			impl<P> diesel::serialize::ToSql<crate::sql_types::GeoPackageGeometry, diesel::sqlite::Sqlite> for $type
+			where
+				P: crate::types::PointT,
+			{
+				fn to_sql(
+					&self,
+					out: &mut diesel::serialize::Output<diesel::sqlite::Sqlite>,
+				) -> diesel::serialize::Result {
+					use crate::write_to_read_from_sql::WriteToSql;
+					let mut buffer = Vec::new();
+					self.write_to_sql(true, &mut buffer)?;
+					out.set_value(crate::geopackage::ewkb_to_gpkg(&buffer)?);
+					Ok(diesel::serialize::IsNull::No)
+				}
+			}
+
+			#[cfg(feature = "mysql")]
+			impl<P> diesel::deserialize::FromSql<crate::sql_types::Geometry, diesel::mysql::Mysql> for $type
+			where
+				P: crate::types::PointT,
+			{
+				fn from_sql(bytes: diesel::mysql::MysqlValue<'_>) -> diesel::deserialize::Result<Self> {
+					use crate::write_to_read_from_sql::ReadFromSql;
+					Ok(Self::read_from_mysql(bytes.as_bytes())?)
+				}
+			}
+
+			#[cfg(feature = "mysql")]
+			impl<P> diesel::serialize::ToSql<crate::sql_types::Geometry, diesel::mysql::Mysql> for $type
+			where
+				P: crate::types::PointT,
+			{
+				fn to_sql(
+					&self,
+					out: &mut diesel::serialize::Output<diesel::mysql::Mysql>,
+				) -> diesel::serialize::Result {
+					use crate::write_to_read_from_sql::WriteToSql;
+					self.write_to_mysql(out)?;
+					Ok(diesel::serialize::IsNull::No)
+				}
+			}
+
+			#[cfg(feature = "diesel")]
+			impl<P, DB, K: 'static, const SRID: u32> diesel::deserialize::FromSql<crate::sql_types::TypedGeometry<K, SRID>, DB> for $type
+			where
+				P: crate::types::PointT,
+				DB: diesel::backend::Backend,
+				K: crate::sql_types::TypedGeometryKind<Self>,
+				Self: diesel::deserialize::FromSql<crate::sql_types::Geometry, DB>,
+			{
+				fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+					let value: Self = diesel::deserialize::FromSql::<crate::sql_types::Geometry, DB>::from_sql(bytes)?;
+					check_srid(crate::ewkb::EwkbSerializable::srid(&value), SRID)?;
+					Ok(value)
+				}
+			}
+
+			#[cfg(feature = "diesel")]
+			impl<P, DB, K: 'static, const SRID: u32> diesel::serialize::ToSql<crate::sql_types::TypedGeometry<K, SRID>, DB> for $type
+			where
+				P: crate::types::PointT,
+				DB: diesel::backend::Backend,
+				K: crate::sql_types::TypedGeometryKind<Self>,
+				Self: diesel::serialize::ToSql<crate::sql_types::Geometry, DB>,
+			{
+				fn to_sql<'b>(
+					&'b self,
+					out: &mut diesel::serialize::Output<'b, '_, DB>,
+				) -> diesel::serialize::Result {
+					check_srid(crate::ewkb::EwkbSerializable::srid(self), SRID)?;
+					diesel::serialize::ToSql::<crate::sql_types::Geometry, DB>::to_sql(self, out)
+				}
+			}
+
+			#[cfg(feature = "diesel")]
+			impl<P, DB, K: 'static, const SRID: u32> diesel::serialize::ToSql<diesel::sql_types::Nullable<crate::sql_types::TypedGeometry<K, SRID>>, DB> for $type
+			where
+				P: crate::types::PointT,
+				DB: diesel::backend::Backend,
+				Self: diesel::serialize::ToSql<crate::sql_types::TypedGeometry<K, SRID>, DB>,
+			{
+				fn to_sql<'b>(
+					&'b self,
+					out: &mut diesel::serialize::Output<'b, '_, DB>,
+				) -> diesel::serialize::Result {
+					diesel::serialize::ToSql::<crate::sql_types::TypedGeometry<K, SRID>, DB>::to_sql(self, out)
+				}
+			}
+
+			#[cfg(feature = "diesel")]
+			impl<P, K: 'static + crate::sql_types::TypedGeometryKind<Self>, const SRID: u32> diesel::expression::AsExpression<crate::sql_types::TypedGeometry<K, SRID>> for $type
+			where
+				P: crate::types::PointT,
+			{
+				type Expression = crate::sql_types::TypedBind<crate::sql_types::TypedGeometry<K, SRID>, Self>;
+
+				fn as_expression(self) -> Self::Expression {
+					crate::sql_types::TypedBind::new(self)
+				}
+			}
+
+			#[cfg(feature = "diesel")]
+			impl<'a, P, K: 'static + crate::sql_types::TypedGeometryKind<$type>, const SRID: u32> diesel::expression::AsExpression<crate::sql_types::TypedGeometry<K, SRID>> for &'a $type
+			where
+				P: crate::types::PointT,
+			{
+				type Expression = crate::sql_types::TypedBind<crate::sql_types::TypedGeometry<K, SRID>, Self>;
+
+				fn as_expression(self) -> Self::Expression {
+					crate::sql_types::TypedBind::new(self)
+				}
+			}
+
+			#[cfg(feature = "diesel")]
+			impl<P, K: 'static + crate::sql_types::TypedGeometryKind<Self>, const SRID: u32> diesel::expression::AsExpression<diesel::sql_types::Nullable<crate::sql_types::TypedGeometry<K, SRID>>> for $type
+			where
+				P: crate::types::PointT,
+			{
+				type Expression = crate::sql_types::TypedBind<diesel::sql_types::Nullable<crate::sql_types::TypedGeometry<K, SRID>>, Self>;
+
+				fn as_expression(self) -> Self::Expression {
+					crate::sql_types::TypedBind::new(self)
+				}
+			}
+
+			#[cfg(feature = "diesel")]
+			impl<'a, P, K: 'static + crate::sql_types::TypedGeometryKind<$type>, const SRID: u32> diesel::expression::AsExpression<diesel::sql_types::Nullable<crate::sql_types::TypedGeometry<K, SRID>>> for &'a $type
+			where
+				P: crate::types::PointT,
+			{
+				type Expression = crate::sql_types::TypedBind<diesel::sql_types::Nullable<crate::sql_types::TypedGeometry<K, SRID>>, Self>;
+
+				fn as_expression(self) -> Self::Expression {
+					crate::sql_types::TypedBind::new(self)
+				}
+			}
 		)+
 	};
 }
@@ -115,6 +312,162 @@ macro_rules! impl_point_to_sql_geometry {
 					Ok(diesel::serialize::IsNull::No)
 				}
 			}
+
+			#[cfg(feature = "sqlite")]
+			impl diesel::deserialize::FromSql<crate::sql_types::SpatiaLiteGeometry, diesel::sqlite::Sqlite> for $type
+			{
+				fn from_sql(
+					mut bytes: diesel::sqlite::SqliteValue<'_, '_, '_>,
+				) -> diesel::deserialize::Result<Self> {
+					use crate::write_to_read_from_sql::ReadFromSql;
+					let ewkb = crate::spatialite::spatialite_to_ewkb(bytes.read_blob())?;
+					Ok(Self::read_from_sql(&ewkb)?)
+				}
+			}
+
+			#[cfg(feature = "sqlite")]
+			impl diesel::serialize::ToSql<crate::sql_types::SpatiaLiteGeometry, diesel::sqlite::Sqlite> for $type
+			{
+				fn to_sql(
+					&self,
+					out: &mut diesel::serialize::Output<diesel::sqlite::Sqlite>,
+				) -> diesel::serialize::Result {
+					use crate::write_to_read_from_sql::WriteToSql;
+					let mut buffer = Vec::new();
+					self.write_to_sql(true, &mut buffer)?;
+					out.set_value(crate::spatialite::ewkb_to_spatialite(&buffer)?);
+					Ok(diesel::serialize::IsNull::No)
+				}
+			}
+
+			#[cfg(feature = "sqlite")]
+			impl diesel::deserialize::FromSql<crate::sql_types::GeoPackageGeometry, diesel::sqlite::Sqlite> for $type
+			{
+				fn from_sql(
+					mut bytes: diesel::sqlite::SqliteValue<'_, '_, '_>,
+				) -> diesel::deserialize::Result<Self> {
+					use crate::write_to_read_from_sql::ReadFromSql;
+					let ewkb = crate::geopackage::gpkg_to_ewkb(bytes.read_blob())?;
+					Ok(Self::read_from_sql(&ewkb)?)
+				}
+			}
+
+			#[cfg(feature = "sqlite")]
+			impl diesel::serialize::ToSql<crate::sql_types::GeoPackageGeometry, diesel::sqlite::Sqlite> for $type
+			{
+				fn to_sql(
+					&self,
+					out: &mut diesel::serialize::Output<diesel::sqlite::Sqlite>,
+				) -> diesel::serialize::Result {
+					use crate::write_to_read_from_sql::WriteToSql;
+					let mut buffer = Vec::new();
+					self.write_to_sql(true, &mut buffer)?;
+					out.set_value(crate::geopackage::ewkb_to_gpkg(&buffer)?);
+					Ok(diesel::serialize::IsNull::No)
+				}
+			}
+
+			#[cfg(feature = "mysql")]
+			impl diesel::deserialize::FromSql<crate::sql_types::Geometry, diesel::mysql::Mysql> for $type
+			{
+				fn from_sql(bytes: diesel::mysql::MysqlValue<'_>) -> diesel::deserialize::Result<Self> {
+					use crate::write_to_read_from_sql::ReadFromSql;
+					Ok(Self::read_from_mysql(bytes.as_bytes())?)
+				}
+			}
+
+			#[cfg(feature = "mysql")]
+			impl diesel::serialize::ToSql<crate::sql_types::Geometry, diesel::mysql::Mysql> for $type
+			{
+				fn to_sql(
+					&self,
+					out: &mut diesel::serialize::Output<diesel::mysql::Mysql>,
+				) -> diesel::serialize::Result {
+					use crate::write_to_read_from_sql::WriteToSql;
+					self.write_to_mysql(out)?;
+					Ok(diesel::serialize::IsNull::No)
+				}
+			}
+
+			#[cfg(feature = "diesel")]
+			impl<DB, K: 'static, const SRID: u32> diesel::deserialize::FromSql<crate::sql_types::TypedGeometry<K, SRID>, DB> for $type
+			where
+				DB: diesel::backend::Backend,
+				K: crate::sql_types::TypedGeometryKind<Self>,
+				Self: diesel::deserialize::FromSql<crate::sql_types::Geometry, DB>,
+			{
+				fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+					let value: Self = diesel::deserialize::FromSql::<crate::sql_types::Geometry, DB>::from_sql(bytes)?;
+					check_srid(crate::ewkb::EwkbSerializable::srid(&value), SRID)?;
+					Ok(value)
+				}
+			}
+
+			#[cfg(feature = "diesel")]
+			impl<DB, K: 'static, const SRID: u32> diesel::serialize::ToSql<crate::sql_types::TypedGeometry<K, SRID>, DB> for $type
+			where
+				DB: diesel::backend::Backend,
+				K: crate::sql_types::TypedGeometryKind<Self>,
+				Self: diesel::serialize::ToSql<crate::sql_types::Geometry, DB>,
+			{
+				fn to_sql<'b>(
+					&'b self,
+					out: &mut diesel::serialize::Output<'b, '_, DB>,
+				) -> diesel::serialize::Result {
+					check_srid(crate::ewkb::EwkbSerializable::srid(self), SRID)?;
+					diesel::serialize::ToSql::<crate::sql_types::Geometry, DB>::to_sql(self, out)
+				}
+			}
+
+			#[cfg(feature = "diesel")]
+			impl<DB, K: 'static, const SRID: u32> diesel::serialize::ToSql<diesel::sql_types::Nullable<crate::sql_types::TypedGeometry<K, SRID>>, DB> for $type
+			where
+				DB: diesel::backend::Backend,
+				Self: diesel::serialize::ToSql<crate::sql_types::TypedGeometry<K, SRID>, DB>,
+			{
+				fn to_sql<'b>(
+					&'b self,
+					out: &mut diesel::serialize::Output<'b, '_, DB>,
+				) -> diesel::serialize::Result {
+					diesel::serialize::ToSql::<crate::sql_types::TypedGeometry<K, SRID>, DB>::to_sql(self, out)
+				}
+			}
+
+			#[cfg(feature = "diesel")]
+			impl<K: 'static + crate::sql_types::TypedGeometryKind<Self>, const SRID: u32> diesel::expression::AsExpression<crate::sql_types::TypedGeometry<K, SRID>> for $type {
+				type Expression = crate::sql_types::TypedBind<crate::sql_types::TypedGeometry<K, SRID>, Self>;
+
+				fn as_expression(self) -> Self::Expression {
+					crate::sql_types::TypedBind::new(self)
+				}
+			}
+
+			#[cfg(feature = "diesel")]
+			impl<'a, K: 'static + crate::sql_types::TypedGeometryKind<$type>, const SRID: u32> diesel::expression::AsExpression<crate::sql_types::TypedGeometry<K, SRID>> for &'a $type {
+				type Expression = crate::sql_types::TypedBind<crate::sql_types::TypedGeometry<K, SRID>, Self>;
+
+				fn as_expression(self) -> Self::Expression {
+					crate::sql_types::TypedBind::new(self)
+				}
+			}
+
+			#[cfg(feature = "diesel")]
+			impl<K: 'static + crate::sql_types::TypedGeometryKind<Self>, const SRID: u32> diesel::expression::AsExpression<diesel::sql_types::Nullable<crate::sql_types::TypedGeometry<K, SRID>>> for $type {
+				type Expression = crate::sql_types::TypedBind<diesel::sql_types::Nullable<crate::sql_types::TypedGeometry<K, SRID>>, Self>;
+
+				fn as_expression(self) -> Self::Expression {
+					crate::sql_types::TypedBind::new(self)
+				}
+			}
+
+			#[cfg(feature = "diesel")]
+			impl<'a, K: 'static + crate::sql_types::TypedGeometryKind<$type>, const SRID: u32> diesel::expression::AsExpression<diesel::sql_types::Nullable<crate::sql_types::TypedGeometry<K, SRID>>> for &'a $type {
+				type Expression = crate::sql_types::TypedBind<diesel::sql_types::Nullable<crate::sql_types::TypedGeometry<K, SRID>>, Self>;
+
+				fn as_expression(self) -> Self::Expression {
+					crate::sql_types::TypedBind::new(self)
+				}
+			}
 		)+
 	};
 }
@@ -133,5 +486,6 @@ impl_point_to_sql_geometry!(
     crate::types::Point,
     crate::types::PointZ,
     crate::types::PointM,
-    crate::types::PointZM
+    crate::types::PointZM,
+    crate::types::FlatGeometry
 );
@@ -0,0 +1,243 @@
+//! Submodule implementing the `Type`, `Encode` and `Decode` traits of sqlx for Postgres, where the
+//! geometries are `geometry` or `geography` values, and for SQLite, where they are EWKB blobs.
+#![cfg(any(feature = "sqlx-postgres", feature = "sqlx-sqlite"))]
+
+use std::borrow::Cow;
+
+use sqlx::encode::IsNull;
+use sqlx::error::BoxDynError;
+
+use crate::write_to_read_from_sql::{ReadFromSql, WriteToSql};
+
+#[cfg(feature = "sqlx-postgres")]
+mod postgres {
+    use sqlx::postgres::{PgArgumentBuffer, PgTypeInfo, PgValueFormat, PgValueRef};
+    use sqlx::TypeInfo;
+
+    use super::*;
+
+    pub(super) fn type_info() -> PgTypeInfo {
+        PgTypeInfo::with_name("geometry")
+    }
+
+    pub(super) fn compatible(ty: &PgTypeInfo) -> bool {
+        matches!(ty.name(), "geometry" | "geography")
+    }
+
+    pub(super) fn encode_by_ref<T>(
+        value: &T,
+        buf: &mut PgArgumentBuffer,
+    ) -> Result<IsNull, BoxDynError>
+    where
+        T: WriteToSql,
+    {
+        value.write_to_sql(true, &mut **buf)?;
+        Ok(IsNull::No)
+    }
+
+    pub(super) fn encode_bytes(
+        bytes: &[u8],
+        buf: &mut PgArgumentBuffer,
+    ) -> Result<IsNull, BoxDynError> {
+        buf.extend_from_slice(bytes);
+        Ok(IsNull::No)
+    }
+
+    /// Borrows the binary format, or decodes the hex encoded EWKB of the text format.
+    pub(super) fn bytes(value: PgValueRef<'_>) -> Result<Cow<'_, [u8]>, BoxDynError> {
+        match value.format() {
+            PgValueFormat::Binary => Ok(Cow::Borrowed(value.as_bytes()?)),
+            PgValueFormat::Text => {
+                let hex = value.as_str()?;
+                let bytes = (0..hex.len())
+                    .step_by(2)
+                    .map(|i| {
+                        hex.get(i..i + 2)
+                            .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                            .ok_or("Invalid hex encoded EWKB")
+                    })
+                    .collect::<Result<Vec<u8>, _>>()?;
+                Ok(Cow::Owned(bytes))
+            }
+        }
+    }
+
+    pub(super) fn decode<T>(value: PgValueRef<'_>) -> Result<T, BoxDynError>
+    where
+        T: ReadFromSql,
+    {
+        Ok(T::read_from_sql(&bytes(value)?)?)
+    }
+}
+
+#[cfg(feature = "sqlx-sqlite")]
+mod sqlite {
+    use sqlx::sqlite::{SqliteArgumentValue, SqliteTypeInfo, SqliteValueRef};
+
+    use super::*;
+
+    pub(super) fn type_info() -> SqliteTypeInfo {
+        <Vec<u8> as sqlx::Type<sqlx::Sqlite>>::type_info()
+    }
+
+    pub(super) fn compatible(ty: &SqliteTypeInfo) -> bool {
+        <Vec<u8> as sqlx::Type<sqlx::Sqlite>>::compatible(ty)
+    }
+
+    pub(super) fn encode_by_ref<T>(
+        value: &T,
+        buf: &mut Vec<SqliteArgumentValue<'_>>,
+    ) -> Result<IsNull, BoxDynError>
+    where
+        T: WriteToSql,
+    {
+        let mut buffer = Vec::new();
+        value.write_to_sql(true, &mut buffer)?;
+        buf.push(SqliteArgumentValue::Blob(buffer.into()));
+        Ok(IsNull::No)
+    }
+
+    pub(super) fn encode_bytes(
+        bytes: &[u8],
+        buf: &mut Vec<SqliteArgumentValue<'_>>,
+    ) -> Result<IsNull, BoxDynError> {
+        buf.push(SqliteArgumentValue::Blob(bytes.to_vec().into()));
+        Ok(IsNull::No)
+    }
+
+    pub(super) fn bytes(value: SqliteValueRef<'_>) -> Result<Cow<'_, [u8]>, BoxDynError> {
+        Ok(Cow::Borrowed(
+            <&[u8] as sqlx::Decode<sqlx::Sqlite>>::decode(value)?,
+        ))
+    }
+
+    pub(super) fn decode<T>(value: SqliteValueRef<'_>) -> Result<T, BoxDynError>
+    where
+        T: ReadFromSql,
+    {
+        Ok(T::read_from_sql(&bytes(value)?)?)
+    }
+}
+
+/// Macro implementing the sqlx traits for one backend, `$($generics)*` declares the point type.
+macro_rules! impl_sqlx_backend {
+	($backend:ident, $db:ty, $type:ty, [$($generics:tt)*]) => {
+		impl<$($generics)*> sqlx::Type<$db> for $type {
+			fn type_info() -> <$db as sqlx::Database>::TypeInfo {
+				$backend::type_info()
+			}
+
+			fn compatible(ty: &<$db as sqlx::Database>::TypeInfo) -> bool {
+				$backend::compatible(ty)
+			}
+		}
+
+		impl<'q, $($generics)*> sqlx::Encode<'q, $db> for $type {
+			fn encode_by_ref(
+				&self,
+				buf: &mut <$db as sqlx::Database>::ArgumentBuffer<'q>,
+			) -> Result<IsNull, BoxDynError> {
+				$backend::encode_by_ref(self, buf)
+			}
+		}
+
+		impl<'r, $($generics)*> sqlx::Decode<'r, $db> for $type {
+			fn decode(value: <$db as sqlx::Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+				$backend::decode(value)
+			}
+		}
+	};
+}
+
+/// Macro implementing the sqlx traits of `EwkbRef` and `EwkbBuf` for one backend.
+macro_rules! impl_sqlx_ewkb {
+    ($backend:ident, $db:ty) => {
+        impl sqlx::Type<$db> for crate::types::EwkbRef<'_> {
+            fn type_info() -> <$db as sqlx::Database>::TypeInfo {
+                $backend::type_info()
+            }
+
+            fn compatible(ty: &<$db as sqlx::Database>::TypeInfo) -> bool {
+                $backend::compatible(ty)
+            }
+        }
+
+        impl<'q> sqlx::Encode<'q, $db> for crate::types::EwkbRef<'_> {
+            fn encode_by_ref(
+                &self,
+                buf: &mut <$db as sqlx::Database>::ArgumentBuffer<'q>,
+            ) -> Result<IsNull, BoxDynError> {
+                $backend::encode_bytes(self.as_bytes(), buf)
+            }
+        }
+
+        /// Borrows the bytes of the row, which fails on the hex encoded text format of Postgres.
+        impl<'r> sqlx::Decode<'r, $db> for crate::types::EwkbRef<'r> {
+            fn decode(value: <$db as sqlx::Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+                match $backend::bytes(value)? {
+                    Cow::Borrowed(bytes) => Ok(Self::new(bytes)),
+                    Cow::Owned(_) => {
+                        Err("EwkbRef can only borrow the binary format, use EwkbBuf".into())
+                    }
+                }
+            }
+        }
+
+        impl sqlx::Type<$db> for crate::types::EwkbBuf {
+            fn type_info() -> <$db as sqlx::Database>::TypeInfo {
+                $backend::type_info()
+            }
+
+            fn compatible(ty: &<$db as sqlx::Database>::TypeInfo) -> bool {
+                $backend::compatible(ty)
+            }
+        }
+
+        impl<'q> sqlx::Encode<'q, $db> for crate::types::EwkbBuf {
+            fn encode_by_ref(
+                &self,
+                buf: &mut <$db as sqlx::Database>::ArgumentBuffer<'q>,
+            ) -> Result<IsNull, BoxDynError> {
+                $backend::encode_bytes(self.as_ref(), buf)
+            }
+        }
+
+        impl<'r> sqlx::Decode<'r, $db> for crate::types::EwkbBuf {
+            fn decode(value: <$db as sqlx::Database>::ValueRef<'r>) -> Result<Self, BoxDynError> {
+                Ok(Self::new($backend::bytes(value)?.into_owned()))
+            }
+        }
+    };
+}
+
+#[cfg(feature = "sqlx-postgres")]
+impl_sqlx_ewkb!(postgres, sqlx::Postgres);
+#[cfg(feature = "sqlx-sqlite")]
+impl_sqlx_ewkb!(sqlite, sqlx::Sqlite);
+
+/// Macro implementing the sqlx traits for every enabled backend.
+macro_rules! impl_sqlx {
+	($([$($generics:tt)*] $type:ty),+) => {
+		$(
+			#[cfg(feature = "sqlx-postgres")]
+			impl_sqlx_backend!(postgres, sqlx::Postgres, $type, [$($generics)*]);
+			#[cfg(feature = "sqlx-sqlite")]
+			impl_sqlx_backend!(sqlite, sqlx::Sqlite, $type, [$($generics)*]);
+		)*
+	};
+}
+
+impl_sqlx!(
+    [P: crate::types::PointT] crate::types::MultiPoint<P>,
+    [P: crate::types::PointT] crate::types::MultiLineString<P>,
+    [P: crate::types::PointT] crate::types::MultiPolygon<P>,
+    [P: crate::types::PointT] crate::types::GeometryCollection<P>,
+    [P: crate::types::PointT] crate::types::GeometryContainer<P>,
+    [P: crate::types::PointT] crate::types::LineString<P>,
+    [P: crate::types::PointT] crate::types::Polygon<P>,
+    [] crate::types::Point,
+    [] crate::types::PointZ,
+    [] crate::types::PointM,
+    [] crate::types::PointZM,
+    [] crate::types::FlatGeometry
+);
@@ -1,5 +1,18 @@
 use std::fmt;
 
+pub use crate::ewkb::GeometryType;
+#[cfg(feature = "serde")]
+pub use crate::geojson::{
+    with_geojson_options, with_measure_policy, GeoJsonOptions, MeasurePolicy, SridPolicy,
+};
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use crate::geojson_feature::__private;
+#[cfg(feature = "serde")]
+pub use crate::geojson_feature::{FeatureGeometry, FeatureProperties, GeoJsonFeature};
+#[cfg(feature = "derive")]
+pub use postgis_diesel_derive::GeoJsonFeature;
+
 use crate::write_to_read_from_sql::ReadFromSql;
 use crate::write_to_read_from_sql::WriteToSql;
 
@@ -49,11 +62,20 @@ impl std::error::Error for PointConstructorError {}
 )]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geometry))]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geography))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::SpatiaLiteGeometry))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::GeoPackageGeometry))]
 #[cfg_attr(
     all(feature = "serde", not(feature = "serde_geojson")),
     derive(serde::Serialize, serde::Deserialize)
 )]
-#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "serde_geojson")),
+    derive(JsonSchema)
+)]
+#[cfg_attr(
+    all(feature = "utoipa", not(feature = "serde_geojson")),
+    derive(utoipa::ToSchema)
+)]
 pub struct Point {
     pub x: f64,
     pub y: f64,
@@ -81,11 +103,20 @@ pub struct Point {
 )]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geometry))]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geography))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::SpatiaLiteGeometry))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::GeoPackageGeometry))]
 #[cfg_attr(
     all(feature = "serde", not(feature = "serde_geojson")),
     derive(serde::Serialize, serde::Deserialize)
 )]
-#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "serde_geojson")),
+    derive(JsonSchema)
+)]
+#[cfg_attr(
+    all(feature = "utoipa", not(feature = "serde_geojson")),
+    derive(utoipa::ToSchema)
+)]
 pub struct PointZ {
     pub x: f64,
     pub y: f64,
@@ -114,13 +145,28 @@ pub struct PointZ {
 )]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geometry))]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geography))]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::SpatiaLiteGeometry))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::GeoPackageGeometry))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde_geojson")),
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "serde_geojson")),
+    derive(JsonSchema)
+)]
+#[cfg_attr(
+    all(feature = "utoipa", not(feature = "serde_geojson")),
+    derive(utoipa::ToSchema)
+)]
 pub struct PointM {
     pub x: f64,
     pub y: f64,
     pub m: f64,
-    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    #[cfg_attr(
+        all(feature = "serde", not(feature = "serde_geojson")),
+        serde(skip_serializing_if = "Option::is_none")
+    )]
     pub srid: Option<u32>,
 }
 
@@ -141,14 +187,29 @@ pub struct PointM {
 )]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geometry))]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geography))]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::SpatiaLiteGeometry))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::GeoPackageGeometry))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde_geojson")),
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "serde_geojson")),
+    derive(JsonSchema)
+)]
+#[cfg_attr(
+    all(feature = "utoipa", not(feature = "serde_geojson")),
+    derive(utoipa::ToSchema)
+)]
 pub struct PointZM {
     pub x: f64,
     pub y: f64,
     pub z: f64,
     pub m: f64,
-    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    #[cfg_attr(
+        all(feature = "serde", not(feature = "serde_geojson")),
+        serde(skip_serializing_if = "Option::is_none")
+    )]
     pub srid: Option<u32>,
 }
 
@@ -188,11 +249,20 @@ pub trait PointT: ReadFromSql + WriteToSql + Copy + core::fmt::Debug {
 )]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geometry))]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geography))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::SpatiaLiteGeometry))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::GeoPackageGeometry))]
 #[cfg_attr(
     all(feature = "serde", not(feature = "serde_geojson")),
     derive(serde::Serialize, serde::Deserialize)
 )]
-#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "serde_geojson")),
+    derive(JsonSchema)
+)]
+#[cfg_attr(
+    all(feature = "utoipa", not(feature = "serde_geojson")),
+    derive(utoipa::ToSchema)
+)]
 pub struct MultiPoint<T> {
     pub points: Vec<T>,
     #[cfg_attr(
@@ -219,11 +289,20 @@ pub struct MultiPoint<T> {
 )]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geometry))]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geography))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::SpatiaLiteGeometry))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::GeoPackageGeometry))]
 #[cfg_attr(
     all(feature = "serde", not(feature = "serde_geojson")),
     derive(serde::Serialize, serde::Deserialize)
 )]
-#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "serde_geojson")),
+    derive(JsonSchema)
+)]
+#[cfg_attr(
+    all(feature = "utoipa", not(feature = "serde_geojson")),
+    derive(utoipa::ToSchema)
+)]
 pub struct LineString<T> {
     pub points: Vec<T>,
     #[cfg_attr(
@@ -250,11 +329,20 @@ pub struct LineString<T> {
 )]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geometry))]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geography))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::SpatiaLiteGeometry))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::GeoPackageGeometry))]
 #[cfg_attr(
     all(feature = "serde", not(feature = "serde_geojson")),
     derive(serde::Serialize, serde::Deserialize)
 )]
-#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "serde_geojson")),
+    derive(JsonSchema)
+)]
+#[cfg_attr(
+    all(feature = "utoipa", not(feature = "serde_geojson")),
+    derive(utoipa::ToSchema)
+)]
 pub struct MultiLineString<T> {
     pub lines: Vec<LineString<T>>,
     #[cfg_attr(
@@ -281,11 +369,20 @@ pub struct MultiLineString<T> {
 )]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geometry))]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geography))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::SpatiaLiteGeometry))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::GeoPackageGeometry))]
 #[cfg_attr(
     all(feature = "serde", not(feature = "serde_geojson")),
     derive(serde::Serialize, serde::Deserialize)
 )]
-#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "serde_geojson")),
+    derive(JsonSchema)
+)]
+#[cfg_attr(
+    all(feature = "utoipa", not(feature = "serde_geojson")),
+    derive(utoipa::ToSchema)
+)]
 pub struct Polygon<T> {
     pub rings: Vec<Vec<T>>,
     #[cfg_attr(
@@ -312,11 +409,20 @@ pub struct Polygon<T> {
 )]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geometry))]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geography))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::SpatiaLiteGeometry))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::GeoPackageGeometry))]
 #[cfg_attr(
     all(feature = "serde", not(feature = "serde_geojson")),
     derive(serde::Serialize, serde::Deserialize)
 )]
-#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "serde_geojson")),
+    derive(JsonSchema)
+)]
+#[cfg_attr(
+    all(feature = "utoipa", not(feature = "serde_geojson")),
+    derive(utoipa::ToSchema)
+)]
 pub struct MultiPolygon<T> {
     pub polygons: Vec<Polygon<T>>,
     #[cfg_attr(
@@ -336,21 +442,16 @@ pub struct MultiPolygon<T> {
 )]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geometry))]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geography))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::SpatiaLiteGeometry))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::GeoPackageGeometry))]
 #[cfg_attr(
     all(feature = "serde", not(feature = "serde_geojson")),
-    derive(serde::Serialize)
+    derive(serde::Serialize, serde::Deserialize)
 )]
-#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 #[cfg_attr(
-    feature = "serde_geojson",
-    serde(
-        tag = "type",
-        bound(
-            deserialize = "T: crate::geojson::GeoJsonGeometry<f64> + PointT + serde::Deserialize<'de>"
-        )
-    )
+    all(feature = "schemars", not(feature = "serde_geojson")),
+    derive(JsonSchema)
 )]
-#[cfg_attr(feature = "schemars", derive(JsonSchema))]
 pub enum GeometryContainer<T> {
     Point(T),
     LineString(LineString<T>),
@@ -378,11 +479,16 @@ pub enum GeometryContainer<T> {
 )]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geometry))]
 #[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geography))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::SpatiaLiteGeometry))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::GeoPackageGeometry))]
 #[cfg_attr(
     all(feature = "serde", not(feature = "serde_geojson")),
     derive(serde::Serialize, serde::Deserialize)
 )]
-#[cfg_attr(feature = "schemars", derive(JsonSchema))]
+#[cfg_attr(
+    all(feature = "schemars", not(feature = "serde_geojson")),
+    derive(JsonSchema)
+)]
 pub struct GeometryCollection<T> {
     pub geometries: Vec<GeometryContainer<T>>,
     #[cfg_attr(
@@ -392,30 +498,192 @@ pub struct GeometryCollection<T> {
     pub srid: Option<u32>,
 }
 
-#[cfg(feature = "serde_geojson")]
-#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
-#[serde(
-    tag = "type",
-    bound(
-        deserialize = "T: crate::geojson::GeoJsonGeometry<f64> + PointT + serde::Deserialize<'de>, P: serde::Deserialize<'de>"
-    )
+/// Wrapper of a geometry which is always stored and loaded with the SRID `SRID`.
+///
+/// Loading a row with another SRID fails with
+/// [`IncompatibleSpatialReferenceSystemIdentifier`](crate::errors::Error::IncompatibleSpatialReferenceSystemIdentifier),
+/// writing a geometry without SRID sets it to `SRID`.
+/// ```
+/// #[macro_use] extern crate diesel;
+/// use postgis_diesel::types::{Point, Srid};
+/// #[derive(Queryable)]
+/// struct QueryableSridExample {
+///     id: i32,
+///     point: Srid<Point, 4326>,
+/// }
+/// let _point: Srid<Point, 4326> = Srid::new(Point::new(72.0, 64.0, None)).unwrap();
+/// assert!(Srid::<Point, 4326>::new(Point::new(72.0, 64.0, Some(3857))).is_err());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::deserialize::FromSqlRow, diesel::expression::AsExpression)
 )]
+#[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geometry))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geography))]
+pub struct Srid<T, const SRID: u32>(pub(crate) T);
+
+/// Columnar representation of a geometry, whose coordinates are stored one after the other in a
+/// single `Vec<f64>` with offset arrays delimiting the rings and the polygons, like GeoArrow.
+///
+/// Every coordinate is `x`, `y`, then `z` and `m` when the geometry has them. `ring_offsets` holds
+/// the index of the first coordinate of every ring of a polygon or line of a multi line string,
+/// followed by the number of coordinates. `polygon_offsets` holds the index in `ring_offsets` of
+/// the first ring of every polygon of a multi polygon, followed by the number of rings. Unused
+/// offsets are `[0]`. Geometry collections are not supported. Writing a geometry whose offsets or
+/// number of values break these rules fails, and the accessors like [`FlatGeometry::ring`] return
+/// `None`.
+/// ```
+/// #[macro_use] extern crate diesel;
+/// use postgis_diesel::types::FlatGeometry;
+/// #[derive(Queryable)]
+/// struct QueryableFlatGeometryExample {
+///     id: i32,
+///     geometry: FlatGeometry,
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::deserialize::FromSqlRow, diesel::expression::AsExpression)
+)]
+#[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geometry))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geography))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::SpatiaLiteGeometry))]
+#[cfg_attr(feature = "sqlite", diesel(sql_type = crate::sql_types::GeoPackageGeometry))]
+pub struct FlatGeometry {
+    pub geometry_type: GeometryType,
+    pub has_z: bool,
+    pub has_m: bool,
+    pub coordinates: Vec<f64>,
+    pub ring_offsets: Vec<usize>,
+    pub polygon_offsets: Vec<usize>,
+    pub srid: Option<u32>,
+}
+
+/// Borrowed view of an EWKB geometry, which reads the header and the coordinates straight from the
+/// bytes when they are asked for, without allocating.
+///
+/// It is loaded with `postgres`, `tokio-postgres` and `sqlx`, which lend the bytes of the row.
+/// Diesel does not, so [`EwkbBuf`] holds the bytes of a row loaded with Diesel.
+/// ```
+/// use postgis_diesel::types::{EwkbRef, GeometryType, LineString, Point};
+/// let mut line = LineString::new(Some(4326));
+/// line.add_points([Point::new(1.0, 2.0, Some(4326)), Point::new(3.0, 0.0, Some(4326))])
+///     .unwrap();
+/// let bytes = postgis_diesel::types::EwkbBuf::from_geometry(&line).unwrap();
+/// let ewkb: EwkbRef = bytes.as_ewkb_ref();
+/// assert_eq!(GeometryType::LineString, ewkb.geometry_type().unwrap());
+/// assert_eq!(Some(4326), ewkb.srid().unwrap());
+/// let xs: Vec<f64> = ewkb.points().unwrap().map(|coordinate| coordinate.x).collect();
+/// assert_eq!(vec![1.0, 3.0], xs);
+/// let bbox = ewkb.bbox().unwrap().unwrap();
+/// assert_eq!((1.0, 0.0, 3.0, 2.0), (bbox.xmin, bbox.ymin, bbox.xmax, bbox.ymax));
+/// assert_eq!(line, ewkb.decode::<LineString<Point>>().unwrap());
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct EwkbRef<'a> {
+    pub(crate) bytes: &'a [u8],
+}
+
+/// Owned EWKB bytes of a geometry, loaded from a `Geometry` or `Geography` column without decoding
+/// them, and written back as they are.
+///
+/// [`EwkbBuf::as_ewkb_ref`] gives the [`EwkbRef`] view of the bytes.
+/// ```
+/// #[macro_use] extern crate diesel;
+/// use postgis_diesel::types::EwkbBuf;
+/// #[derive(Queryable)]
+/// struct QueryableEwkbExample {
+///     id: i32,
+///     geometry: EwkbBuf,
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::deserialize::FromSqlRow, diesel::expression::AsExpression)
+)]
+#[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geometry))]
+#[cfg_attr(feature = "diesel", diesel(sql_type = crate::sql_types::Geography))]
+pub struct EwkbBuf {
+    pub(crate) bytes: Vec<u8>,
+}
+
+/// Coordinate read by [`EwkbRef`], `z` and `m` are set when the geometry has them.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Coordinate {
+    pub x: f64,
+    pub y: f64,
+    pub z: Option<f64>,
+    pub m: Option<f64>,
+}
+
+/// Bounding box of the coordinates of a geometry.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BoundingBox {
+    pub xmin: f64,
+    pub ymin: f64,
+    pub xmax: f64,
+    pub ymax: f64,
+}
+
+/// Iterator over the coordinates of a point or a line string, or of a ring of a polygon.
+#[derive(Clone, Debug)]
+pub struct Coordinates<'a> {
+    pub(crate) bytes: &'a [u8],
+    pub(crate) big_endian: bool,
+    pub(crate) has_z: bool,
+    pub(crate) has_m: bool,
+}
+
+/// Iterator over the rings of a polygon, the outer ring first.
+#[derive(Clone, Debug)]
+pub struct Rings<'a> {
+    pub(crate) bytes: &'a [u8],
+    pub(crate) remaining: u32,
+    pub(crate) big_endian: bool,
+    pub(crate) has_z: bool,
+    pub(crate) has_m: bool,
+}
+
+/// Iterator over the geometries of a multi geometry or of a geometry collection.
+#[derive(Clone, Debug)]
+pub struct Parts<'a> {
+    pub(crate) bytes: &'a [u8],
+    pub(crate) remaining: u32,
+    pub(crate) depth: usize,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Feature<T, P: serde::Serialize> {
-    pub id: Option<String>,
+    pub id: Option<FeatureId>,
+    pub bbox: Option<Vec<f64>>,
     pub geometry: Option<GeometryContainer<T>>,
     pub properties: Option<P>,
+    /// Members which are not defined by GeoJSON, those named like a GeoJSON member are not
+    /// written.
+    pub foreign_members: serde_json::Map<String, serde_json::Value>,
 }
 
-#[cfg(feature = "serde_geojson")]
-#[derive(Clone, Debug, PartialEq, serde::Deserialize)]
-#[serde(
-    tag = "type",
-    bound(
-        deserialize = "T: crate::geojson::GeoJsonGeometry<f64> + PointT + serde::Deserialize<'de>, P: serde::Deserialize<'de>"
-    )
-)]
+#[cfg(feature = "serde")]
+#[derive(Clone, Debug, PartialEq)]
 pub struct FeatureCollection<T, P: serde::Serialize> {
+    pub bbox: Option<Vec<f64>>,
     pub features: Vec<Feature<T, P>>,
+    /// Members which are not defined by GeoJSON, those named like a GeoJSON member are not
+    /// written.
+    pub foreign_members: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Identifier of a [`Feature`], GeoJSON allows strings and numbers.
+#[cfg(feature = "serde")]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum FeatureId {
+    String(String),
+    Number(serde_json::Number),
 }
 
 #[cfg(test)]
@@ -447,4 +715,57 @@ mod tests {
             assert_eq!(expected_schema_for_value, schema_for_value_json);
         }
     }
+
+    #[cfg(feature = "utoipa")]
+    #[test]
+    fn test_geometry_openapi() {
+        use utoipa::OpenApi;
+
+        #[derive(utoipa::ToSchema)]
+        #[allow(dead_code)]
+        struct Parcel {
+            area: GeometryCollection<PointZ>,
+        }
+
+        #[derive(OpenApi)]
+        #[openapi(components(schemas(Parcel)))]
+        struct ParcelApi;
+
+        fn references(value: &serde_json::Value, refs: &mut Vec<String>) {
+            match value {
+                serde_json::Value::Object(map) => {
+                    if let Some(serde_json::Value::String(r)) = map.get("$ref") {
+                        refs.push(r.replace("#/components/schemas/", ""));
+                    }
+                    map.values().for_each(|v| references(v, refs));
+                }
+                serde_json::Value::Array(values) => values.iter().for_each(|v| references(v, refs)),
+                _ => {}
+            }
+        }
+
+        let openapi = serde_json::to_value(ParcelApi::openapi()).unwrap();
+        let components = &openapi["components"]["schemas"];
+        let mut refs = Vec::new();
+        references(components, &mut refs);
+        for r in refs {
+            assert!(components.get(&r).is_some(), "missing component {}", r);
+        }
+        assert_eq!(
+            serde_json::json!(["x", "y", "z"]),
+            components["GeometryContainer_PointZ"]["oneOf"][0]["properties"]["Point"]["required"]
+        );
+        assert_eq!(
+            serde_json::json!({"$ref": "#/components/schemas/GeometryCollection_PointZ"}),
+            components["GeometryContainer_PointZ"]["oneOf"][6]["properties"]["GeometryCollection"]
+        );
+        assert_eq!(
+            serde_json::json!(["geometries"]),
+            components["GeometryCollection_PointZ"]["required"]
+        );
+        assert_eq!(
+            serde_json::json!(["x", "y"]),
+            serde_json::to_value(<Point as utoipa::PartialSchema>::schema()).unwrap()["required"]
+        );
+    }
 }
@@ -2,7 +2,9 @@
 
 use byteorder::{BigEndian, LittleEndian};
 
-use crate::ewkb::{EwkbHeader, EwkbSerializable, BIG_ENDIAN};
+use crate::errors::{DecodeError, DecodeErrorReason};
+use crate::ewkb::{EwkbHeader, EwkbSerializable, GeometryType, BIG_ENDIAN, LITTLE_ENDIAN};
+use crate::points::Dimension;
 
 pub trait WriteToSql: EwkbSerializable {
     fn write_to_sql<Writer>(
@@ -17,30 +19,76 @@ pub trait WriteToSql: EwkbSerializable {
         self.write_body(out)
     }
 
+    /// Writes MySQL internal geometry format: little-endian SRID followed by WKB.
+    #[cfg(feature = "mysql")]
+    fn write_to_mysql<Writer>(&self, out: &mut Writer) -> Result<(), std::io::Error>
+    where
+        Writer: std::io::Write,
+    {
+        self.write_to_mysql_with_srid(self.srid().unwrap_or(0), out)
+    }
+
+    /// Writes MySQL internal geometry format with the given SRID. MySQL geometries are 2D, so
+    /// geometries with Z or M values are rejected instead of being written with dimension flags
+    /// which MySQL can't read.
+    #[cfg(feature = "mysql")]
+    fn write_to_mysql_with_srid<Writer>(
+        &self,
+        srid: u32,
+        out: &mut Writer,
+    ) -> Result<(), std::io::Error>
+    where
+        Writer: std::io::Write,
+    {
+        use byteorder::WriteBytesExt;
+        if self.geometry_type() & Dimension::ZM != 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "MySQL geometries can't have Z or M values",
+            ));
+        }
+        out.write_u32::<LittleEndian>(srid)?;
+        self.write_to_sql(false, out)
+    }
+
     fn write_body<Writer>(&self, out: &mut Writer) -> Result<(), std::io::Error>
     where
         Writer: std::io::Write;
 }
 
+/// Elements allocated ahead for a count read from the bytes, which may be crafted.
+const MAX_PREALLOCATED: usize = 1024;
+
+/// Maximum number of geometry collections around a geometry.
+pub const MAX_NESTING_DEPTH: usize = 32;
+
+/// Capacity to allocate for `count` elements read from the bytes, the vector grows past it as
+/// the elements are actually read.
+pub fn capacity(count: u32) -> usize {
+    (count as usize).min(MAX_PREALLOCATED)
+}
+
 pub trait ReadFromSql: Sized + EwkbSerializable {
-    fn read_from_sql(bytes: &[u8]) -> Result<Self, std::io::Error> {
+    fn read_from_sql(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut reader = SliceReader { bytes, offset: 0 };
+        let geometry = read_geometry(&mut reader, None)?;
+        reader.finish(geometry)
+    }
+
+    /// Reads MySQL internal geometry format, SRID 0 is read as no SRID.
+    #[cfg(feature = "mysql")]
+    fn read_from_mysql(bytes: &[u8]) -> Result<Self, DecodeError> {
         use byteorder::ReadBytesExt;
-        use std::io::Cursor;
-        let mut cursor = Cursor::new(bytes);
-        let endianness = cursor.read_u8()?;
-        if endianness == BIG_ENDIAN {
-            let header = Self::read_header::<BigEndian, _>(&mut cursor)?;
-            Self::read_body::<BigEndian, _>(
-                header.expect(Self::expected_geometry_variant(header.g_type))?,
-                &mut cursor,
-            )
-        } else {
-            let header = Self::read_header::<LittleEndian, _>(&mut cursor)?;
-            Self::read_body::<LittleEndian, _>(
-                header.expect(Self::expected_geometry_variant(header.g_type))?,
-                &mut cursor,
-            )
-        }
+        let mut reader = SliceReader { bytes, offset: 0 };
+        let srid = match reader
+            .read_u32::<LittleEndian>()
+            .map_err(|e| reader.error(e))?
+        {
+            0 => None,
+            srid => Some(srid),
+        };
+        let geometry = read_geometry(&mut reader, Some(srid))?;
+        reader.finish(geometry)
     }
 
     fn read_body<Endianness, Reader>(
@@ -52,12 +100,164 @@ pub trait ReadFromSql: Sized + EwkbSerializable {
         Endianness: byteorder::ByteOrder;
 }
 
+/// Reads the byte order, the header and the body of a geometry, `srid` replaces the SRID of the
+/// header when it is given.
+fn read_geometry<G>(
+    reader: &mut SliceReader<'_>,
+    srid: Option<Option<u32>>,
+) -> Result<G, DecodeError>
+where
+    G: ReadFromSql,
+{
+    use byteorder::ReadBytesExt;
+    let offset = reader.offset;
+    match reader.read_u8().map_err(|e| reader.error(e))? {
+        BIG_ENDIAN => read_header_and_body::<G, BigEndian>(reader, srid),
+        LITTLE_ENDIAN => read_header_and_body::<G, LittleEndian>(reader, srid),
+        byte_order => Err(DecodeError {
+            offset,
+            reason: DecodeErrorReason::InvalidByteOrder(byte_order),
+        }),
+    }
+}
+
+fn read_header_and_body<G, Endianness>(
+    reader: &mut SliceReader<'_>,
+    srid: Option<Option<u32>>,
+) -> Result<G, DecodeError>
+where
+    G: ReadFromSql,
+    Endianness: byteorder::ByteOrder,
+{
+    let offset = reader.offset;
+    let mut header = G::read_header::<Endianness, _>(reader).map_err(|e| reader.error(e))?;
+    check_geometry_type::<G>(header.g_type).map_err(|reason| DecodeError { offset, reason })?;
+    if let Some(srid) = srid {
+        header.srid = srid;
+    }
+    G::read_body::<Endianness, _>(header, reader).map_err(|e| reader.error(e))
+}
+
+fn check_geometry_type<G>(g_type: u32) -> Result<(), DecodeErrorReason>
+where
+    G: EwkbSerializable,
+{
+    if !(1..=7).contains(&(g_type & !Dimension::ZM)) {
+        return Err(DecodeErrorReason::UnknownGeometryType(g_type));
+    }
+    let expected = G::expected_geometry_variant(g_type);
+    let actual = GeometryType::from(g_type);
+    if expected != actual {
+        return Err(DecodeErrorReason::UnexpectedGeometryType { expected, actual });
+    }
+    Ok(())
+}
+
+/// Reads the byte order and the header of a part of a multi geometry or of a geometry collection.
+/// Parts have their own byte order and dimensions, and take the SRID of the geometry, which a
+/// part SRID has to match.
+pub fn read_part_header<G, Reader>(
+    parent: EwkbHeader,
+    reader: &mut Reader,
+) -> Result<(u8, EwkbHeader), std::io::Error>
+where
+    G: EwkbSerializable,
+    Reader: std::io::Read,
+{
+    use byteorder::ReadBytesExt;
+    let byte_order = reader.read_u8()?;
+    let mut header = match byte_order {
+        BIG_ENDIAN => G::read_header::<BigEndian, _>(reader)?,
+        LITTLE_ENDIAN => G::read_header::<LittleEndian, _>(reader)?,
+        _ => return Err(DecodeErrorReason::InvalidByteOrder(byte_order).into()),
+    };
+    check_geometry_type::<G>(header.g_type)?;
+    if let (Some(srid), Some(parent_srid)) = (header.srid, parent.srid) {
+        if srid != parent_srid {
+            return Err(DecodeErrorReason::InvalidGeometry(format!(
+                "SRID {} of a part differs from SRID {} of the geometry",
+                srid, parent_srid
+            ))
+            .into());
+        }
+    }
+    header.srid = parent.srid;
+    header.depth = parent.depth;
+    Ok((byte_order, header))
+}
+
+/// Reads a part of a multi geometry or of a geometry collection in its own byte order.
+pub fn read_part<G, Reader>(parent: EwkbHeader, reader: &mut Reader) -> Result<G, std::io::Error>
+where
+    G: ReadFromSql,
+    Reader: std::io::Read,
+{
+    match read_part_header::<G, Reader>(parent, reader)? {
+        (BIG_ENDIAN, header) => G::read_body::<BigEndian, Reader>(header, reader),
+        (_, header) => G::read_body::<LittleEndian, Reader>(header, reader),
+    }
+}
+
+/// Reader over the decoded bytes, which stays at the start of a value that is truncated.
+struct SliceReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl SliceReader<'_> {
+    fn error(&self, error: std::io::Error) -> DecodeError {
+        let reason = if error.kind() == std::io::ErrorKind::UnexpectedEof {
+            DecodeErrorReason::UnexpectedEnd
+        } else if let Some(reason) = error
+            .get_ref()
+            .and_then(|inner| inner.downcast_ref::<DecodeErrorReason>())
+        {
+            reason.clone()
+        } else {
+            DecodeErrorReason::InvalidGeometry(error.to_string())
+        };
+        DecodeError {
+            offset: self.offset,
+            reason,
+        }
+    }
+
+    /// Returns the geometry if there are no bytes left after it.
+    fn finish<G>(&self, geometry: G) -> Result<G, DecodeError> {
+        if self.offset < self.bytes.len() {
+            return Err(DecodeError {
+                offset: self.offset,
+                reason: DecodeErrorReason::TrailingBytes(self.bytes.len() - self.offset),
+            });
+        }
+        Ok(geometry)
+    }
+}
+
+impl std::io::Read for SliceReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let count = buf.len().min(self.bytes.len() - self.offset);
+        buf[..count].copy_from_slice(&self.bytes[self.offset..self.offset + count]);
+        self.offset += count;
+        Ok(count)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        if buf.len() > self.bytes.len() - self.offset {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        self.read(buf).map(|_| ())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::ReadFromSql;
     use super::WriteToSql;
+    use crate::errors::{DecodeError, DecodeErrorReason};
     use crate::types::MultiLineString;
-    use crate::types::{Point, PointM, PointZ, PointZM};
+    use crate::types::{GeometryCollection, GeometryContainer, GeometryType, LineString};
+    use crate::types::{MultiPolygon, Point, PointM, PointZ, PointZM, Polygon};
 
     #[test]
     fn test_encode_decode_point() {
@@ -131,4 +331,163 @@ mod test {
         let decoded_multiline = crate::types::MultiLineString::read_from_sql(&buffer).unwrap();
         assert_eq!(multiline, decoded_multiline);
     }
+
+    #[cfg(feature = "mysql")]
+    #[test]
+    fn test_encode_decode_mysql_point() {
+        let point = Point::new(1.0, 2.0, Some(4326));
+        let mut buffer = Vec::new();
+        point.write_to_mysql(&mut buffer).unwrap();
+        let expected: [u8; 25] = [
+            0xE6, 0x10, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0xF0, 0x3F, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40,
+        ];
+        assert_eq!(expected.as_slice(), buffer.as_slice());
+        assert_eq!(point, Point::read_from_mysql(&buffer).unwrap());
+    }
+
+    #[cfg(feature = "mysql")]
+    #[test]
+    fn test_encode_decode_mysql_multi_line_string() {
+        let point1 = Point::new(1.0, 2.0, None);
+        let point2 = Point::new(3.0, 4.0, None);
+
+        let mut multiline: MultiLineString<Point> = MultiLineString::new(None);
+        multiline.add_points([point1, point2]).unwrap();
+
+        let mut buffer = Vec::new();
+        multiline.write_to_mysql(&mut buffer).unwrap();
+        assert_eq!([0, 0, 0, 0], buffer[..4]);
+        let decoded_multiline = MultiLineString::read_from_mysql(&buffer).unwrap();
+        assert_eq!(multiline, decoded_multiline);
+    }
+
+    #[cfg(feature = "mysql")]
+    #[test]
+    fn test_encode_mysql_rejects_z_and_m() {
+        let mut buffer = Vec::new();
+        let error = PointZ::new(1.0, 2.0, 3.0, None)
+            .write_to_mysql(&mut buffer)
+            .unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidInput, error.kind());
+        let mut line = LineString::new(Some(4326));
+        line.add_point(PointM::new(1.0, 2.0, 3.0, Some(4326)))
+            .unwrap();
+        assert!(line.write_to_mysql(&mut buffer).is_err());
+        let mut collection = GeometryCollection::new(None);
+        collection.add_geometry(GeometryContainer::Point(PointZM::new(
+            1.0, 2.0, 3.0, 4.0, None,
+        )));
+        assert!(collection.write_to_mysql(&mut buffer).is_err());
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_decode_crafted_counts() {
+        // a polygon with one ring of u32::MAX points
+        let polygon = [1, 3, 0, 0, 0, 1, 0, 0, 0, 0xff, 0xff, 0xff, 0xff];
+        assert_eq!(
+            Err(DecodeError {
+                offset: 13,
+                reason: DecodeErrorReason::UnexpectedEnd
+            }),
+            Polygon::<Point>::read_from_sql(&polygon)
+        );
+        let line = [0, 0, 0, 0, 2, 0xff, 0xff, 0xff, 0xff, 0x3f, 0xf0];
+        assert_eq!(
+            Err(DecodeError {
+                offset: 9,
+                reason: DecodeErrorReason::UnexpectedEnd
+            }),
+            LineString::<Point>::read_from_sql(&line)
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid_header() {
+        let mut buffer = Vec::new();
+        Point::new(1.0, 2.0, Some(4326))
+            .write_to_sql(true, &mut buffer)
+            .unwrap();
+        assert_eq!(
+            Err(DecodeError {
+                offset: 1,
+                reason: DecodeErrorReason::UnexpectedGeometryType {
+                    expected: GeometryType::LineString,
+                    actual: GeometryType::Point
+                }
+            }),
+            LineString::<Point>::read_from_sql(&buffer)
+        );
+        buffer[1] = 8;
+        assert_eq!(
+            Err(DecodeError {
+                offset: 1,
+                reason: DecodeErrorReason::UnknownGeometryType(8)
+            }),
+            Point::read_from_sql(&buffer)
+        );
+        buffer[0] = 2;
+        assert_eq!(
+            Err(DecodeError {
+                offset: 0,
+                reason: DecodeErrorReason::InvalidByteOrder(2)
+            }),
+            Point::read_from_sql(&buffer)
+        );
+    }
+
+    #[test]
+    fn test_decode_trailing_bytes() {
+        let mut buffer = Vec::new();
+        PointZ::new(1.0, 2.0, 3.0, None)
+            .write_to_sql(true, &mut buffer)
+            .unwrap();
+        buffer.extend_from_slice(&[0, 0]);
+        assert_eq!(
+            Err(DecodeError {
+                offset: 29,
+                reason: DecodeErrorReason::TrailingBytes(2)
+            }),
+            PointZ::read_from_sql(&buffer)
+        );
+    }
+
+    #[test]
+    fn test_decode_nested_collections() {
+        let mut buffer = vec![1, 7, 0, 0, 0, 1, 0, 0, 0];
+        for _ in 0..100 {
+            buffer.extend_from_slice(&[1, 7, 0, 0, 0, 1, 0, 0, 0]);
+        }
+        let error = GeometryCollection::<Point>::read_from_sql(&buffer).unwrap_err();
+        assert_eq!(DecodeErrorReason::TooDeeplyNested, error.reason);
+    }
+
+    #[test]
+    fn test_decode_truncated() {
+        let mut polygon = Polygon::new(Some(4326));
+        polygon
+            .add_points([
+                PointZ::new(0.0, 0.0, 1.0, Some(4326)),
+                PointZ::new(1.0, 0.0, 2.0, Some(4326)),
+                PointZ::new(0.0, 0.0, 1.0, Some(4326)),
+            ])
+            .unwrap();
+        let multipolygon = MultiPolygon {
+            polygons: vec![polygon.clone(), polygon],
+            srid: Some(4326),
+        };
+        let mut buffer = Vec::new();
+        multipolygon.write_to_sql(true, &mut buffer).unwrap();
+        for len in 0..buffer.len() {
+            let error = MultiPolygon::<PointZ>::read_from_sql(&buffer[..len]).unwrap_err();
+            assert_eq!(DecodeErrorReason::UnexpectedEnd, error.reason);
+            assert!(error.offset <= len);
+            assert!(GeometryContainer::<PointZ>::read_from_sql(&buffer[..len]).is_err());
+        }
+        assert_eq!(
+            Ok(multipolygon),
+            MultiPolygon::<PointZ>::read_from_sql(&buffer)
+        );
+    }
 }
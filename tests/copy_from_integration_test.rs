@@ -0,0 +1,195 @@
+#![cfg(feature = "postgres")]
+//! Submodule to test the bulk insertion of geometries with `COPY FROM`.
+#[macro_use]
+extern crate diesel;
+
+use diesel::pg::{CopyFormat, PgConnection};
+use diesel::prelude::ExecuteCopyFromDsl;
+use diesel::{Connection, QueryDsl, RunQueryDsl};
+use dotenvy::dotenv;
+use std::env;
+
+use postgis_diesel::copy::BinaryCopy;
+use postgis_diesel::types::*;
+
+table! {
+    use postgis_diesel::sql_types::*;
+    use diesel::sql_types::*;
+    copy_samples (id) {
+        id -> Int4,
+        name -> Nullable<Text>,
+        point -> Geometry,
+        polygon -> Nullable<Geometry>,
+        geography -> Geography,
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = copy_samples, treat_none_as_default_value = false)]
+struct NewCopySample {
+    name: Option<String>,
+    point: Point,
+    polygon: Option<Polygon<Point>>,
+    geography: Point,
+}
+
+#[derive(Queryable, Debug, PartialEq)]
+struct CopySample {
+    id: i32,
+    name: Option<String>,
+    point: Point,
+    polygon: Option<Polygon<Point>>,
+    geography: Point,
+}
+
+fn establish_connection() -> PgConnection {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL not set");
+    let mut conn = PgConnection::establish(&database_url)
+        .unwrap_or_else(|_| panic!("Error connecting to {}", database_url));
+    diesel::sql_query("CREATE EXTENSION IF NOT EXISTS postgis")
+        .execute(&mut conn)
+        .unwrap();
+    diesel::sql_query(
+        "CREATE TEMPORARY TABLE copy_samples
+(
+    id                SERIAL PRIMARY KEY,
+    name              text,
+    point             geometry(Point,4326) NOT NULL,
+    polygon           geometry(Polygon,4326),
+    geography         geography(Point,4326) NOT NULL
+)",
+    )
+    .execute(&mut conn)
+    .unwrap();
+    conn
+}
+
+fn new_polygon(offset: f64) -> Polygon<Point> {
+    let mut polygon = Polygon::new(Some(4326));
+    polygon
+        .add_points([
+            Point::new(offset, offset, Some(4326)),
+            Point::new(offset + 1.0, offset, Some(4326)),
+            Point::new(offset + 1.0, offset + 1.0, Some(4326)),
+            Point::new(offset, offset, Some(4326)),
+        ])
+        .unwrap();
+    polygon
+}
+
+#[test]
+fn copy_from_insertable_test() {
+    let mut conn = establish_connection();
+    let samples = vec![
+        NewCopySample {
+            name: Some("with polygon".to_string()),
+            point: Point::new(1.0, 2.0, Some(4326)),
+            polygon: Some(new_polygon(0.0)),
+            geography: Point::new(13.4, 52.5, Some(4326)),
+        },
+        NewCopySample {
+            name: None,
+            point: Point::new(3.0, 4.0, Some(4326)),
+            polygon: None,
+            geography: Point::new(2.3, 48.8, Some(4326)),
+        },
+    ];
+    let inserted = diesel::copy_from(copy_samples::table)
+        .from_insertable(&samples)
+        .execute(&mut conn)
+        .unwrap();
+    assert_eq!(2, inserted);
+    let found: Vec<CopySample> = copy_samples::table
+        .order_by(copy_samples::id)
+        .load(&mut conn)
+        .unwrap();
+    assert_eq!(
+        vec![
+            CopySample {
+                id: 1,
+                name: Some("with polygon".to_string()),
+                point: Point::new(1.0, 2.0, Some(4326)),
+                polygon: Some(new_polygon(0.0)),
+                geography: Point::new(13.4, 52.5, Some(4326)),
+            },
+            CopySample {
+                id: 2,
+                name: None,
+                point: Point::new(3.0, 4.0, Some(4326)),
+                polygon: None,
+                geography: Point::new(2.3, 48.8, Some(4326)),
+            },
+        ],
+        found
+    );
+}
+
+#[test]
+fn binary_copy_from_iterator_test() {
+    let mut conn = establish_connection();
+    let rows = BinaryCopy::new((0..1000).map(|i| {
+        let offset = f64::from(i);
+        (
+            Point::new(offset, offset, Some(4326)),
+            if i % 2 == 0 {
+                Some(new_polygon(offset))
+            } else {
+                None
+            },
+            Point::new(offset / 10.0, offset / 20.0, Some(4326)),
+        )
+    }));
+    let inserted = diesel::copy_from(copy_samples::table)
+        .from_raw_data(
+            (
+                copy_samples::point,
+                copy_samples::polygon,
+                copy_samples::geography,
+            ),
+            |out| rows.write_to(out),
+        )
+        .with_format(CopyFormat::Binary)
+        .execute(&mut conn)
+        .unwrap();
+    assert_eq!(1000, inserted);
+    // the name is not a geometry, so it is left to its default
+    let found: CopySample = copy_samples::table
+        .order(copy_samples::id)
+        .offset(42)
+        .first(&mut conn)
+        .unwrap();
+    assert_eq!(
+        CopySample {
+            id: 43,
+            name: None,
+            point: Point::new(42.0, 42.0, Some(4326)),
+            polygon: Some(new_polygon(42.0)),
+            geography: Point::new(4.2, 2.1, Some(4326)),
+        },
+        found
+    );
+}
+
+#[test]
+fn binary_copy_wrong_type_test() {
+    let mut conn = establish_connection();
+    // the polygon column is given a point
+    let rows = BinaryCopy::new(vec![(
+        Point::new(1.0, 2.0, Some(4326)),
+        Point::new(1.0, 2.0, Some(4326)),
+        Point::new(1.0, 2.0, Some(4326)),
+    )]);
+    let result = diesel::copy_from(copy_samples::table)
+        .from_raw_data(
+            (
+                copy_samples::point,
+                copy_samples::polygon,
+                copy_samples::geography,
+            ),
+            |out| rows.write_to(out),
+        )
+        .with_format(CopyFormat::Binary)
+        .execute(&mut conn);
+    assert!(result.is_err());
+}
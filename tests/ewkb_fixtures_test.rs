@@ -0,0 +1,178 @@
+//! Submodule to test the decoding of EWKB and WKB written by other tools, from the fixtures in
+//! `tests/fixtures/ewkb`.
+use postgis_diesel::errors::DecodeErrorReason;
+use postgis_diesel::types::*;
+
+/// Reads the hex lines of a fixture, skipping the comments.
+fn fixture(text: &str) -> EwkbBuf {
+    let hex: String = text
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .concat();
+    let bytes = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect();
+    EwkbBuf::new(bytes)
+}
+
+fn new_line<P>(points: Vec<P>, srid: Option<u32>) -> LineString<P>
+where
+    P: PointT,
+{
+    let mut line = LineString::new(srid);
+    line.add_points(points).unwrap();
+    line
+}
+
+fn new_collection_2d() -> GeometryCollection<Point> {
+    let mut collection = GeometryCollection::new(Some(4326));
+    collection.geometries = vec![
+        GeometryContainer::Point(Point::new(1.0, 2.0, Some(4326))),
+        GeometryContainer::LineString(new_line(
+            vec![
+                Point::new(3.0, 4.0, Some(4326)),
+                Point::new(5.0, 6.0, Some(4326)),
+            ],
+            Some(4326),
+        )),
+    ];
+    collection
+}
+
+#[test]
+fn postgis_collection_test() {
+    let ewkb = fixture(include_str!("fixtures/ewkb/postgis_collection.hex"));
+    let collection = new_collection_2d();
+    assert_eq!(collection, ewkb.decode().unwrap());
+    assert_eq!(ewkb, EwkbBuf::from_geometry(&collection).unwrap());
+}
+
+#[test]
+fn postgis_nested_collection_test() {
+    let ewkb = fixture(include_str!(
+        "fixtures/ewkb/postgis_nested_collection_z.hex"
+    ));
+    let mut nested = GeometryCollection::new(Some(3857));
+    nested.geometries = vec![GeometryContainer::LineString(new_line(
+        vec![
+            PointZ::new(1.0, 2.0, 3.0, Some(3857)),
+            PointZ::new(4.0, 5.0, 6.0, Some(3857)),
+        ],
+        Some(3857),
+    ))];
+    let mut collection = GeometryCollection::new(Some(3857));
+    collection.geometries = vec![
+        GeometryContainer::Point(PointZ::new(1.0, 2.0, 3.0, Some(3857))),
+        GeometryContainer::GeometryCollection(nested),
+    ];
+    assert_eq!(collection, ewkb.decode().unwrap());
+    assert_eq!(ewkb, EwkbBuf::from_geometry(&collection).unwrap());
+    assert!(ewkb.decode::<GeometryCollection<Point>>().is_err());
+}
+
+#[test]
+fn postgis_multipolygon_xdr_test() {
+    let ewkb = fixture(include_str!("fixtures/ewkb/postgis_multipolygon_xdr.hex"));
+    let square = |o: f64| {
+        let mut polygon = Polygon::new(Some(4326));
+        polygon
+            .add_points([
+                Point::new(o, o, Some(4326)),
+                Point::new(o + 1.0, o, Some(4326)),
+                Point::new(o + 1.0, o + 1.0, Some(4326)),
+                Point::new(o, o, Some(4326)),
+            ])
+            .unwrap();
+        polygon
+    };
+    let multipolygon = MultiPolygon {
+        polygons: vec![square(0.0), square(2.0)],
+        srid: Some(4326),
+    };
+    assert_eq!(multipolygon, ewkb.decode().unwrap());
+    let flat: FlatGeometry = ewkb.decode().unwrap();
+    assert_eq!(vec![0, 1, 2], flat.polygon_offsets);
+}
+
+#[test]
+fn postgis_multilinestring_m_test() {
+    let ewkb = fixture(include_str!("fixtures/ewkb/postgis_multilinestring_m.hex"));
+    let mut multiline = MultiLineString::new(Some(4326));
+    multiline
+        .add_points([
+            PointM::new(1.0, 2.0, 3.0, Some(4326)),
+            PointM::new(4.0, 5.0, 6.0, Some(4326)),
+        ])
+        .unwrap();
+    multiline
+        .add_line()
+        .add_points([
+            PointM::new(7.0, 8.0, 9.0, Some(4326)),
+            PointM::new(10.0, 11.0, 12.0, Some(4326)),
+        ])
+        .unwrap();
+    assert_eq!(multiline, ewkb.decode().unwrap());
+    assert_eq!(ewkb, EwkbBuf::from_geometry(&multiline).unwrap());
+}
+
+#[test]
+fn gdal_iso_test() {
+    let ewkb = fixture(include_str!("fixtures/ewkb/gdal_multipoint_m_iso.hex"));
+    let mut multipoint = MultiPoint::new(None);
+    multipoint.add_points([
+        PointM::new(1.0, 2.0, 3.0, None),
+        PointM::new(4.0, 5.0, 6.0, None),
+    ]);
+    assert_eq!(multipoint, ewkb.decode().unwrap());
+    let view = ewkb.as_ewkb_ref();
+    assert!(view.has_m().unwrap() && !view.has_z().unwrap());
+
+    let ewkb = fixture(include_str!("fixtures/ewkb/gdal_collection_zm_iso.hex"));
+    let mut collection = GeometryCollection::new(None);
+    collection.geometries = vec![
+        GeometryContainer::Point(PointZM::new(1.0, 2.0, 3.0, 4.0, None)),
+        GeometryContainer::LineString(new_line(
+            vec![
+                PointZM::new(1.0, 2.0, 3.0, 4.0, None),
+                PointZM::new(5.0, 6.0, 7.0, 8.0, None),
+            ],
+            None,
+        )),
+    ];
+    assert_eq!(collection, ewkb.decode().unwrap());
+}
+
+#[test]
+fn gdal_polygon_25d_test() {
+    let ewkb = fixture(include_str!("fixtures/ewkb/gdal_polygon_25d.hex"));
+    let mut polygon = Polygon::new(None);
+    polygon
+        .add_points([
+            PointZ::new(0.0, 0.0, 1.0, None),
+            PointZ::new(1.0, 0.0, 2.0, None),
+            PointZ::new(1.0, 1.0, 3.0, None),
+            PointZ::new(0.0, 0.0, 1.0, None),
+        ])
+        .unwrap();
+    assert_eq!(polygon, ewkb.decode().unwrap());
+    assert_eq!(ewkb, EwkbBuf::from_geometry(&polygon).unwrap());
+}
+
+#[test]
+fn mixed_endian_collection_test() {
+    let ewkb = fixture(include_str!("fixtures/ewkb/mixed_endian_collection.hex"));
+    assert_eq!(new_collection_2d(), ewkb.decode().unwrap());
+    // a part SRID which differs from the one of the collection
+    let mut bytes = ewkb.into_bytes();
+    let part_srid = bytes.len() - 40;
+    bytes[part_srid..part_srid + 4].copy_from_slice(&3857u32.to_le_bytes());
+    let error = EwkbBuf::new(bytes)
+        .decode::<GeometryCollection<Point>>()
+        .unwrap_err();
+    assert!(matches!(
+        error.reason,
+        DecodeErrorReason::InvalidGeometry(_)
+    ));
+}
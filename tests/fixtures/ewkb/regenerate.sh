@@ -0,0 +1,47 @@
+#!/bin/sh
+# Captures the PostGIS and GDAL fixtures of this directory, with the command and the version which
+# produced them in their header. Needs `psql` connected to a PostGIS database through
+# DATABASE_URL, and the GDAL Python bindings (`osgeo`).
+# mixed_endian_collection.hex is made by hand and is not regenerated.
+set -eu
+cd "$(dirname "$0")"
+
+postgis() {
+    file=$1
+    query=$2
+    version=$(psql "$DATABASE_URL" -XAtc "SELECT postgis_full_version()")
+    hex=$(psql "$DATABASE_URL" -XAtc "SELECT upper(encode($query, 'hex'))")
+    {
+        echo "# psql -XAtc \"SELECT upper(encode($query, 'hex'))\""
+        echo "# $version"
+        echo "$hex" | fold -w 64
+    } > "$file"
+}
+
+gdal() {
+    file=$1
+    wkt=$2
+    export_call=$3
+    version=$(python3 -c "from osgeo import gdal; print(gdal.VersionInfo('--version'))")
+    hex=$(python3 -c "from osgeo import ogr; print(ogr.CreateGeometryFromWkt('$wkt').$export_call.hex().upper())")
+    {
+        echo "# python3: ogr.CreateGeometryFromWkt('$wkt').$export_call"
+        echo "# $version"
+        echo "$hex" | fold -w 64
+    } > "$file"
+}
+
+postgis postgis_collection.hex \
+    "ST_AsEWKB('SRID=4326;GEOMETRYCOLLECTION(POINT(1 2),LINESTRING(3 4,5 6))'::geometry)"
+postgis postgis_multilinestring_m.hex \
+    "ST_AsEWKB('SRID=4326;MULTILINESTRING M((1 2 3,4 5 6),(7 8 9,10 11 12))'::geometry)"
+postgis postgis_multipolygon_xdr.hex \
+    "ST_AsEWKB('SRID=4326;MULTIPOLYGON(((0 0,1 0,1 1,0 0)),((2 2,3 2,3 3,2 2)))'::geometry, 'XDR')"
+postgis postgis_nested_collection_z.hex \
+    "ST_AsEWKB('SRID=3857;GEOMETRYCOLLECTION Z(POINT Z(1 2 3),GEOMETRYCOLLECTION Z(LINESTRING Z(1 2 3,4 5 6)))'::geometry)"
+
+gdal gdal_collection_zm_iso.hex \
+    "GEOMETRYCOLLECTION ZM (POINT ZM (1 2 3 4),LINESTRING ZM (1 2 3 4,5 6 7 8))" \
+    "ExportToIsoWkb(ogr.wkbNDR)"
+gdal gdal_multipoint_m_iso.hex "MULTIPOINT M ((1 2 3),(4 5 6))" "ExportToIsoWkb(ogr.wkbNDR)"
+gdal gdal_polygon_25d.hex "POLYGON Z ((0 0 1,1 0 2,1 1 3,0 0 1))" "ExportToWkb(ogr.wkbNDR)"
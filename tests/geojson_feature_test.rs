@@ -0,0 +1,130 @@
+#![cfg(feature = "derive")]
+//! Submodule to test `#[derive(GeoJsonFeature)]`.
+use postgis_diesel::types::*;
+
+#[derive(Debug, PartialEq, GeoJsonFeature)]
+struct Station {
+    #[geojson(id)]
+    id: i32,
+    #[geojson(rename = "stationName")]
+    name: String,
+    platforms: Option<u32>,
+    #[geojson(skip)]
+    cached: bool,
+    #[geojson(geometry)]
+    location: Point,
+}
+
+#[derive(Debug, PartialEq, GeoJsonFeature)]
+struct Parcel {
+    #[geojson(id)]
+    number: Option<String>,
+    #[geojson(geometry)]
+    area: Option<Polygon<Point>>,
+}
+
+fn station(id: i32) -> Station {
+    Station {
+        id,
+        name: format!("station {}", id),
+        platforms: Some(2),
+        cached: false,
+        location: Point::new(7.44, 46.95, Some(4326)),
+    }
+}
+
+#[test]
+fn test_feature_round_trip() {
+    let feature = station(1).into_feature().unwrap();
+    assert_eq!(Some(FeatureId::from(1)), feature.id);
+    let json = serde_json::to_value(&feature).unwrap();
+    assert_eq!(
+        serde_json::json!({
+            "type": "Feature",
+            "id": 1,
+            "geometry": {"type": "Point", "coordinates": [7.44, 46.95]},
+            "properties": {"stationName": "station 1", "platforms": 2}
+        }),
+        json
+    );
+    let feature: Feature<Point, FeatureProperties> = serde_json::from_value(json).unwrap();
+    assert_eq!(station(1), Station::from_feature(feature).unwrap());
+}
+
+#[test]
+fn test_feature_collection_round_trip() {
+    let stations = vec![station(1), station(2)];
+    let collection = Station::into_feature_collection(stations).unwrap();
+    let json = serde_json::to_string(&collection).unwrap();
+    let collection: FeatureCollection<Point, FeatureProperties> =
+        serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        vec![station(1), station(2)],
+        Station::from_feature_collection(collection).unwrap()
+    );
+}
+
+#[test]
+fn test_optional_id_and_geometry() {
+    let parcel = Parcel {
+        number: None,
+        area: None,
+    };
+    let feature = parcel.into_feature().unwrap();
+    assert_eq!(None, feature.id);
+    assert_eq!(None, feature.geometry);
+    assert_eq!(Some(FeatureProperties::new()), feature.properties);
+    assert_eq!(
+        Parcel {
+            number: None,
+            area: None
+        },
+        Parcel::from_feature(feature).unwrap()
+    );
+
+    let mut polygon = Polygon::new(Some(4326));
+    polygon
+        .add_points([
+            Point::new(0.0, 0.0, Some(4326)),
+            Point::new(1.0, 0.0, Some(4326)),
+            Point::new(0.0, 1.0, Some(4326)),
+            Point::new(0.0, 0.0, Some(4326)),
+        ])
+        .unwrap();
+    let parcel = Parcel {
+        number: Some("CH-1".to_string()),
+        area: Some(polygon),
+    };
+    let feature = parcel.into_feature().unwrap();
+    assert_eq!(Some(FeatureId::from("CH-1")), feature.id);
+    let json = serde_json::to_string(&feature).unwrap();
+    let feature: Feature<Point, FeatureProperties> = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        Some("CH-1".to_string()),
+        Parcel::from_feature(feature).unwrap().number
+    );
+}
+
+#[test]
+fn test_from_feature_errors() {
+    let json = r#"{"type":"Feature","id":1,"geometry":null,"properties":{"stationName":"x"}}"#;
+    let feature: Feature<Point, FeatureProperties> = serde_json::from_str(json).unwrap();
+    assert_eq!(
+        "missing geometry or geometry of another type",
+        Station::from_feature(feature).unwrap_err().to_string()
+    );
+
+    let json = r#"{"type":"Feature","id":"a","geometry":{"type":"Point","coordinates":[1,2]},"properties":{"stationName":"x"}}"#;
+    let feature: Feature<Point, FeatureProperties> = serde_json::from_str(json).unwrap();
+    assert!(Station::from_feature(feature)
+        .unwrap_err()
+        .to_string()
+        .starts_with("id: "));
+
+    let json = r#"{"type":"Feature","id":1,"geometry":{"type":"Point","coordinates":[1,2]},"properties":{}}"#;
+    let feature: Feature<Point, FeatureProperties> = serde_json::from_str(json).unwrap();
+    assert!(Station::from_feature(feature)
+        .unwrap_err()
+        .to_string()
+        .starts_with("property `stationName`: "));
+}
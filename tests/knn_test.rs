@@ -0,0 +1,272 @@
+#![cfg(feature = "postgres")]
+#[macro_use]
+extern crate diesel;
+
+use std::env;
+use std::sync::Once;
+
+use diesel::pg::{Pg, PgConnection};
+use diesel::query_builder::{AstPass, Query, QueryFragment, QueryId};
+use diesel::sql_types::Text;
+use diesel::Connection;
+use diesel::QueryResult;
+use diesel::RunQueryDsl;
+use dotenvy::dotenv;
+
+use postgis_diesel::knn::nearest;
+use postgis_diesel::types::Point;
+
+static INIT: Once = Once::new();
+
+#[derive(Insertable)]
+#[diesel(table_name = knn_samples)]
+struct NewKnnSample {
+    name: String,
+    point: Point,
+    location: Point,
+}
+
+#[derive(Queryable, Debug)]
+#[diesel(table_name = knn_samples)]
+#[allow(dead_code)]
+struct KnnSample {
+    id: i32,
+    name: String,
+    point: Point,
+    location: Point,
+}
+
+table! {
+    use postgis_diesel::sql_types::*;
+    use diesel::sql_types::*;
+    knn_samples (id) {
+        id -> Int4,
+        name -> Text,
+        point -> Geometry,
+        location -> Geography,
+    }
+}
+
+fn establish_connection() -> PgConnection {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL not set");
+
+    PgConnection::establish(&database_url)
+        .unwrap_or_else(|_| panic!("Error connecting to {}", database_url))
+}
+
+/// `EXPLAIN` of a query, loaded as the lines of the plan.
+struct Explain<Q>(Q);
+
+impl<Q> QueryId for Explain<Q> {
+    type QueryId = ();
+
+    const HAS_STATIC_QUERY_ID: bool = false;
+}
+
+impl<Q> Query for Explain<Q> {
+    type SqlType = Text;
+}
+
+impl<Q, Conn> RunQueryDsl<Conn> for Explain<Q> {}
+
+impl<Q: QueryFragment<Pg>> QueryFragment<Pg> for Explain<Q> {
+    fn walk_ast<'b>(&'b self, mut out: AstPass<'_, 'b, Pg>) -> QueryResult<()> {
+        out.push_sql("EXPLAIN ");
+        self.0.walk_ast(out.reborrow())
+    }
+}
+
+/// Plan of `query`, with sequential scans disabled since the table only has a few rows.
+fn explain<Q: QueryFragment<Pg>>(conn: &mut PgConnection, query: Q) -> String {
+    diesel::sql_query("SET enable_seqscan = off")
+        .execute(conn)
+        .unwrap();
+    let plan: Vec<String> = Explain(query).load(conn).unwrap();
+    diesel::sql_query("RESET enable_seqscan")
+        .execute(conn)
+        .unwrap();
+    plan.join("\n")
+}
+
+fn new_sample(name: &str, x: f64, y: f64) -> NewKnnSample {
+    NewKnnSample {
+        name: name.to_string(),
+        point: Point::new(x, y, Some(4326)),
+        location: Point::new(x, y, Some(4326)),
+    }
+}
+
+fn initialize() -> PgConnection {
+    let mut conn = establish_connection();
+    INIT.call_once(|| {
+        let _ = diesel::sql_query("CREATE EXTENSION IF NOT EXISTS postgis").execute(&mut conn);
+        let _ = diesel::sql_query("DROP TABLE knn_samples").execute(&mut conn);
+
+        let _ = diesel::sql_query(
+            "CREATE TABLE knn_samples
+(
+    id                SERIAL PRIMARY KEY,
+    name              TEXT NOT NULL,
+    point             geometry(POINT, 4326) NOT NULL,
+    location          geography(POINT, 4326) NOT NULL
+)",
+        )
+        .execute(&mut conn);
+        let _ = diesel::sql_query(
+            "CREATE INDEX knn_samples_point_idx ON knn_samples USING GIST (point)",
+        )
+        .execute(&mut conn);
+        let _ = diesel::sql_query(
+            "CREATE INDEX knn_samples_location_idx ON knn_samples USING GIST (location)",
+        )
+        .execute(&mut conn);
+        let samples = vec![
+            new_sample("far", 10.0, 10.0),
+            new_sample("near", 1.0, 0.0),
+            new_sample("middle", 0.0, 3.0),
+        ];
+        diesel::insert_into(knn_samples::table)
+            .values(&samples)
+            .execute(&mut conn)
+            .unwrap();
+    });
+    conn
+}
+
+#[test]
+fn knn_geometry_test() {
+    let mut conn = initialize();
+    let found_samples: Vec<(KnnSample, f64)> = nearest(
+        knn_samples::table,
+        knn_samples::point,
+        Point::new(0.0, 0.0, Some(4326)),
+        2,
+    )
+    .load(&mut conn)
+    .unwrap();
+    assert_eq!(2, found_samples.len());
+    assert_eq!("near", found_samples[0].0.name);
+    assert_eq!(1.0, found_samples[0].1);
+    assert_eq!("middle", found_samples[1].0.name);
+    assert_eq!(3.0, found_samples[1].1);
+}
+
+#[test]
+fn knn_geometry_recheck_test() {
+    let mut conn = initialize();
+    let found_samples: Vec<(KnnSample, f64)> = nearest(
+        knn_samples::table,
+        knn_samples::point,
+        Point::new(0.0, 0.0, Some(4326)),
+        3,
+    )
+    .recheck(10)
+    .load(&mut conn)
+    .unwrap();
+    let names: Vec<&str> = found_samples.iter().map(|s| s.0.name.as_str()).collect();
+    assert_eq!(vec!["near", "middle", "far"], names);
+    assert_eq!(3.0, found_samples[1].1);
+}
+
+#[test]
+fn knn_recheck_fewer_candidates_test() {
+    let mut conn = initialize();
+    // the candidates are raised to the limit
+    let found_samples: Vec<(KnnSample, f64)> = nearest(
+        knn_samples::table,
+        knn_samples::point,
+        Point::new(0.0, 0.0, Some(4326)),
+        3,
+    )
+    .recheck(1)
+    .load(&mut conn)
+    .unwrap();
+    assert_eq!(3, found_samples.len());
+}
+
+#[test]
+fn knn_geography_test() {
+    let mut conn = initialize();
+    // <-> measures geography on a sphere
+    let found_samples: Vec<(KnnSample, f64)> = nearest(
+        knn_samples::table,
+        knn_samples::location,
+        Point::new(0.0, 0.0, Some(4326)),
+        1,
+    )
+    .load(&mut conn)
+    .unwrap();
+    assert_eq!(1, found_samples.len());
+    assert_eq!("near", found_samples[0].0.name);
+    assert!((found_samples[0].1 - 111_195.08).abs() < 1.0);
+}
+
+#[test]
+fn knn_geography_recheck_test() {
+    let mut conn = initialize();
+    // ST_Distance measures geography on the spheroid
+    let found_samples: Vec<(KnnSample, f64)> = nearest(
+        knn_samples::table,
+        knn_samples::location,
+        Point::new(0.0, 0.0, Some(4326)),
+        2,
+    )
+    .recheck(10)
+    .load(&mut conn)
+    .unwrap();
+    assert_eq!(2, found_samples.len());
+    assert_eq!("near", found_samples[0].0.name);
+    assert!((found_samples[0].1 - 111_319.49).abs() < 1.0);
+    assert_eq!("middle", found_samples[1].0.name);
+    assert!(found_samples[0].1 < found_samples[1].1);
+}
+
+#[test]
+fn knn_index_scan_test() {
+    let mut conn = initialize();
+    let target = Point::new(0.0, 0.0, Some(4326));
+    let plan = explain(
+        &mut conn,
+        nearest(knn_samples::table, knn_samples::point, target, 2),
+    );
+    assert!(
+        plan.contains("Index Scan using knn_samples_point_idx"),
+        "{}",
+        plan
+    );
+    let plan = explain(
+        &mut conn,
+        nearest(knn_samples::table, knn_samples::location, target, 2),
+    );
+    assert!(
+        plan.contains("Index Scan using knn_samples_location_idx"),
+        "{}",
+        plan
+    );
+}
+
+#[test]
+fn knn_recheck_index_scan_test() {
+    let mut conn = initialize();
+    let target = Point::new(0.0, 0.0, Some(4326));
+    // the candidates are taken from the index, only they are sorted by ST_Distance
+    let plan = explain(
+        &mut conn,
+        nearest(knn_samples::table, knn_samples::point, target, 2).recheck(10),
+    );
+    assert!(
+        plan.contains("Index Scan using knn_samples_point_idx"),
+        "{}",
+        plan
+    );
+    let plan = explain(
+        &mut conn,
+        nearest(knn_samples::table, knn_samples::location, target, 2).recheck(10),
+    );
+    assert!(
+        plan.contains("Index Scan using knn_samples_location_idx"),
+        "{}",
+        plan
+    );
+}
@@ -0,0 +1,173 @@
+// Needs a MySQL server (see docker-compose.yml) and `MysqlConnection`, which the `mysql` feature does not
+// enable, run with RUSTFLAGS="--cfg mysql_integration" cargo test --no-default-features --features "mysql diesel/mysql"
+#![cfg(all(feature = "mysql", mysql_integration))]
+#[macro_use]
+extern crate diesel;
+
+use std::env;
+use std::sync::Once;
+
+use diesel::mysql::MysqlConnection;
+use diesel::Connection;
+use diesel::{ExpressionMethods, QueryDsl, RunQueryDsl};
+use dotenvy::dotenv;
+
+use postgis_diesel::functions::*;
+use postgis_diesel::types::*;
+
+static INIT: Once = Once::new();
+
+#[derive(Insertable)]
+#[diesel(table_name = mysql_geometry_samples)]
+struct NewGeometrySample {
+    name: String,
+    point: Point,
+    linestring: LineString<Point>,
+    polygon: Option<Polygon<Point>>,
+    multipoint: MultiPoint<Point>,
+    geometrycollection: GeometryCollection<Point>,
+}
+
+#[derive(Queryable, Debug, PartialEq)]
+#[diesel(table_name = mysql_geometry_samples)]
+struct GeometrySample {
+    id: i32,
+    name: String,
+    point: Point,
+    linestring: LineString<Point>,
+    polygon: Option<Polygon<Point>>,
+    multipoint: MultiPoint<Point>,
+    geometrycollection: GeometryCollection<Point>,
+}
+
+table! {
+    use postgis_diesel::sql_types::*;
+    use diesel::sql_types::*;
+    mysql_geometry_samples (id) {
+        id -> Integer,
+        name -> Text,
+        point -> Geometry,
+        linestring -> Geometry,
+        polygon -> Nullable<Geometry>,
+        multipoint -> Geometry,
+        geometrycollection -> Geometry,
+    }
+}
+
+fn establish_connection() -> MysqlConnection {
+    dotenv().ok();
+    let database_url = env::var("MYSQL_DATABASE_URL").expect("MYSQL_DATABASE_URL not set");
+
+    MysqlConnection::establish(&database_url)
+        .unwrap_or_else(|_| panic!("Error connecting to {}", database_url))
+}
+
+fn initialize() -> MysqlConnection {
+    let mut conn = establish_connection();
+    INIT.call_once(|| {
+        let _ = diesel::sql_query("DROP TABLE mysql_geometry_samples").execute(&mut conn);
+
+        let _ = diesel::sql_query(
+            "CREATE TABLE mysql_geometry_samples
+(
+    id                 INTEGER AUTO_INCREMENT PRIMARY KEY,
+    name               TEXT NOT NULL,
+    point              POINT NOT NULL,
+    linestring         LINESTRING NOT NULL,
+    polygon            POLYGON,
+    multipoint         MULTIPOINT NOT NULL,
+    geometrycollection GEOMETRYCOLLECTION NOT NULL
+)",
+        )
+        .execute(&mut conn);
+    });
+    conn
+}
+
+fn new_sample(name: &str, srid: Option<u32>) -> NewGeometrySample {
+    let point = Point::new(1.0, 2.0, srid);
+    let mut linestring = LineString::new(srid);
+    linestring
+        .add_points([Point::new(0.0, 0.0, srid), Point::new(4.0, 4.0, srid)])
+        .unwrap();
+    let mut polygon = Polygon::new(srid);
+    polygon
+        .add_points([
+            Point::new(0.0, 0.0, srid),
+            Point::new(0.0, 4.0, srid),
+            Point::new(4.0, 4.0, srid),
+            Point::new(4.0, 0.0, srid),
+            Point::new(0.0, 0.0, srid),
+        ])
+        .unwrap();
+    let mut multipoint = MultiPoint::new(srid);
+    multipoint.add_points([Point::new(1.0, 1.0, srid), Point::new(2.0, 2.0, srid)]);
+    let mut geometrycollection = GeometryCollection::new(srid);
+    geometrycollection
+        .geometries
+        .push(GeometryContainer::Point(point));
+    geometrycollection
+        .geometries
+        .push(GeometryContainer::LineString(linestring.clone()));
+    NewGeometrySample {
+        name: name.to_string(),
+        point,
+        linestring,
+        polygon: Some(polygon),
+        multipoint,
+        geometrycollection,
+    }
+}
+
+#[test]
+fn mysql_geometry_round_trip_test() {
+    let mut conn = initialize();
+    for (name, srid) in [("mysql_no_srid", None), ("mysql_3857", Some(3857))] {
+        let sample = new_sample(name, srid);
+        diesel::insert_into(mysql_geometry_samples::table)
+            .values(&sample)
+            .execute(&mut conn)
+            .unwrap();
+        let found: GeometrySample = mysql_geometry_samples::table
+            .filter(mysql_geometry_samples::name.eq(name))
+            .first(&mut conn)
+            .unwrap();
+        assert_eq!(sample.point, found.point);
+        assert_eq!(sample.linestring, found.linestring);
+        assert_eq!(sample.polygon, found.polygon);
+        assert_eq!(sample.multipoint, found.multipoint);
+        assert_eq!(sample.geometrycollection, found.geometrycollection);
+    }
+}
+
+#[test]
+fn mysql_functions_test() {
+    let mut conn = initialize();
+    let sample = new_sample("mysql_functions", None);
+    diesel::insert_into(mysql_geometry_samples::table)
+        .values(&sample)
+        .execute(&mut conn)
+        .unwrap();
+    let found: Vec<(String, f64, f64)> = mysql_geometry_samples::table
+        .filter(mysql_geometry_samples::name.eq("mysql_functions"))
+        .filter(st_within(
+            mysql_geometry_samples::point,
+            sample.polygon.clone().unwrap(),
+        ))
+        .filter(st_intersects(
+            mysql_geometry_samples::linestring,
+            Point::new(2.0, 2.0, None),
+        ))
+        .filter(st_disjoint(
+            mysql_geometry_samples::point,
+            Point::new(10.0, 10.0, None),
+        ))
+        .select((
+            mysql_geometry_samples::name,
+            st_x(mysql_geometry_samples::point),
+            st_y(mysql_geometry_samples::point),
+        ))
+        .load(&mut conn)
+        .unwrap();
+    assert_eq!(vec![("mysql_functions".to_string(), 1.0, 2.0)], found);
+}
@@ -0,0 +1,126 @@
+#![cfg(feature = "postgres-types")]
+//! Submodule to test the `postgres-types` integration with the `postgres` crate, without Diesel.
+
+use dotenvy::dotenv;
+use postgres::{Client, NoTls};
+use std::env;
+
+use postgis_diesel::types::*;
+
+fn establish_client() -> Client {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL not set");
+    let mut client = Client::connect(&database_url, NoTls)
+        .unwrap_or_else(|_| panic!("Error connecting to {}", database_url));
+    client
+        .batch_execute(
+            "CREATE EXTENSION IF NOT EXISTS postgis;
+            CREATE TEMPORARY TABLE postgres_types_samples
+            (
+                id            SERIAL PRIMARY KEY,
+                point         geometry(Point,4326) NOT NULL,
+                point_zm      geometry(PointZM,4326) NOT NULL,
+                polygon       geometry(Polygon,4326) NOT NULL,
+                container     geometry NOT NULL,
+                geography     geography(Point,4326) NOT NULL,
+                nullable      geometry
+            )",
+        )
+        .unwrap();
+    client
+}
+
+fn new_polygon() -> Polygon<Point> {
+    let mut polygon = Polygon::new(Some(4326));
+    polygon
+        .add_points([
+            Point::new(72.0, 64.0, Some(4326)),
+            Point::new(73.0, 65.0, Some(4326)),
+            Point::new(71.0, 62.0, Some(4326)),
+            Point::new(72.0, 64.0, Some(4326)),
+        ])
+        .unwrap();
+    polygon
+}
+
+fn new_collection() -> GeometryContainer<Point> {
+    let mut collection = GeometryCollection::new(Some(4326));
+    collection
+        .geometries
+        .push(GeometryContainer::Point(Point::new(1.0, 2.0, Some(4326))));
+    collection
+        .geometries
+        .push(GeometryContainer::Polygon(new_polygon()));
+    GeometryContainer::GeometryCollection(collection)
+}
+
+#[test]
+fn postgres_types_round_trip_test() {
+    let mut client = establish_client();
+    let point = Point::new(72.0, 64.0, Some(4326));
+    let point_zm = PointZM::new(72.0, 64.0, 10.0, 20.0, Some(4326));
+    client
+        .execute(
+            "INSERT INTO postgres_types_samples (point, point_zm, polygon, container, geography, nullable)
+            VALUES ($1, $2, $3, $4, $5, $6)",
+            &[
+                &point,
+                &point_zm,
+                &new_polygon(),
+                &new_collection(),
+                &point,
+                &None::<LineString<Point>>,
+            ],
+        )
+        .unwrap();
+    let row = client
+        .query_one(
+            "SELECT point, point_zm, polygon, container, geography, nullable FROM postgres_types_samples",
+            &[],
+        )
+        .unwrap();
+    assert_eq!(point, row.get::<_, Point>(0));
+    assert_eq!(point_zm, row.get::<_, PointZM>(1));
+    assert_eq!(new_polygon(), row.get::<_, Polygon<Point>>(2));
+    assert_eq!(new_collection(), row.get::<_, GeometryContainer<Point>>(3));
+    assert_eq!(point, row.get::<_, Point>(4));
+    assert_eq!(None, row.get::<_, Option<LineString<Point>>>(5));
+}
+
+#[test]
+fn postgres_types_function_test() {
+    let mut client = establish_client();
+    let row = client
+        .query_one(
+            "SELECT ST_SetSRID(ST_MakePoint($1, $2), 4326), ST_Distance($3::geometry, $4::geometry)",
+            &[
+                &1.0f64,
+                &2.0f64,
+                &Point::new(0.0, 0.0, None),
+                &Point::new(3.0, 4.0, None),
+            ],
+        )
+        .unwrap();
+    assert_eq!(Point::new(1.0, 2.0, Some(4326)), row.get::<_, Point>(0));
+    assert_eq!(5.0, row.get::<_, f64>(1));
+    let ewkb = row.get::<_, EwkbRef>(0);
+    assert_eq!(Some(4326), ewkb.srid().unwrap());
+    assert_eq!(
+        Point::new(1.0, 2.0, Some(4326)),
+        ewkb.decode::<Point>().unwrap()
+    );
+}
+
+#[test]
+fn postgres_types_wrong_type_test() {
+    let mut client = establish_client();
+    let row = client
+        .query_one(
+            "SELECT ST_MakeLine(ST_MakePoint(0, 0), ST_MakePoint(1, 1))",
+            &[],
+        )
+        .unwrap();
+    assert!(row.try_get::<_, Point>(0).is_err());
+    assert!(row.try_get::<_, LineString<Point>>(0).is_ok());
+    assert!(row.try_get::<_, String>(0).is_err());
+}
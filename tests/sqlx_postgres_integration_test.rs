@@ -0,0 +1,123 @@
+#![cfg(feature = "sqlx-postgres")]
+//! Submodule to test the sqlx integration with Postgres backend.
+
+use dotenvy::dotenv;
+use sqlx::postgres::{PgConnection, PgRow};
+use sqlx::{Connection, Row};
+use std::env;
+
+use postgis_diesel::types::*;
+
+async fn establish_connection() -> PgConnection {
+    dotenv().ok();
+    let database_url = env::var("DATABASE_URL").expect("DATABASE_URL not set");
+    let mut conn = PgConnection::connect(&database_url)
+        .await
+        .unwrap_or_else(|_| panic!("Error connecting to {}", database_url));
+    sqlx::raw_sql(
+        "CREATE EXTENSION IF NOT EXISTS postgis;
+        CREATE TEMPORARY TABLE sqlx_samples
+        (
+            id            SERIAL PRIMARY KEY,
+            point         geometry(Point,4326) NOT NULL,
+            point_zm      geometry(PointZM,4326) NOT NULL,
+            container     geometry NOT NULL,
+            geography     geography(Point,4326) NOT NULL,
+            polygon       geometry(Polygon,4326)
+        )",
+    )
+    .execute(&mut conn)
+    .await
+    .unwrap();
+    conn
+}
+
+fn new_polygon() -> Polygon<Point> {
+    let mut polygon = Polygon::new(Some(4326));
+    polygon
+        .add_points([
+            Point::new(72.0, 64.0, Some(4326)),
+            Point::new(73.0, 65.0, Some(4326)),
+            Point::new(71.0, 62.0, Some(4326)),
+            Point::new(72.0, 64.0, Some(4326)),
+        ])
+        .unwrap();
+    polygon
+}
+
+#[derive(sqlx::FromRow, Debug, PartialEq)]
+struct Sample {
+    id: i32,
+    point: Point,
+    point_zm: PointZM,
+    container: GeometryContainer<Point>,
+    geography: Point,
+    polygon: Option<Polygon<Point>>,
+}
+
+#[tokio::test]
+async fn sqlx_round_trip_test() {
+    let mut conn = establish_connection().await;
+    let point = Point::new(72.0, 64.0, Some(4326));
+    let point_zm = PointZM::new(72.0, 64.0, 10.0, 20.0, Some(4326));
+    let container = GeometryContainer::Polygon(new_polygon());
+    sqlx::query(
+        "INSERT INTO sqlx_samples (point, point_zm, container, geography, polygon)
+        VALUES ($1, $2, $3, $4, $5)",
+    )
+    .bind(point)
+    .bind(point_zm)
+    .bind(&container)
+    .bind(point)
+    .bind(None::<Polygon<Point>>)
+    .execute(&mut conn)
+    .await
+    .unwrap();
+    let sample: Sample = sqlx::query_as("SELECT * FROM sqlx_samples")
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(
+        Sample {
+            id: 1,
+            point,
+            point_zm,
+            container,
+            geography: point,
+            polygon: None,
+        },
+        sample
+    );
+}
+
+#[tokio::test]
+async fn sqlx_text_format_test() {
+    let mut conn = establish_connection().await;
+    let rows: Vec<PgRow> = sqlx::raw_sql("SELECT ST_SetSRID(ST_MakePoint(1, 2), 4326)")
+        .fetch_all(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(Point::new(1.0, 2.0, Some(4326)), rows[0].get::<Point, _>(0));
+    // the text format can not be borrowed
+    assert!(rows[0].try_get::<EwkbRef, _>(0).is_err());
+    assert_eq!(
+        Point::new(1.0, 2.0, Some(4326)),
+        rows[0].get::<EwkbBuf, _>(0).decode::<Point>().unwrap()
+    );
+}
+
+#[tokio::test]
+async fn sqlx_srid_test() {
+    let mut conn = establish_connection().await;
+    let found: Srid<Point, 4326> = sqlx::query_scalar("SELECT ST_SetSRID($1::geometry, 4326)")
+        .bind(Point::new(1.0, 2.0, None))
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(Point::new(1.0, 2.0, Some(4326)), *found);
+    let wrong_srid = sqlx::query_scalar::<_, Srid<Point, 4326>>("SELECT $1::geometry")
+        .bind(Point::new(1.0, 2.0, Some(3857)))
+        .fetch_one(&mut conn)
+        .await;
+    assert!(wrong_srid.is_err());
+}
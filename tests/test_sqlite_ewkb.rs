@@ -0,0 +1,77 @@
+#![cfg(feature = "sqlite")]
+//! Submodule to test the use the `EwkbBuf` type with SQLite backend.
+
+#[macro_use]
+extern crate diesel;
+
+use diesel::SqliteConnection;
+
+use diesel::Connection;
+use diesel::ExpressionMethods;
+use diesel::QueryDsl;
+use diesel::RunQueryDsl;
+
+use postgis_diesel::types::{BoundingBox, EwkbBuf, GeometryType, LineString, Point};
+
+table! {
+    use postgis_diesel::sql_types::*;
+    use diesel::sql_types::*;
+    ewkb_samples (id) {
+        id -> Int4,
+        geometry -> Geometry,
+    }
+}
+
+fn establish_sqlite_connection() -> SqliteConnection {
+    let mut conn = SqliteConnection::establish(":memory:").expect("Error connecting to sqlite");
+    diesel::sql_query(
+        "CREATE TABLE ewkb_samples
+(
+    id                INTEGER PRIMARY KEY AUTOINCREMENT,
+    geometry          BLOB NOT NULL
+)",
+    )
+    .execute(&mut conn)
+    .unwrap();
+    conn
+}
+
+#[test]
+fn ewkb_buf_test_sqlite() {
+    let mut conn = establish_sqlite_connection();
+    let mut line = LineString::new(Some(4326));
+    line.add_points((0..50_000).map(|i| Point::new(f64::from(i), f64::from(-i), Some(4326))))
+        .unwrap();
+    diesel::insert_into(ewkb_samples::table)
+        .values(ewkb_samples::geometry.eq(&line))
+        .execute(&mut conn)
+        .unwrap();
+    let ewkb: EwkbBuf = ewkb_samples::table
+        .select(ewkb_samples::geometry)
+        .first(&mut conn)
+        .unwrap();
+    let view = ewkb.as_ewkb_ref();
+    assert_eq!(GeometryType::LineString, view.geometry_type().unwrap());
+    assert_eq!(50_000, view.points().unwrap().len());
+    assert_eq!(
+        Some(BoundingBox {
+            xmin: 0.0,
+            ymin: -49_999.0,
+            xmax: 49_999.0,
+            ymax: 0.0
+        }),
+        view.bbox().unwrap()
+    );
+    assert_eq!(line, ewkb.decode::<LineString<Point>>().unwrap());
+    // written back as it is
+    diesel::insert_into(ewkb_samples::table)
+        .values(ewkb_samples::geometry.eq(&ewkb))
+        .execute(&mut conn)
+        .unwrap();
+    let copied: LineString<Point> = ewkb_samples::table
+        .select(ewkb_samples::geometry)
+        .filter(ewkb_samples::id.eq(2))
+        .first(&mut conn)
+        .unwrap();
+    assert_eq!(line, copied);
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "sqlite")]
+//! Submodule to test the use the `FlatGeometry` type with SQLite backend.
+
+#[macro_use]
+extern crate diesel;
+
+use diesel::SqliteConnection;
+
+use diesel::Connection;
+use diesel::ExpressionMethods;
+use diesel::QueryDsl;
+use diesel::RunQueryDsl;
+
+use postgis_diesel::types::{FlatGeometry, GeometryType, MultiPolygon, Point, Polygon};
+
+table! {
+    use postgis_diesel::sql_types::*;
+    use diesel::sql_types::*;
+    flat_samples (id) {
+        id -> Int4,
+        geometry -> Geometry,
+    }
+}
+
+fn establish_sqlite_connection() -> SqliteConnection {
+    let mut conn = SqliteConnection::establish(":memory:").expect("Error connecting to sqlite");
+    diesel::sql_query(
+        "CREATE TABLE flat_samples
+(
+    id                INTEGER PRIMARY KEY AUTOINCREMENT,
+    geometry          BLOB NOT NULL
+)",
+    )
+    .execute(&mut conn)
+    .unwrap();
+    conn
+}
+
+fn new_square(offset: f64) -> Polygon<Point> {
+    let mut polygon = Polygon::new(Some(4326));
+    polygon
+        .add_points([
+            Point::new(offset, offset, Some(4326)),
+            Point::new(offset + 1.0, offset, Some(4326)),
+            Point::new(offset + 1.0, offset + 1.0, Some(4326)),
+            Point::new(offset, offset + 1.0, Some(4326)),
+            Point::new(offset, offset, Some(4326)),
+        ])
+        .unwrap();
+    polygon
+}
+
+#[test]
+fn flat_geometry_test_sqlite() {
+    let mut conn = establish_sqlite_connection();
+    let multipolygon = MultiPolygon {
+        polygons: (0..1000).map(|i| new_square(f64::from(i) * 2.0)).collect(),
+        srid: Some(4326),
+    };
+    diesel::insert_into(flat_samples::table)
+        .values(flat_samples::geometry.eq(&multipolygon))
+        .execute(&mut conn)
+        .unwrap();
+    let flat: FlatGeometry = flat_samples::table
+        .select(flat_samples::geometry)
+        .first(&mut conn)
+        .unwrap();
+    assert_eq!(GeometryType::MultiPolygon, flat.geometry_type);
+    assert_eq!(Some(4326), flat.srid);
+    assert_eq!(5000, flat.num_coordinates());
+    assert_eq!(1000, flat.num_rings());
+    assert_eq!(1000, flat.num_polygons());
+    assert_eq!(&[2.0, 2.0, 3.0, 2.0], &flat.ring(1).unwrap()[..4]);
+    // written back as flat geometry
+    diesel::insert_into(flat_samples::table)
+        .values(flat_samples::geometry.eq(&flat))
+        .execute(&mut conn)
+        .unwrap();
+    let copied: MultiPolygon<Point> = flat_samples::table
+        .select(flat_samples::geometry)
+        .filter(flat_samples::id.eq(2))
+        .first(&mut conn)
+        .unwrap();
+    assert_eq!(multipolygon, copied);
+}
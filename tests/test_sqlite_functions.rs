@@ -0,0 +1,194 @@
+#![cfg(feature = "sqlite")]
+//! Submodule to test the spatial functions registered on a SQLite connection.
+
+#[macro_use]
+extern crate diesel;
+
+use diesel::SqliteConnection;
+
+use diesel::Connection;
+use diesel::ExpressionMethods;
+use diesel::QueryDsl;
+use diesel::RunQueryDsl;
+
+use postgis_diesel::functions::*;
+use postgis_diesel::functions_nullable;
+use postgis_diesel::operators::intersects_2d;
+use postgis_diesel::sqlite_functions::register_functions;
+use postgis_diesel::types::{LineString, Point, Polygon};
+
+#[derive(Insertable)]
+#[diesel(table_name = places)]
+struct NewPlace {
+    name: String,
+    location: Point,
+    area: Option<Polygon<Point>>,
+}
+
+table! {
+    use postgis_diesel::sql_types::*;
+    use diesel::sql_types::*;
+    places (id) {
+        id -> Int4,
+        name -> Text,
+        location -> Geometry,
+        area -> Nullable<Geometry>,
+    }
+}
+
+fn establish_sqlite_connection() -> SqliteConnection {
+    let mut conn = SqliteConnection::establish(":memory:").expect("Error connecting to sqlite");
+    register_functions(&mut conn).unwrap();
+    diesel::sql_query(
+        "CREATE TABLE places
+(
+    id                INTEGER PRIMARY KEY AUTOINCREMENT,
+    name              TEXT NOT NULL,
+    location          BLOB NOT NULL,
+    area              BLOB
+)",
+    )
+    .execute(&mut conn)
+    .unwrap();
+    diesel::insert_into(places::table)
+        .values(&vec![
+            NewPlace {
+                name: "square".to_string(),
+                location: Point::new(1.0, 1.0, Some(4326)),
+                area: Some(new_square(0.0, 2.0)),
+            },
+            NewPlace {
+                name: "far".to_string(),
+                location: Point::new(10.0, 10.0, Some(4326)),
+                area: Some(new_square(10.0, 14.0)),
+            },
+            NewPlace {
+                name: "unknown".to_string(),
+                location: Point::new(3.0, 4.0, Some(4326)),
+                area: None,
+            },
+        ])
+        .execute(&mut conn)
+        .unwrap();
+    conn
+}
+
+fn new_square(min: f64, max: f64) -> Polygon<Point> {
+    let mut polygon = Polygon::new(Some(4326));
+    polygon
+        .add_points([
+            Point::new(min, min, Some(4326)),
+            Point::new(max, min, Some(4326)),
+            Point::new(max, max, Some(4326)),
+            Point::new(min, max, Some(4326)),
+            Point::new(min, min, Some(4326)),
+        ])
+        .unwrap();
+    polygon
+}
+
+#[test]
+fn accessors_test_sqlite() {
+    let mut conn = establish_sqlite_connection();
+    let found: Vec<(f64, f64, i32)> = places::table
+        .select((
+            st_x(places::location),
+            st_y(places::location),
+            st_srid(places::location),
+        ))
+        .order_by(places::id)
+        .load(&mut conn)
+        .unwrap();
+    assert_eq!(
+        vec![(1.0, 1.0, 4326), (10.0, 10.0, 4326), (3.0, 4.0, 4326)],
+        found
+    );
+    let not_a_point = places::table
+        .select(functions_nullable::st_x(places::area))
+        .first::<Option<f64>>(&mut conn);
+    assert!(not_a_point.is_err());
+}
+
+#[test]
+fn measures_test_sqlite() {
+    let mut conn = establish_sqlite_connection();
+    let areas: Vec<Option<f64>> = places::table
+        .select(functions_nullable::st_area(places::area))
+        .order_by(places::id)
+        .load(&mut conn)
+        .unwrap();
+    assert_eq!(vec![Some(4.0), Some(16.0), None], areas);
+    let mut line = LineString::new(Some(4326));
+    line.add_points([
+        Point::new(0.0, 0.0, Some(4326)),
+        Point::new(3.0, 4.0, Some(4326)),
+    ])
+    .unwrap();
+    let length = diesel::select(st_length(diesel::IntoSql::into_sql::<
+        postgis_diesel::sql_types::Geometry,
+    >(line)))
+    .get_result::<f64>(&mut conn)
+    .unwrap();
+    assert_eq!(5.0, length);
+    let distance: f64 = places::table
+        .select(st_distance(
+            places::location,
+            Point::new(0.0, 0.0, Some(4326)),
+        ))
+        .filter(places::name.eq("unknown"))
+        .first(&mut conn)
+        .unwrap();
+    assert_eq!(5.0, distance);
+}
+
+#[test]
+fn predicates_test_sqlite() {
+    let mut conn = establish_sqlite_connection();
+    let intersecting: Vec<String> = places::table
+        .select(places::name)
+        .filter(functions_nullable::st_intersects(
+            places::area,
+            Some(new_square(1.0, 3.0)),
+        ))
+        .load(&mut conn)
+        .unwrap();
+    assert_eq!(vec!["square".to_string()], intersecting);
+    let containing: Vec<String> = places::table
+        .select(places::name)
+        .filter(functions_nullable::st_contains(
+            places::area,
+            Some(Point::new(12.0, 12.0, Some(4326))),
+        ))
+        .load(&mut conn)
+        .unwrap();
+    assert_eq!(vec!["far".to_string()], containing);
+    let bbox_intersecting: Vec<String> = places::table
+        .select(places::name)
+        .filter(intersects_2d(places::location, new_square(2.0, 5.0)))
+        .load(&mut conn)
+        .unwrap();
+    assert_eq!(vec!["unknown".to_string()], bbox_intersecting);
+    let near: Vec<String> = places::table
+        .select(places::name)
+        .filter(st_d_within(
+            places::location,
+            Point::new(0.0, 0.0, Some(4326)),
+            5.0,
+        ))
+        .order_by(places::id)
+        .load(&mut conn)
+        .unwrap();
+    assert_eq!(vec!["square".to_string(), "unknown".to_string()], near);
+}
+
+#[test]
+fn mixed_srid_test_sqlite() {
+    let mut conn = establish_sqlite_connection();
+    let result = places::table
+        .select(st_distance(
+            places::location,
+            Point::new(0.0, 0.0, Some(3857)),
+        ))
+        .load::<f64>(&mut conn);
+    assert!(result.is_err());
+}
@@ -0,0 +1,200 @@
+#![cfg(feature = "sqlite")]
+//! Submodule to test the use of GeoPackage geometries with SQLite backend.
+
+#[macro_use]
+extern crate diesel;
+
+use diesel::SqliteConnection;
+
+use diesel::Connection;
+use diesel::ExpressionMethods;
+use diesel::QueryDsl;
+use diesel::RunQueryDsl;
+
+use postgis_diesel::geopackage::{create_metadata_tables, gpkg_contents, gpkg_geometry_columns};
+use postgis_diesel::types::{GeometryContainer, LineString, Point, PointZ, Polygon};
+
+#[derive(Insertable)]
+#[diesel(table_name = gpkg_samples)]
+struct NewGeometrySample {
+    name: String,
+    geom: Option<GeometryContainer<Point>>,
+}
+
+#[derive(Queryable, Debug, PartialEq)]
+#[diesel(table_name = gpkg_samples)]
+struct GeometrySample {
+    fid: i32,
+    name: String,
+    geom: Option<GeometryContainer<Point>>,
+}
+
+table! {
+    use postgis_diesel::sql_types::*;
+    use diesel::sql_types::*;
+    gpkg_samples (fid) {
+        fid -> Int4,
+        name -> Text,
+        geom -> Nullable<GeoPackageGeometry>,
+    }
+}
+
+table! {
+    use postgis_diesel::sql_types::*;
+    use diesel::sql_types::*;
+    gpkg_z_samples (fid) {
+        fid -> Int4,
+        geom -> GeoPackageGeometry,
+    }
+}
+
+fn new_point(x: f64, y: f64) -> Point {
+    Point::new(x, y, Some(4326))
+}
+
+fn establish_geopackage() -> SqliteConnection {
+    let mut conn = SqliteConnection::establish(":memory:").expect("Error connecting to sqlite");
+    create_metadata_tables(&mut conn).unwrap();
+    diesel::sql_query(
+        "CREATE TABLE gpkg_samples
+(
+    fid               INTEGER PRIMARY KEY AUTOINCREMENT,
+    name              TEXT NOT NULL,
+    geom              GEOMETRY
+)",
+    )
+    .execute(&mut conn)
+    .unwrap();
+    diesel::insert_into(gpkg_contents::table)
+        .values((
+            gpkg_contents::table_name.eq("gpkg_samples"),
+            gpkg_contents::data_type.eq("features"),
+            gpkg_contents::identifier.eq("samples"),
+            gpkg_contents::srs_id.eq(4326),
+        ))
+        .execute(&mut conn)
+        .unwrap();
+    diesel::insert_into(gpkg_geometry_columns::table)
+        .values((
+            gpkg_geometry_columns::table_name.eq("gpkg_samples"),
+            gpkg_geometry_columns::column_name.eq("geom"),
+            gpkg_geometry_columns::geometry_type_name.eq("GEOMETRY"),
+            gpkg_geometry_columns::srs_id.eq(4326),
+            gpkg_geometry_columns::z.eq(0),
+            gpkg_geometry_columns::m.eq(0),
+        ))
+        .execute(&mut conn)
+        .unwrap();
+    conn
+}
+
+#[test]
+fn geopackage_metadata_test_sqlite() {
+    let mut conn = establish_geopackage();
+    let application_id: i32 = diesel::select(diesel::dsl::sql::<diesel::sql_types::Integer>(
+        "application_id FROM pragma_application_id",
+    ))
+    .first(&mut conn)
+    .unwrap();
+    // "GPKG" in ASCII
+    assert_eq!(0x47504B47, application_id);
+    let contents: Vec<(String, Option<i32>)> = gpkg_contents::table
+        .select((gpkg_contents::table_name, gpkg_contents::srs_id))
+        .load(&mut conn)
+        .unwrap();
+    assert_eq!(vec![("gpkg_samples".to_string(), Some(4326))], contents);
+    let columns: Vec<(String, String)> = gpkg_geometry_columns::table
+        .select((
+            gpkg_geometry_columns::column_name,
+            gpkg_geometry_columns::geometry_type_name,
+        ))
+        .filter(gpkg_geometry_columns::table_name.eq("gpkg_samples"))
+        .load(&mut conn)
+        .unwrap();
+    assert_eq!(vec![("geom".to_string(), "GEOMETRY".to_string())], columns);
+}
+
+#[test]
+fn geopackage_geometry_test_sqlite() {
+    let mut conn = establish_geopackage();
+    let mut linestring = LineString::new(Some(4326));
+    linestring
+        .add_points([new_point(72.0, 64.0), new_point(73.0, 65.0)])
+        .unwrap();
+    let mut polygon = Polygon::new(Some(4326));
+    polygon
+        .add_points([
+            new_point(72.0, 64.0),
+            new_point(73.0, 65.0),
+            new_point(71.0, 62.0),
+            new_point(72.0, 64.0),
+        ])
+        .unwrap();
+    let samples = vec![
+        NewGeometrySample {
+            name: "point".to_string(),
+            geom: Some(GeometryContainer::Point(new_point(72.0, 64.0))),
+        },
+        NewGeometrySample {
+            name: "linestring".to_string(),
+            geom: Some(GeometryContainer::LineString(linestring)),
+        },
+        NewGeometrySample {
+            name: "polygon".to_string(),
+            geom: Some(GeometryContainer::Polygon(polygon)),
+        },
+        NewGeometrySample {
+            name: "empty".to_string(),
+            geom: Some(GeometryContainer::LineString(LineString::new(Some(4326)))),
+        },
+        NewGeometrySample {
+            name: "null".to_string(),
+            geom: None,
+        },
+    ];
+    diesel::insert_into(gpkg_samples::table)
+        .values(&samples)
+        .execute(&mut conn)
+        .unwrap();
+    let found_samples: Vec<GeometrySample> = gpkg_samples::table
+        .order_by(gpkg_samples::fid)
+        .load(&mut conn)
+        .unwrap();
+    assert_eq!(samples.len(), found_samples.len());
+    for (sample, found) in samples.iter().zip(found_samples.iter()) {
+        assert_eq!(sample.name, found.name);
+        assert_eq!(sample.geom, found.geom);
+    }
+    let blob: Vec<u8> = gpkg_samples::table
+        .select(diesel::dsl::sql::<diesel::sql_types::Binary>("geom"))
+        .filter(gpkg_samples::name.eq("polygon"))
+        .first(&mut conn)
+        .unwrap();
+    assert_eq!(b"GP", &blob[..2]);
+    // little endian with a XY envelope
+    assert_eq!(0b0000_0011, blob[3]);
+    let empty_flags: Vec<u8> = gpkg_samples::table
+        .select(diesel::dsl::sql::<diesel::sql_types::Binary>("geom"))
+        .filter(gpkg_samples::name.eq("empty"))
+        .first(&mut conn)
+        .unwrap();
+    assert_eq!(0b0001_0001, empty_flags[3]);
+}
+
+#[test]
+fn geopackage_z_geometry_test_sqlite() {
+    let mut conn = establish_geopackage();
+    diesel::sql_query("CREATE TABLE gpkg_z_samples (fid INTEGER PRIMARY KEY, geom POINT NOT NULL)")
+        .execute(&mut conn)
+        .unwrap();
+    let point = PointZ::new(72.0, 64.0, 30.0, Some(4326));
+    diesel::insert_into(gpkg_z_samples::table)
+        .values(gpkg_z_samples::geom.eq(point))
+        .execute(&mut conn)
+        .unwrap();
+    let found: PointZ = gpkg_z_samples::table
+        .select(gpkg_z_samples::geom)
+        .first(&mut conn)
+        .unwrap();
+    assert_eq!(point, found);
+}
@@ -0,0 +1,184 @@
+#![cfg(feature = "sqlite")]
+//! Submodule to test the R*Tree index of a geometry column with SQLite backend.
+
+#[macro_use]
+extern crate diesel;
+
+use diesel::sql_types::{BigInt, Integer};
+use diesel::SqliteConnection;
+
+use diesel::Connection;
+use diesel::ExpressionMethods;
+use diesel::QueryDsl;
+use diesel::RunQueryDsl;
+
+use postgis_diesel::operators::intersects_2d;
+use postgis_diesel::rtree::RTreeIndex;
+use postgis_diesel::sqlite_functions::register_functions;
+use postgis_diesel::types::{LineString, Point, Polygon};
+
+const SAMPLES_GEOM: RTreeIndex = RTreeIndex::new("rtree_samples", "geom");
+
+table! {
+    use postgis_diesel::sql_types::*;
+    use diesel::sql_types::*;
+    rtree_samples (id) {
+        id -> Integer,
+        name -> Text,
+        geom -> Nullable<Geometry>,
+    }
+}
+
+#[derive(QueryableByName)]
+struct Count {
+    #[diesel(sql_type = BigInt)]
+    count: i64,
+}
+
+#[derive(QueryableByName)]
+struct Id {
+    #[diesel(sql_type = Integer)]
+    id: i32,
+}
+
+fn establish_sqlite_connection() -> SqliteConnection {
+    let mut conn = SqliteConnection::establish(":memory:").expect("Error connecting to sqlite");
+    register_functions(&mut conn).unwrap();
+    diesel::sql_query(
+        "CREATE TABLE rtree_samples
+(
+    id                INTEGER PRIMARY KEY AUTOINCREMENT,
+    name              TEXT NOT NULL,
+    geom              BLOB
+)",
+    )
+    .execute(&mut conn)
+    .unwrap();
+    conn
+}
+
+fn insert(conn: &mut SqliteConnection, name: &str, x: f64, y: f64) {
+    diesel::insert_into(rtree_samples::table)
+        .values((
+            rtree_samples::name.eq(name),
+            rtree_samples::geom.eq(Some(Point::new(x, y, Some(4326)))),
+        ))
+        .execute(conn)
+        .unwrap();
+}
+
+fn indexed_rows(conn: &mut SqliteConnection) -> i64 {
+    diesel::sql_query(format!(
+        "SELECT COUNT(*) AS count FROM {}",
+        SAMPLES_GEOM.name()
+    ))
+    .get_result::<Count>(conn)
+    .unwrap()
+    .count
+}
+
+fn new_square(min: f64, max: f64) -> Polygon<Point> {
+    let mut polygon = Polygon::new(Some(4326));
+    polygon
+        .add_points([
+            Point::new(min, min, Some(4326)),
+            Point::new(max, min, Some(4326)),
+            Point::new(max, max, Some(4326)),
+            Point::new(min, max, Some(4326)),
+            Point::new(min, min, Some(4326)),
+        ])
+        .unwrap();
+    polygon
+}
+
+fn find(conn: &mut SqliteConnection, area: Polygon<Point>) -> Vec<String> {
+    let found: Vec<String> = rtree_samples::table
+        .select(rtree_samples::name)
+        .filter(SAMPLES_GEOM.intersects_2d(rtree_samples::geom, Some(area.clone())))
+        .order_by(rtree_samples::id)
+        .load(conn)
+        .unwrap();
+    let scanned: Vec<String> = rtree_samples::table
+        .select(rtree_samples::name)
+        .filter(intersects_2d(rtree_samples::geom, Some(area)))
+        .order_by(rtree_samples::id)
+        .load(conn)
+        .unwrap();
+    assert_eq!(scanned, found);
+    found
+}
+
+#[test]
+fn rtree_index_test_sqlite() {
+    let mut conn = establish_sqlite_connection();
+    insert(&mut conn, "before", 1.0, 1.0);
+    diesel::insert_into(rtree_samples::table)
+        .values(rtree_samples::name.eq("no geometry"))
+        .execute(&mut conn)
+        .unwrap();
+    SAMPLES_GEOM.create(&mut conn).unwrap();
+    assert_eq!(1, indexed_rows(&mut conn));
+    insert(&mut conn, "after", 5.0, 5.0);
+    let mut line = LineString::new(Some(4326));
+    line.add_points([
+        Point::new(8.0, 0.0, Some(4326)),
+        Point::new(8.0, 20.0, Some(4326)),
+    ])
+    .unwrap();
+    diesel::insert_into(rtree_samples::table)
+        .values((
+            rtree_samples::name.eq("line"),
+            rtree_samples::geom.eq(Some(line)),
+        ))
+        .execute(&mut conn)
+        .unwrap();
+    assert_eq!(3, indexed_rows(&mut conn));
+    let bbox = diesel::sql_query(format!(
+        "SELECT id FROM {} WHERE minx = 8 AND maxx = 8 AND miny = 0 AND maxy = 20",
+        SAMPLES_GEOM.name()
+    ))
+    .load::<Id>(&mut conn)
+    .unwrap();
+    assert_eq!(vec![4], bbox.iter().map(|row| row.id).collect::<Vec<_>>());
+
+    assert_eq!(
+        vec!["before", "after"],
+        find(&mut conn, new_square(0.0, 6.0))
+    );
+    assert_eq!(
+        vec!["after", "line"],
+        find(&mut conn, new_square(4.0, 10.0))
+    );
+    assert!(find(&mut conn, new_square(20.0, 30.0)).is_empty());
+}
+
+#[test]
+fn rtree_index_sync_test_sqlite() {
+    let mut conn = establish_sqlite_connection();
+    SAMPLES_GEOM.create(&mut conn).unwrap();
+    insert(&mut conn, "moved", 1.0, 1.0);
+    insert(&mut conn, "deleted", 2.0, 2.0);
+    insert(&mut conn, "cleared", 3.0, 3.0);
+    diesel::update(rtree_samples::table.filter(rtree_samples::name.eq("moved")))
+        .set(rtree_samples::geom.eq(Some(Point::new(50.0, 50.0, Some(4326)))))
+        .execute(&mut conn)
+        .unwrap();
+    diesel::delete(rtree_samples::table.filter(rtree_samples::name.eq("deleted")))
+        .execute(&mut conn)
+        .unwrap();
+    diesel::update(rtree_samples::table.filter(rtree_samples::name.eq("cleared")))
+        .set(rtree_samples::geom.eq(None::<Point>))
+        .execute(&mut conn)
+        .unwrap();
+    assert_eq!(1, indexed_rows(&mut conn));
+    assert!(find(&mut conn, new_square(0.0, 10.0)).is_empty());
+    assert_eq!(vec!["moved"], find(&mut conn, new_square(40.0, 60.0)));
+
+    SAMPLES_GEOM.drop(&mut conn).unwrap();
+    insert(&mut conn, "unindexed", 1.0, 1.0);
+    assert!(
+        diesel::sql_query(format!("SELECT * FROM {}", SAMPLES_GEOM.name()))
+            .execute(&mut conn)
+            .is_err()
+    );
+}
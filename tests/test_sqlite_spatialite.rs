@@ -0,0 +1,188 @@
+#![cfg(feature = "sqlite")]
+//! Submodule to test the SpatiaLite BLOB format of the `SpatiaLiteGeometry` type with SQLite
+//! backend.
+
+#[macro_use]
+extern crate diesel;
+
+use std::convert::TryInto;
+
+use diesel::{Connection, ExpressionMethods, QueryDsl, RunQueryDsl, SqliteConnection};
+
+use postgis_diesel::types::*;
+
+table! {
+    use postgis_diesel::sql_types::*;
+    use diesel::sql_types::*;
+    spatialite_samples (id) {
+        id -> Int4,
+        name -> Text,
+        geometry -> SpatiaLiteGeometry,
+    }
+}
+
+fn establish_sqlite_connection() -> SqliteConnection {
+    let mut conn = SqliteConnection::establish(":memory:").expect("Error connecting to sqlite");
+    diesel::sql_query(
+        "CREATE TABLE spatialite_samples
+(
+    id                INTEGER PRIMARY KEY AUTOINCREMENT,
+    name              TEXT NOT NULL,
+    geometry          BLOB NOT NULL
+)",
+    )
+    .execute(&mut conn)
+    .unwrap();
+    conn
+}
+
+/// Stores the geometry, checks it is read back unchanged and returns the stored BLOB.
+macro_rules! round_trip {
+    ($conn:expr, $name:expr, $geometry:expr $(,)?) => {{
+        let geometry = $geometry;
+        diesel::insert_into(spatialite_samples::table)
+            .values((
+                spatialite_samples::name.eq($name),
+                spatialite_samples::geometry.eq(&geometry),
+            ))
+            .execute($conn)
+            .unwrap();
+        let found = spatialite_samples::table
+            .filter(spatialite_samples::name.eq($name))
+            .select(spatialite_samples::geometry)
+            .first($conn)
+            .unwrap();
+        assert_eq!(geometry, found, "{}", $name);
+        spatialite_samples::table
+            .filter(spatialite_samples::name.eq($name))
+            .select(diesel::dsl::sql::<diesel::sql_types::Binary>("geometry"))
+            .first::<Vec<u8>>($conn)
+            .unwrap()
+    }};
+}
+
+fn new_line<P: PointT>(points: Vec<P>) -> LineString<P> {
+    let mut line = LineString::new(Some(4326));
+    line.add_points(points).unwrap();
+    line
+}
+
+fn new_polygon() -> Polygon<Point> {
+    let mut polygon = Polygon::new(Some(4326));
+    polygon
+        .add_points([
+            Point::new(72.0, 64.0, Some(4326)),
+            Point::new(73.0, 65.0, Some(4326)),
+            Point::new(71.0, 62.0, Some(4326)),
+            Point::new(72.0, 64.0, Some(4326)),
+        ])
+        .unwrap();
+    polygon
+}
+
+#[test]
+fn spatialite_round_trip_test_sqlite() {
+    let mut conn = establish_sqlite_connection();
+    let point = |x: f64, y: f64| Point::new(x, y, Some(4326));
+    let mut multi_point = MultiPoint::new(Some(4326));
+    multi_point.add_points([point(72.0, 64.0), point(73.0, 64.0)]);
+    let mut multi_line = MultiLineString::new(Some(4326));
+    multi_line.lines = vec![
+        new_line(vec![point(72.0, 64.0), point(73.0, 64.0)]),
+        new_line(vec![point(74.0, 64.0), point(75.0, 64.0)]),
+    ];
+    let mut multi_polygon = MultiPolygon::new(Some(4326));
+    multi_polygon.polygons = vec![new_polygon(), new_polygon()];
+    let mut collection = GeometryCollection::new(Some(4326));
+    collection.geometries = vec![
+        GeometryContainer::Point(point(72.0, 64.0)),
+        GeometryContainer::Polygon(new_polygon()),
+    ];
+
+    // name, SpatiaLite class code and stored BLOB
+    let cases = vec![
+        (
+            "point",
+            1,
+            round_trip!(&mut conn, "point", point(72.0, 64.0)),
+        ),
+        (
+            "pointz",
+            1001,
+            round_trip!(
+                &mut conn,
+                "pointz",
+                PointZ::new(72.0, 64.0, 10.0, Some(4326)),
+            ),
+        ),
+        (
+            "pointm",
+            2001,
+            round_trip!(
+                &mut conn,
+                "pointm",
+                PointM::new(72.0, 64.0, 11.0, Some(4326)),
+            ),
+        ),
+        (
+            "pointzm",
+            3001,
+            round_trip!(
+                &mut conn,
+                "pointzm",
+                PointZM::new(72.0, 64.0, 10.0, 11.0, Some(4326)),
+            ),
+        ),
+        (
+            "linestring",
+            2,
+            round_trip!(
+                &mut conn,
+                "linestring",
+                new_line(vec![point(72.0, 64.0), point(73.0, 64.0)]),
+            ),
+        ),
+        (
+            "polygon",
+            3,
+            round_trip!(&mut conn, "polygon", new_polygon()),
+        ),
+        (
+            "multipoint",
+            4,
+            round_trip!(&mut conn, "multipoint", multi_point),
+        ),
+        (
+            "multiline",
+            5,
+            round_trip!(&mut conn, "multiline", multi_line),
+        ),
+        (
+            "multipolygon",
+            6,
+            round_trip!(&mut conn, "multipolygon", multi_polygon),
+        ),
+        (
+            "collection",
+            7,
+            round_trip!(&mut conn, "collection", collection),
+        ),
+    ];
+    for (name, class, blob) in cases {
+        assert_eq!(0x00, blob[0], "{}", name);
+        assert_eq!(
+            4326,
+            u32::from_le_bytes(blob[2..6].try_into().unwrap()),
+            "{}",
+            name
+        );
+        assert_eq!(0x7C, blob[38], "{}", name);
+        assert_eq!(
+            class,
+            u32::from_le_bytes(blob[39..43].try_into().unwrap()),
+            "{}",
+            name
+        );
+        assert_eq!(0xFE, blob[blob.len() - 1], "{}", name);
+    }
+}
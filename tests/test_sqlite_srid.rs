@@ -0,0 +1,106 @@
+#![cfg(feature = "sqlite")]
+//! Submodule to test the use of the `Srid` wrapper with SQLite backend.
+
+#[macro_use]
+extern crate diesel;
+
+use diesel::SqliteConnection;
+
+use diesel::Connection;
+use diesel::ExpressionMethods;
+use diesel::RunQueryDsl;
+
+use postgis_diesel::errors::Error;
+use postgis_diesel::types::{LineString, Point, Srid};
+
+#[derive(Insertable)]
+#[diesel(table_name = srid_samples)]
+struct NewSridSample {
+    name: String,
+    point: Srid<Point, 4326>,
+    linestring: Option<Srid<LineString<Point>, 4326>>,
+}
+
+#[derive(Queryable, Debug, PartialEq)]
+#[diesel(table_name = srid_samples)]
+struct SridSample {
+    id: i32,
+    name: String,
+    point: Srid<Point, 4326>,
+    linestring: Option<Srid<LineString<Point>, 4326>>,
+}
+
+table! {
+    use postgis_diesel::sql_types::*;
+    use diesel::sql_types::*;
+    srid_samples (id) {
+        id -> Int4,
+        name -> Text,
+        point -> Geometry,
+        linestring -> Nullable<Geometry>,
+    }
+}
+
+fn establish_sqlite_connection() -> SqliteConnection {
+    let mut conn = SqliteConnection::establish(":memory:").expect("Error connecting to sqlite");
+    diesel::sql_query(
+        "CREATE TABLE srid_samples
+(
+    id                INTEGER PRIMARY KEY AUTOINCREMENT,
+    name              TEXT NOT NULL,
+    point             BLOB NOT NULL,
+    linestring        BLOB
+)",
+    )
+    .execute(&mut conn)
+    .unwrap();
+    conn
+}
+
+fn new_line(srid: Option<u32>) -> LineString<Point> {
+    LineString::new(srid)
+        .add_points([Point::new(72.0, 64.0, srid), Point::new(73.0, 64.0, srid)])
+        .unwrap()
+        .to_owned()
+}
+
+#[test]
+fn srid_wrapper_test_sqlite() {
+    let mut conn = establish_sqlite_connection();
+    let sample = NewSridSample {
+        name: "without srid".to_string(),
+        point: Srid::new(Point::new(72.0, 64.0, None)).unwrap(),
+        linestring: Some(Srid::new(new_line(Some(4326))).unwrap()),
+    };
+    diesel::insert_into(srid_samples::table)
+        .values(&sample)
+        .execute(&mut conn)
+        .unwrap();
+    let found_sample: SridSample = srid_samples::table.first(&mut conn).unwrap();
+    assert_eq!(Point::new(72.0, 64.0, Some(4326)), *found_sample.point);
+    assert_eq!(
+        new_line(Some(4326)),
+        found_sample.linestring.unwrap().into_inner()
+    );
+}
+
+#[test]
+fn srid_wrapper_mismatch_test_sqlite() {
+    let mut conn = establish_sqlite_connection();
+    match Srid::<Point, 4326>::new(Point::new(72.0, 64.0, Some(3857))) {
+        Err(Error::IncompatibleSpatialReferenceSystemIdentifier { expected, actual }) => {
+            assert_eq!(Some(4326), expected);
+            assert_eq!(Some(3857), actual);
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+    diesel::insert_into(srid_samples::table)
+        .values((
+            srid_samples::name.eq("web mercator"),
+            srid_samples::point.eq(Point::new(72.0, 64.0, Some(3857))),
+        ))
+        .execute(&mut conn)
+        .unwrap();
+    let found_samples = srid_samples::table.get_results::<SridSample>(&mut conn);
+    assert!(found_samples.is_err());
+}
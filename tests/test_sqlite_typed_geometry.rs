@@ -0,0 +1,137 @@
+#![cfg(feature = "sqlite")]
+//! Submodule to test the use of the `TypedGeometry` SQL type with SQLite backend.
+
+#[macro_use]
+extern crate diesel;
+
+use diesel::SqliteConnection;
+
+use diesel::Connection;
+use diesel::ExpressionMethods;
+use diesel::RunQueryDsl;
+
+use postgis_diesel::types::{LineString, Point};
+
+#[derive(Insertable)]
+#[diesel(table_name = typed_samples)]
+struct NewTypedSample {
+    name: String,
+    point: Point,
+    linestring: Option<LineString<Point>>,
+}
+
+#[derive(Queryable, Debug, PartialEq)]
+#[diesel(table_name = typed_samples)]
+struct TypedSample {
+    id: i32,
+    name: String,
+    point: Point,
+    linestring: Option<LineString<Point>>,
+}
+
+table! {
+    use postgis_diesel::sql_types::*;
+    use diesel::sql_types::*;
+    typed_samples (id) {
+        id -> Int4,
+        name -> Text,
+        point -> TypedGeometry<PointKind, 4326>,
+        linestring -> Nullable<TypedGeometry<LineStringKind, 4326>>,
+    }
+}
+
+table! {
+    use postgis_diesel::sql_types::*;
+    use diesel::sql_types::*;
+    #[sql_name = "typed_samples"]
+    untyped_samples (id) {
+        id -> Int4,
+        name -> Text,
+        point -> Geometry,
+    }
+}
+
+fn establish_sqlite_connection() -> SqliteConnection {
+    let mut conn = SqliteConnection::establish(":memory:").expect("Error connecting to sqlite");
+    diesel::sql_query(
+        "CREATE TABLE typed_samples
+(
+    id                INTEGER PRIMARY KEY AUTOINCREMENT,
+    name              TEXT NOT NULL,
+    point             BLOB NOT NULL,
+    linestring        BLOB
+)",
+    )
+    .execute(&mut conn)
+    .unwrap();
+    conn
+}
+
+fn new_line(srid: Option<u32>) -> LineString<Point> {
+    LineString::new(srid)
+        .add_points([Point::new(72.0, 64.0, srid), Point::new(73.0, 64.0, srid)])
+        .unwrap()
+        .to_owned()
+}
+
+#[test]
+fn typed_geometry_test_sqlite() {
+    let mut conn = establish_sqlite_connection();
+    let samples = vec![
+        NewTypedSample {
+            name: "with line".to_string(),
+            point: Point::new(72.0, 64.0, Some(4326)),
+            linestring: Some(new_line(Some(4326))),
+        },
+        NewTypedSample {
+            name: "without line".to_string(),
+            point: Point::new(73.0, 64.0, Some(4326)),
+            linestring: None,
+        },
+    ];
+    diesel::insert_into(typed_samples::table)
+        .values(&samples)
+        .execute(&mut conn)
+        .unwrap();
+    let found_samples: Vec<TypedSample> = typed_samples::table.get_results(&mut conn).unwrap();
+    assert_eq!(2, found_samples.len());
+    assert_eq!(Some(new_line(Some(4326))), found_samples[0].linestring);
+    assert_eq!(None, found_samples[1].linestring);
+}
+
+#[test]
+fn typed_geometry_write_srid_test_sqlite() {
+    let mut conn = establish_sqlite_connection();
+    let wrong_srid = NewTypedSample {
+        name: "web mercator".to_string(),
+        point: Point::new(72.0, 64.0, Some(3857)),
+        linestring: None,
+    };
+    assert!(diesel::insert_into(typed_samples::table)
+        .values(&wrong_srid)
+        .execute(&mut conn)
+        .is_err());
+    let no_srid = NewTypedSample {
+        name: "no srid".to_string(),
+        point: Point::new(72.0, 64.0, Some(4326)),
+        linestring: Some(new_line(None)),
+    };
+    assert!(diesel::insert_into(typed_samples::table)
+        .values(&no_srid)
+        .execute(&mut conn)
+        .is_err());
+}
+
+#[test]
+fn typed_geometry_read_srid_test_sqlite() {
+    let mut conn = establish_sqlite_connection();
+    diesel::insert_into(untyped_samples::table)
+        .values((
+            untyped_samples::name.eq("web mercator"),
+            untyped_samples::point.eq(Point::new(72.0, 64.0, Some(3857))),
+        ))
+        .execute(&mut conn)
+        .unwrap();
+    let found_samples = typed_samples::table.get_results::<TypedSample>(&mut conn);
+    assert!(found_samples.is_err());
+}
@@ -0,0 +1,143 @@
+#![cfg(feature = "sqlx-sqlite")]
+//! Submodule to test the sqlx integration with SQLite backend.
+
+use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
+
+use postgis_diesel::types::*;
+
+async fn establish_pool() -> SqlitePool {
+    // Every connection to `sqlite::memory:` opens its own database.
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    sqlx::query(
+        "CREATE TABLE sqlx_samples
+(
+    id                INTEGER PRIMARY KEY AUTOINCREMENT,
+    point             BLOB NOT NULL,
+    point_zm          BLOB NOT NULL,
+    container         BLOB NOT NULL,
+    polygon           BLOB
+)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    pool
+}
+
+fn new_polygon() -> Polygon<Point> {
+    let mut polygon = Polygon::new(Some(4326));
+    polygon
+        .add_points([
+            Point::new(72.0, 64.0, Some(4326)),
+            Point::new(73.0, 65.0, Some(4326)),
+            Point::new(71.0, 62.0, Some(4326)),
+            Point::new(72.0, 64.0, Some(4326)),
+        ])
+        .unwrap();
+    polygon
+}
+
+#[derive(sqlx::FromRow, Debug, PartialEq)]
+struct Sample {
+    id: i64,
+    point: Point,
+    point_zm: PointZM,
+    container: GeometryContainer<Point>,
+    polygon: Option<Polygon<Point>>,
+}
+
+#[tokio::test]
+async fn sqlx_round_trip_test_sqlite() {
+    let pool = establish_pool().await;
+    let point = Point::new(72.0, 64.0, Some(4326));
+    let point_zm = PointZM::new(72.0, 64.0, 10.0, 20.0, None);
+    let container = GeometryContainer::Polygon(new_polygon());
+    for polygon in [Some(new_polygon()), None] {
+        sqlx::query(
+            "INSERT INTO sqlx_samples (point, point_zm, container, polygon) VALUES ($1, $2, $3, $4)",
+        )
+        .bind(point)
+        .bind(point_zm)
+        .bind(&container)
+        .bind(polygon)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+    let samples: Vec<Sample> = sqlx::query_as("SELECT * FROM sqlx_samples ORDER BY id")
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+    assert_eq!(
+        vec![
+            Sample {
+                id: 1,
+                point,
+                point_zm,
+                container: container.clone(),
+                polygon: Some(new_polygon()),
+            },
+            Sample {
+                id: 2,
+                point,
+                point_zm,
+                container,
+                polygon: None,
+            },
+        ],
+        samples
+    );
+    let wrong_type = sqlx::query_scalar::<_, Point>("SELECT container FROM sqlx_samples")
+        .fetch_one(&pool)
+        .await;
+    assert!(wrong_type.is_err());
+}
+
+#[tokio::test]
+async fn sqlx_srid_test_sqlite() {
+    let pool = establish_pool().await;
+    let point = Srid::<Point, 4326>::new(Point::new(72.0, 64.0, None)).unwrap();
+    sqlx::query("INSERT INTO sqlx_samples (point, point_zm, container) VALUES ($1, $2, $3)")
+        .bind(point)
+        .bind(PointZM::new(72.0, 64.0, 10.0, 20.0, Some(3857)))
+        .bind(GeometryContainer::Point(Point::new(1.0, 2.0, None)))
+        .execute(&pool)
+        .await
+        .unwrap();
+    let found = sqlx::query_scalar::<_, Srid<Point, 4326>>("SELECT point FROM sqlx_samples")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(Point::new(72.0, 64.0, Some(4326)), found.into_inner());
+    let wrong_srid =
+        sqlx::query_scalar::<_, Srid<PointZM, 4326>>("SELECT point_zm FROM sqlx_samples")
+            .fetch_one(&pool)
+            .await;
+    assert!(wrong_srid.is_err());
+}
+
+#[tokio::test]
+async fn sqlx_ewkb_test_sqlite() {
+    use sqlx::Row;
+
+    let pool = establish_pool().await;
+    sqlx::query("INSERT INTO sqlx_samples (point, point_zm, container) VALUES ($1, $2, $3)")
+        .bind(Point::new(72.0, 64.0, Some(4326)))
+        .bind(PointZM::new(72.0, 64.0, 10.0, 20.0, None))
+        .bind(EwkbBuf::from_geometry(&new_polygon()).unwrap())
+        .execute(&pool)
+        .await
+        .unwrap();
+    let row = sqlx::query("SELECT container FROM sqlx_samples")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    let ewkb: EwkbRef = row.try_get(0).unwrap();
+    assert_eq!(GeometryType::Polygon, ewkb.geometry_type().unwrap());
+    assert_eq!(4, ewkb.rings().unwrap().next().unwrap().unwrap().len());
+    assert_eq!(new_polygon(), ewkb.decode::<Polygon<Point>>().unwrap());
+}
@@ -6,6 +6,7 @@ use std::env;
 use std::sync::Once;
 
 use diesel::pg::PgConnection;
+use diesel::sql_types::Nullable;
 use diesel::{Connection, IntoSql};
 use diesel::{QueryDsl, RunQueryDsl};
 use dotenvy::dotenv;
@@ -964,6 +965,45 @@ fn within_test() {
     }
 }
 
+#[test]
+fn within_geography_test() {
+    let mut conn = initialize();
+    let polygon = Polygon::new(Some(4326))
+        .add_point(Point::new(0.0, 0.0, Some(4326)))
+        .unwrap()
+        .add_point(Point::new(0.0, 2.0, Some(4326)))
+        .unwrap()
+        .add_point(Point::new(2.0, 2.0, Some(4326)))
+        .unwrap()
+        .add_point(Point::new(2.0, 0.0, Some(4326)))
+        .unwrap()
+        .add_point(Point::new(0.0, 0.0, Some(4326)))
+        .unwrap()
+        .to_owned();
+    let found_samples: Vec<GeometrySample> = topo_rel_functions::table
+        .filter(
+            st_within(
+                as_geometry(None::<Point>.into_sql::<Nullable<Geography>>()),
+                as_geometry(Some(polygon.clone()).into_sql::<Nullable<Geography>>()),
+            )
+            .eq(true),
+        )
+        .get_results(&mut conn)
+        .unwrap();
+    assert_eq!(0, found_samples.len());
+    let found_samples: Vec<GeometrySample> = topo_rel_functions::table
+        .filter(st_within(
+            as_geometry(Some(Point::new(1.0, 1.0, Some(4326))).into_sql::<Nullable<Geography>>()),
+            as_geometry(Some(polygon).into_sql::<Nullable<Geography>>()),
+        ))
+        .get_results(&mut conn)
+        .unwrap();
+    assert_eq!(1, found_samples.len());
+    for gs in found_samples {
+        assert_eq!("topo_rel_test".to_string(), gs.name);
+    }
+}
+
 #[test]
 fn d_within_test() {
     let mut conn = initialize();
@@ -937,6 +937,42 @@ fn within_test() {
     }
 }
 
+#[test]
+fn within_geography_test() {
+    let mut conn = initialize();
+    let polygon = Polygon::new(Some(4326))
+        .add_point(Point::new(0.0, 0.0, Some(4326)))
+        .unwrap()
+        .add_point(Point::new(0.0, 2.0, Some(4326)))
+        .unwrap()
+        .add_point(Point::new(2.0, 2.0, Some(4326)))
+        .unwrap()
+        .add_point(Point::new(2.0, 0.0, Some(4326)))
+        .unwrap()
+        .add_point(Point::new(0.0, 0.0, Some(4326)))
+        .unwrap()
+        .to_owned();
+    let found_samples: Vec<GeometrySample> = topo_rel_functions::table
+        .filter(st_within(
+            as_geometry(Point::new(3.0, 1.0, Some(4326)).into_sql::<Geography>()),
+            as_geometry(polygon.clone().into_sql::<Geography>()),
+        ))
+        .get_results(&mut conn)
+        .unwrap();
+    assert_eq!(0, found_samples.len());
+    let found_samples: Vec<GeometrySample> = topo_rel_functions::table
+        .filter(st_within(
+            as_geometry(Point::new(1.0, 1.0, Some(4326)).into_sql::<Geography>()),
+            as_geometry(polygon.into_sql::<Geography>()),
+        ))
+        .get_results(&mut conn)
+        .unwrap();
+    assert_eq!(1, found_samples.len());
+    for gs in found_samples {
+        assert_eq!("topo_rel_test".to_string(), gs.name);
+    }
+}
+
 #[test]
 fn d_within_test() {
     let mut conn = initialize();